@@ -0,0 +1,33 @@
+#!/bin/zsh
+
+# Times header, section table and symbol table parsing on a large object,
+# sorting the section table by name, which looks every name up over and
+# over, and --strings, which looks up the section of every string.
+# writeork is a binary crate, so there's no library for a benchmark harness
+# to call into: the release executable is timed instead, with its output
+# thrown away. The fixture is generated here, so nothing large is committed.
+#
+# Usage: ./bench.sh [runs]
+
+runs=${1:-20}
+fixture=/tmp/writeork-bench.o
+
+cargo build --release || exit 1
+
+# One function and one variable per section: 20000 functions give some
+# 40000 sections and as many symbols.
+if [ ! -f $fixture ]; then
+    for i in {1..20000}; do
+        echo "int v$i = $i; int f$i(void) { return v$i; }";
+    done > /tmp/writeork-bench.c;
+    gcc -c -O1 -ffunction-sections -fdata-sections -o $fixture /tmp/writeork-bench.c || exit 1
+fi
+
+for mode in "-h" "-S" "-s" "-S --sort=name" "--strings"; do
+    start=$(date +%s%N);
+    for i in $(seq $runs); do
+        eval target/release/writeork $mode $fixture > /dev/null;
+    done;
+    end=$(date +%s%N);
+    echo "$mode: $(( (end - start) / runs / 1000 )) us per run";
+done;
@@ -0,0 +1 @@
+elf_dyn!(Elf32_Sword, Elf32_Word, Elf32_Addr, Elf32_Off);
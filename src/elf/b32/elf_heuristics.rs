@@ -0,0 +1 @@
+elf_heuristics!(Elf32_Addr);
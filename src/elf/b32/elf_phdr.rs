@@ -1 +1 @@
-elf_phdr!(Elf32_Half, Elf32_Word, Elf32_Word, Elf32_Addr, Elf32_Off);
+elf_phdr!(ELFCLASS32, Elf32_Half, Elf32_Word, Elf32_Word, Elf32_Addr, Elf32_Off);
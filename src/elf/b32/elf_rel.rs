@@ -0,0 +1 @@
+elf_rel!(Elf32_Word, Elf32_Sword, Elf32_Addr, 8);
@@ -0,0 +1 @@
+elf_sym!(ELFCLASS32, Elf32_Half, Elf32_Word, Elf32_Word, Elf32_Addr);
@@ -1,7 +1,13 @@
 use super::elf_ehdr::Elf_Ehdr as Elf32_Ehdr;
 use super::elf_phdr::Elf_Phdr as Elf32_Phdr;
+use super::elf_shdr::Elf_Shdr as Elf32_Shdr;
+use super::elf_sym::Elf_Sym as Elf32_Sym;
+use super::elf_dyn::Elf_Dyn as Elf32_Dyn;
 use super::super::elf_ident_named::EI_MAGIC_SIZE;
 use ::std::io::{Read, Seek};
 
 read_ehdr!(Elf32_Ehdr);
 read_phdrs!(Elf32_Phdr, Elf32_Ehdr);
+read_shdrs!(Elf32_Shdr, Elf32_Ehdr);
+read_syms!(Elf32_Sym, Elf32_Shdr);
+read_dyns!(Elf32_Dyn, Elf32_Phdr);
@@ -1,4 +1,9 @@
 pub mod primitive;
 pub mod elf_ehdr;
 pub mod elf_phdr;
+pub mod elf_shdr;
+pub mod elf_sym;
 pub mod helpers;
+pub mod elf_dyn;
+pub mod elf_heuristics;
+pub mod elf_rel;
@@ -5,3 +5,5 @@ pub type Elf32_Word = u32;
 pub type Elf32_Addr = u32;
 
 pub type Elf32_Off = u32;
+
+pub type Elf32_Sword = i32;
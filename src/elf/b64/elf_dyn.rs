@@ -0,0 +1 @@
+elf_dyn!(Elf64_Sxword, Elf64_Xword, Elf64_Addr, Elf64_Off);
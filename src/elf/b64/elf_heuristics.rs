@@ -0,0 +1 @@
+elf_heuristics!(Elf64_Addr);
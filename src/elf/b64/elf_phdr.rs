@@ -1 +1 @@
-elf_phdr!(Elf64_Half, Elf64_Word, Elf64_Xword, Elf64_Addr, Elf64_Off);
+elf_phdr!(ELFCLASS64, Elf64_Half, Elf64_Word, Elf64_Xword, Elf64_Addr, Elf64_Off);
@@ -0,0 +1 @@
+elf_rel!(Elf64_Xword, Elf64_Sxword, Elf64_Addr, 32);
@@ -0,0 +1 @@
+elf_sym!(ELFCLASS64, Elf64_Half, Elf64_Word, Elf64_Xword, Elf64_Addr);
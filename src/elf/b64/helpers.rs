@@ -1,7 +1,13 @@
 use super::elf_ehdr::Elf_Ehdr as Elf64_Ehdr;
 use super::elf_phdr::Elf_Phdr as Elf64_Phdr;
+use super::elf_shdr::Elf_Shdr as Elf64_Shdr;
+use super::elf_sym::Elf_Sym as Elf64_Sym;
+use super::elf_dyn::Elf_Dyn as Elf64_Dyn;
 use super::super::elf_ident_named::EI_MAGIC_SIZE;
 use ::std::io::{Read, Seek};
 
 read_ehdr!(Elf64_Ehdr);
 read_phdrs!(Elf64_Phdr, Elf64_Ehdr);
+read_shdrs!(Elf64_Shdr, Elf64_Ehdr);
+read_syms!(Elf64_Sym, Elf64_Shdr);
+read_dyns!(Elf64_Dyn, Elf64_Phdr);
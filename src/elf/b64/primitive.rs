@@ -7,3 +7,5 @@ pub type Elf64_Addr = u64;
 pub type Elf64_Off = u64;
 
 pub type Elf64_Xword = u64;
+
+pub type Elf64_Sxword = i64;
@@ -0,0 +1,82 @@
+use ::std::io::{self, Write};
+use ::to_host::Endianness;
+use super::helpers::get_u32;
+
+// .ARM.exidx (SHT_ARM_EXIDX) maps functions to the way to unwind them. Each
+// entry is two words: a place-relative offset to the function, and either
+// EXIDX_CANTUNWIND, the unwinding instructions themselves (bit 31 set) or a
+// place-relative offset to them in .ARM.extab. Offsets are prel31: 31-bit
+// signed values relative to the address of the word they are in.
+
+const EXIDX_CANTUNWIND: u32 = 1;
+const EXIDX_ENTRY_SIZE: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExidxUnwind {
+    CantUnwind,
+    // The second word, with the personality routine index in bits 24..27
+    Inline(u32),
+    // The address of the entry in .ARM.extab
+    Table(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExidxEntry {
+    function: u64,
+    unwind: ExidxUnwind,
+}
+
+#[allow(dead_code)]
+impl ExidxEntry {
+    pub fn get_function(&self) -> u64 {
+        self.function
+    }
+    pub fn get_unwind(&self) -> ExidxUnwind {
+        self.unwind
+    }
+}
+
+fn get_prel31(word: u32, place: u64) -> u64 {
+    let offset = ((word << 1) as i32 >> 1) as i64;
+    place.wrapping_add(offset as u64) & 0xffffffff
+}
+
+// `address` is where the table is loaded. A trailing partial entry is
+// ignored.
+#[allow(dead_code)]
+pub fn parse_exidx(data: &[u8], address: u64, e: &Endianness) -> Vec<ExidxEntry> {
+    (0..data.len() / EXIDX_ENTRY_SIZE)
+        .filter_map(|i| {
+            let offset = i * EXIDX_ENTRY_SIZE;
+            let place = address + offset as u64;
+            match (get_u32(data, offset, e), get_u32(data, offset + 4, e)) {
+                (Some(function), Some(unwind)) => Some(ExidxEntry {
+                    function: get_prel31(function, place),
+                    unwind: if unwind == EXIDX_CANTUNWIND {
+                        ExidxUnwind::CantUnwind
+                    } else if unwind & 0x80000000 != 0 {
+                        ExidxUnwind::Inline(unwind)
+                    } else {
+                        ExidxUnwind::Table(get_prel31(unwind, place + 4))
+                    },
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[allow(dead_code)]
+pub fn print_exidx<W: Write>(entries: &[ExidxEntry], out: &mut W) -> io::Result<()> {
+    try!(writeln!(out, "  Function    Unwinding"));
+    for entry in entries {
+        let unwind = match entry.unwind {
+            ExidxUnwind::CantUnwind => "cannot unwind".to_string(),
+            ExidxUnwind::Inline(word) => format!(
+                "inline, personality routine {} ({:#010x})", (word >> 24) & 0xf, word),
+            ExidxUnwind::Table(address) => format!(".ARM.extab entry at {:#010x}", address),
+        };
+        try!(writeln!(out, "  {:#010x}  {}", entry.function, unwind));
+    }
+    Ok(())
+}
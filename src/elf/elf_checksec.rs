@@ -0,0 +1,49 @@
+use ::std::io::{self, Write};
+use super::elf_fortify::FortifyInfo;
+
+// A few hardening properties at a glance, in the spirit of checksec.sh.
+// Each of them is reported in more detail by its own option.
+#[derive(Debug, Clone, Default)]
+pub struct Checksec {
+    textrel: bool,
+    rwx: bool,
+    // None if the file has no dynamic symbols to judge by
+    fortify: Option<FortifyInfo>,
+}
+
+#[allow(dead_code)]
+impl Checksec {
+    pub fn new() -> Checksec {
+        Checksec::default()
+    }
+    pub fn set_textrel(&mut self, textrel: bool) {
+        self.textrel = textrel;
+    }
+    // Whether any segment is readable, writable and executable at once.
+    pub fn set_rwx(&mut self, rwx: bool) {
+        self.rwx = rwx;
+    }
+    pub fn set_fortify(&mut self, fortify: Option<FortifyInfo>) {
+        self.fortify = fortify;
+    }
+
+    pub fn print<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        try!(writeln!(
+            out,
+            "TEXTREL:  {}",
+            if self.textrel { "yes (see -d)" } else { "no" }));
+        try!(writeln!(
+            out,
+            "RWX:      {}",
+            if self.rwx { "yes (see -l)" } else { "no" }));
+        match self.fortify {
+            Some(ref info) if info.is_fortified() => writeln!(
+                out,
+                "FORTIFY:  yes ({} checked function{}, see --fortify)",
+                info.get_checked().len(),
+                if info.get_checked().len() == 1 { "" } else { "s" }),
+            Some(_) => writeln!(out, "FORTIFY:  no"),
+            None => writeln!(out, "FORTIFY:  unknown (no dynamic symbols)"),
+        }
+    }
+}
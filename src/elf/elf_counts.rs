@@ -0,0 +1,26 @@
+use ::std::fmt::{Display, Formatter};
+
+// How many of each structure a file has, for --count.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StructureCounts {
+    pub sections: usize,
+    pub segments: usize,
+    pub symtab_symbols: u64,
+    pub dynsym_symbols: u64,
+    // RELR entries are counted as the relocations they stand for
+    pub relocations: u64,
+    pub notes: usize,
+    // Up to and including DT_NULL, as readelf -d counts them
+    pub dynamic_entries: usize,
+}
+
+impl Display for StructureCounts {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        write!(
+            fmt,
+            "sections: {}, segments: {}, symbols: {} (.symtab) {} (.dynsym), \
+             relocations: {}, notes: {}, dynamic entries: {}",
+            self.sections, self.segments, self.symtab_symbols, self.dynsym_symbols,
+            self.relocations, self.notes, self.dynamic_entries)
+    }
+}
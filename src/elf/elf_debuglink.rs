@@ -0,0 +1,63 @@
+use ::to_host::Endianness;
+use super::helpers::{get_str, get_u32};
+
+// Contents of .gnu_debuglink: the name of the file holding the debug
+// information stripped from this one, and the CRC32 of that file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugLink {
+    filename: String,
+    crc: u32,
+}
+
+#[allow(dead_code)]
+impl DebugLink {
+    pub fn get_filename(&self) -> &str {
+        &self.filename
+    }
+    pub fn get_crc(&self) -> u32 {
+        self.crc
+    }
+}
+
+// The name is NUL-terminated and padded so that the CRC which follows it is
+// 4-byte aligned. Returns None if there is no room for an aligned CRC.
+#[allow(dead_code)]
+pub fn parse_debuglink(data: &[u8], e: &Endianness) -> Option<DebugLink> {
+    let name_end = match data.iter().position(|&b| b == 0) {
+        Some(end) => end,
+        None => return None,
+    };
+    let crc_offset = (name_end + 1 + 3) / 4 * 4;
+    get_u32(data, crc_offset, e).map(|crc| DebugLink {
+        filename: get_str(data, 0).to_string(),
+        crc: crc,
+    })
+}
+
+// Contents of .gnu_debugaltlink: the name of a supplementary debug file
+// shared between several objects (as produced by dwz), and its build ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugAltLink {
+    filename: String,
+    build_id: Vec<u8>,
+}
+
+#[allow(dead_code)]
+impl DebugAltLink {
+    pub fn get_filename(&self) -> &str {
+        &self.filename
+    }
+    pub fn get_build_id(&self) -> &[u8] {
+        &self.build_id
+    }
+}
+
+// The NUL-terminated name is immediately followed by the build ID, which
+// takes up the rest of the section.
+#[allow(dead_code)]
+pub fn parse_debugaltlink(data: &[u8]) -> Option<DebugAltLink> {
+    data.iter().position(|&b| b == 0).map(|name_end| DebugAltLink {
+        filename: get_str(data, 0).to_string(),
+        build_id: data[name_end + 1..].to_vec(),
+    })
+}
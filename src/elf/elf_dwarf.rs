@@ -0,0 +1,471 @@
+use ::std::io::{self, Write};
+use ::to_host::Endianness;
+use super::helpers::{get_u16, get_u32, get_u64};
+
+pub const DW_UT_COMPILE: u8 = 1;
+pub const DW_UT_TYPE: u8 = 2;
+pub const DW_UT_PARTIAL: u8 = 3;
+pub const DW_UT_SKELETON: u8 = 4;
+pub const DW_UT_SPLIT_COMPILE: u8 = 5;
+pub const DW_UT_SPLIT_TYPE: u8 = 6;
+
+// The header of one unit in .debug_info. DWARF has its own 32/64-bit formats
+// independent of the ELF class, chosen per unit by the initial length field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DwarfUnitHeader {
+    offset: u64,
+    length: u64,
+    is_dwarf64: bool,
+    version: u16,
+    unit_type: Option<u8>,
+    abbrev_offset: u64,
+    address_size: u8,
+}
+
+#[allow(dead_code)]
+impl DwarfUnitHeader {
+    pub fn get_offset(&self) -> u64 {
+        self.offset
+    }
+    // The length of the unit not counting the initial length field itself.
+    pub fn get_length(&self) -> u64 {
+        self.length
+    }
+    pub fn is_dwarf64(&self) -> bool {
+        self.is_dwarf64
+    }
+    pub fn get_version(&self) -> u16 {
+        self.version
+    }
+    // Only DWARF 5 headers carry an explicit unit type.
+    pub fn get_unit_type(&self) -> Option<u8> {
+        self.unit_type
+    }
+    pub fn get_abbrev_offset(&self) -> u64 {
+        self.abbrev_offset
+    }
+    pub fn get_address_size(&self) -> u8 {
+        self.address_size
+    }
+}
+
+fn unit_type_name(unit_type: u8) -> &'static str {
+    match unit_type {
+        DW_UT_COMPILE => "DW_UT_compile",
+        DW_UT_TYPE => "DW_UT_type",
+        DW_UT_PARTIAL => "DW_UT_partial",
+        DW_UT_SKELETON => "DW_UT_skeleton",
+        DW_UT_SPLIT_COMPILE => "DW_UT_split_compile",
+        DW_UT_SPLIT_TYPE => "DW_UT_split_type",
+        _ => "<unknown>",
+    }
+}
+
+// Reads a DWARF initial length field. Returns the unit length, whether the
+// unit uses the 64-bit format, and the size of the field itself.
+#[allow(dead_code)]
+pub fn get_initial_length(
+    data: &[u8], offset: usize, e: &Endianness) -> Option<(u64, bool, usize)>
+{
+    match get_u32(data, offset, e) {
+        Some(0xffffffff) =>
+            get_u64(data, offset + 4, e).map(|length| (length, true, 12)),
+        // 0xfffffff0 and up are reserved.
+        Some(length) if length < 0xfffffff0 =>
+            Some((length as u64, false, 4)),
+        _ => None,
+    }
+}
+
+// Walks the unit headers in .debug_info without looking at the DIEs. Stops
+// at the first header which is truncated or doesn't fit in the section.
+#[allow(dead_code)]
+pub fn parse_unit_headers(data: &[u8], e: &Endianness) -> Vec<DwarfUnitHeader> {
+    let mut units = Vec::new();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let (length, is_dwarf64, length_size) =
+            match get_initial_length(data, offset, e) {
+                Some(initial_length) => initial_length,
+                None => break,
+            };
+        let start = offset + length_size;
+        if length > (data.len() - start) as u64 {
+            break;
+        }
+        let end = start + length as usize;
+        let unit = &data[..end];
+        let offset_size = if is_dwarf64 { 8 } else { 4 };
+
+        let version = match get_u16(unit, start, e) {
+            Some(version) => version,
+            None => break,
+        };
+        // DWARF 5 moved the address size before the abbrev offset and added
+        // the unit type in front of it.
+        let (unit_type, address_size_at, abbrev_offset_at) = if version >= 5 {
+            (unit.get(start + 2).cloned(), start + 3, start + 4)
+        } else {
+            (None, start + 2 + offset_size, start + 2)
+        };
+        let abbrev_offset = if is_dwarf64 {
+            get_u64(unit, abbrev_offset_at, e)
+        } else {
+            get_u32(unit, abbrev_offset_at, e).map(|o| o as u64)
+        };
+        let (abbrev_offset, address_size) =
+            match (abbrev_offset, unit.get(address_size_at)) {
+                (Some(abbrev_offset), Some(&address_size)) =>
+                    (abbrev_offset, address_size),
+                _ => break,
+            };
+
+        units.push(DwarfUnitHeader {
+            offset: offset as u64,
+            length: length,
+            is_dwarf64: is_dwarf64,
+            version: version,
+            unit_type: unit_type,
+            abbrev_offset: abbrev_offset,
+            address_size: address_size,
+        });
+        offset = end;
+    }
+    units
+}
+
+#[allow(dead_code)]
+pub fn print_unit_headers<W: Write>(
+    units: &[DwarfUnitHeader], out: &mut W) -> io::Result<()>
+{
+    for unit in units {
+        try!(write!(
+            out,
+            "  Compilation Unit @ offset {:#x}: length {:#x} ({}), version {}",
+            unit.get_offset(),
+            unit.get_length(),
+            if unit.is_dwarf64() { "64-bit" } else { "32-bit" },
+            unit.get_version()));
+        if let Some(unit_type) = unit.get_unit_type() {
+            try!(write!(out, ", {} ({})", unit_type_name(unit_type), unit_type));
+        }
+        try!(writeln!(
+            out,
+            ", abbrev offset {:#x}, pointer size {}",
+            unit.get_abbrev_offset(),
+            unit.get_address_size()));
+    }
+    Ok(())
+}
+
+// Reads an unsigned LEB128 number. Returns the value and the number of bytes
+// it took, or None if it runs off the end of the data or doesn't fit in u64.
+#[allow(dead_code)]
+pub fn get_uleb128(data: &[u8], offset: usize) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    for (i, &b) in data.iter().skip(offset).enumerate() {
+        if shift >= 64 {
+            return None;
+        }
+        value |= ((b & 0x7f) as u64) << shift;
+        if (b & 0x80) == 0 {
+            return Some((value, i + 1));
+        }
+        shift += 7;
+    }
+    None
+}
+
+// Reads a NUL-terminated string. Returns it and its size with the NUL.
+fn get_cstr(data: &[u8], offset: usize) -> Option<(String, usize)> {
+    if offset >= data.len() {
+        return None;
+    }
+    data[offset..].iter().position(|&b| b == 0).map(|end| {
+        let s = String::from_utf8_lossy(&data[offset..offset + end]);
+        (s.into_owned(), end + 1)
+    })
+}
+
+// An entry of the file name table of a DWARF 2-4 line program header.
+// The directory is an index into the include directories, counting from 1,
+// with 0 meaning the compilation directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DwarfLineFile {
+    name: String,
+    dir: u64,
+    mtime: u64,
+    size: u64,
+}
+
+#[allow(dead_code)]
+impl DwarfLineFile {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn get_dir(&self) -> u64 {
+        self.dir
+    }
+    pub fn get_mtime(&self) -> u64 {
+        self.mtime
+    }
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+}
+
+// The header of one line number program in .debug_line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DwarfLineHeader {
+    offset: u64,
+    length: u64,
+    is_dwarf64: bool,
+    version: u16,
+    header_length: u64,
+    min_inst_length: u8,
+    max_ops_per_inst: Option<u8>,
+    default_is_stmt: bool,
+    line_base: i8,
+    line_range: u8,
+    opcode_base: u8,
+    include_directories: Vec<String>,
+    file_names: Vec<DwarfLineFile>,
+}
+
+#[allow(dead_code)]
+impl DwarfLineHeader {
+    pub fn get_offset(&self) -> u64 {
+        self.offset
+    }
+    pub fn get_length(&self) -> u64 {
+        self.length
+    }
+    pub fn is_dwarf64(&self) -> bool {
+        self.is_dwarf64
+    }
+    pub fn get_version(&self) -> u16 {
+        self.version
+    }
+    pub fn get_header_length(&self) -> u64 {
+        self.header_length
+    }
+    pub fn get_min_inst_length(&self) -> u8 {
+        self.min_inst_length
+    }
+    // Only present since DWARF 4.
+    pub fn get_max_ops_per_inst(&self) -> Option<u8> {
+        self.max_ops_per_inst
+    }
+    pub fn get_default_is_stmt(&self) -> bool {
+        self.default_is_stmt
+    }
+    pub fn get_line_base(&self) -> i8 {
+        self.line_base
+    }
+    pub fn get_line_range(&self) -> u8 {
+        self.line_range
+    }
+    pub fn get_opcode_base(&self) -> u8 {
+        self.opcode_base
+    }
+    // Empty for DWARF 5, whose tables are described by entry formats and
+    // are not parsed yet.
+    pub fn get_include_directories(&self) -> &[String] {
+        &self.include_directories
+    }
+    pub fn get_file_names(&self) -> &[DwarfLineFile] {
+        &self.file_names
+    }
+}
+
+// Parses the include_directories and file_names tables of a DWARF 2-4
+// header, both of which end with an empty entry.
+fn parse_line_tables(
+    data: &[u8], mut offset: usize) -> Option<(Vec<String>, Vec<DwarfLineFile>)>
+{
+    let mut include_directories = Vec::new();
+    loop {
+        let (dir, size) = match get_cstr(data, offset) {
+            Some(dir) => dir,
+            None => return None,
+        };
+        offset += size;
+        if dir.is_empty() {
+            break;
+        }
+        include_directories.push(dir);
+    }
+
+    let mut file_names = Vec::new();
+    loop {
+        let (name, size) = match get_cstr(data, offset) {
+            Some(name) => name,
+            None => return None,
+        };
+        offset += size;
+        if name.is_empty() {
+            break;
+        }
+        let mut fields = [0u64; 3];
+        for field in fields.iter_mut() {
+            let (value, size) = match get_uleb128(data, offset) {
+                Some(value) => value,
+                None => return None,
+            };
+            *field = value;
+            offset += size;
+        }
+        file_names.push(DwarfLineFile {
+            name: name,
+            dir: fields[0],
+            mtime: fields[1],
+            size: fields[2],
+        });
+    }
+    Some((include_directories, file_names))
+}
+
+// Walks the line number program headers in .debug_line, skipping the
+// programs themselves. Stops at the first header which is truncated.
+#[allow(dead_code)]
+pub fn parse_line_headers(data: &[u8], e: &Endianness) -> Vec<DwarfLineHeader> {
+    let mut headers = Vec::new();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let (length, is_dwarf64, length_size) =
+            match get_initial_length(data, offset, e) {
+                Some(initial_length) => initial_length,
+                None => break,
+            };
+        let start = offset + length_size;
+        if length > (data.len() - start) as u64 {
+            break;
+        }
+        let end = start + length as usize;
+        let unit = &data[..end];
+
+        let version = match get_u16(unit, start, e) {
+            Some(version) => version,
+            None => break,
+        };
+        // DWARF 5 added the address and segment selector sizes here.
+        let header_length_at = if version >= 5 { start + 4 } else { start + 2 };
+        let (header_length, mut p) = if is_dwarf64 {
+            (get_u64(unit, header_length_at, e), header_length_at + 8)
+        } else {
+            (get_u32(unit, header_length_at, e).map(|l| l as u64),
+             header_length_at + 4)
+        };
+        let header_length = match header_length {
+            Some(header_length) => header_length,
+            None => break,
+        };
+
+        let min_inst_length = unit.get(p).cloned();
+        p += 1;
+        let max_ops_per_inst = if version >= 4 {
+            p += 1;
+            unit.get(p - 1).cloned()
+        } else {
+            None
+        };
+        let params = (
+            min_inst_length, unit.get(p), unit.get(p + 1), unit.get(p + 2),
+            unit.get(p + 3));
+        let (min_inst_length, default_is_stmt, line_base, line_range, opcode_base) =
+            match params {
+                (Some(a), Some(&b), Some(&c), Some(&d), Some(&f)) => (a, b, c, d, f),
+                _ => break,
+            };
+        if version >= 4 && max_ops_per_inst.is_none() {
+            break;
+        }
+        // Skip the standard_opcode_lengths array.
+        p += 4 + opcode_base.saturating_sub(1) as usize;
+
+        let (include_directories, file_names) = if version >= 5 {
+            (Vec::new(), Vec::new())
+        } else {
+            match parse_line_tables(unit, p) {
+                Some(tables) => tables,
+                None => break,
+            }
+        };
+
+        headers.push(DwarfLineHeader {
+            offset: offset as u64,
+            length: length,
+            is_dwarf64: is_dwarf64,
+            version: version,
+            header_length: header_length,
+            min_inst_length: min_inst_length,
+            max_ops_per_inst: max_ops_per_inst,
+            default_is_stmt: default_is_stmt != 0,
+            line_base: line_base as i8,
+            line_range: line_range,
+            opcode_base: opcode_base,
+            include_directories: include_directories,
+            file_names: file_names,
+        });
+        offset = end;
+    }
+    headers
+}
+
+#[allow(dead_code)]
+pub fn print_line_headers<W: Write>(
+    headers: &[DwarfLineHeader], out: &mut W) -> io::Result<()>
+{
+    for header in headers {
+        try!(writeln!(out, "  Offset:                      {:#x}", header.get_offset()));
+        try!(writeln!(out, "  Length:                      {}", header.get_length()));
+        try!(writeln!(out, "  DWARF Version:               {}", header.get_version()));
+        try!(writeln!(out, "  Prologue Length:             {}", header.get_header_length()));
+        try!(writeln!(out, "  Minimum Instruction Length:  {}", header.get_min_inst_length()));
+        if let Some(max_ops) = header.get_max_ops_per_inst() {
+            try!(writeln!(out, "  Maximum Ops per Instruction: {}", max_ops));
+        }
+        try!(writeln!(
+            out, "  Initial value of 'is_stmt':  {}", header.get_default_is_stmt() as u8));
+        try!(writeln!(out, "  Line Base:                   {}", header.get_line_base()));
+        try!(writeln!(out, "  Line Range:                  {}", header.get_line_range()));
+        try!(writeln!(out, "  Opcode Base:                 {}", header.get_opcode_base()));
+        try!(writeln!(out, ""));
+
+        if header.get_version() >= 5 {
+            try!(writeln!(
+                out, " The directory and file name tables of DWARF 5 are not supported yet."));
+            try!(writeln!(out, ""));
+            continue;
+        }
+
+        if header.get_include_directories().is_empty() {
+            try!(writeln!(out, " The Directory Table is empty."));
+        } else {
+            try!(writeln!(out, " The Directory Table:"));
+            for (i, dir) in header.get_include_directories().iter().enumerate() {
+                try!(writeln!(out, "  {}\t{}", i + 1, dir));
+            }
+        }
+        try!(writeln!(out, ""));
+
+        if header.get_file_names().is_empty() {
+            try!(writeln!(out, " The File Name Table is empty."));
+        } else {
+            try!(writeln!(out, " The File Name Table:"));
+            try!(writeln!(out, "  Entry\tDir\tTime\tSize\tName"));
+            for (i, file) in header.get_file_names().iter().enumerate() {
+                try!(writeln!(
+                    out,
+                    "  {}\t{}\t{}\t{}\t{}",
+                    i + 1, file.get_dir(), file.get_mtime(), file.get_size(),
+                    file.get_name()));
+            }
+        }
+        try!(writeln!(out, ""));
+    }
+    Ok(())
+}
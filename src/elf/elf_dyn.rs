@@ -0,0 +1,290 @@
+#[macro_export]
+macro_rules! elf_dyn {
+    ($sword:ty, $xword:ty, $addr:ty, $off:ty) => {
+        use ::std::io::{self, Read, Seek, Write};
+        use ::to_host::Endianness;
+        use ::to_host::to_host_copy::ToHostCopy;
+        use ::to_host::ToHostCopyStruct;
+        use super::super::elf_dyn_tag::ElfDynTag;
+        use super::super::elf_error::ElfError;
+        use super::super::elf_dyn_flags::{ElfDynFlags, ElfDynFlags1};
+        use super::super::elf_phdr_type::ElfPhdrType;
+        use super::super::helpers::{read_bytes, get_str, EntryLimit};
+        use super::super::elf_needed::{Dependencies, split_search_path};
+        use super::super::elf_hash::{
+            get_sysv_bucket_lengths, get_gnu_bucket_lengths, print_bucket_histogram};
+        use super::elf_phdr::{
+            Elf_Phdr, vaddr_to_offset, get_vaddr_extent, read_vaddr};
+        use super::helpers::read_dyns;
+        use super::primitive::*;
+
+        #[derive(Debug, Clone, Copy)]
+        #[repr(C)]
+        pub struct Elf_Dyn {
+            d_tag: $sword,
+            d_val: $xword,
+        }
+
+        impl Elf_Dyn {
+            #[allow(dead_code)]
+            pub fn get_tag(&self) -> ElfDynTag {
+                ElfDynTag::new(self.d_tag as i64)
+            }
+            #[allow(dead_code)]
+            pub fn get_val(&self) -> $xword {
+                self.d_val
+            }
+        }
+
+        impl ToHostCopyStruct for Elf_Dyn {
+            fn to_host_copy(&self, endianness: &Endianness) -> Self {
+                let e = endianness;
+                Elf_Dyn {
+                    d_tag: self.d_tag.to_host_copy(e),
+                    d_val: self.d_val.to_host_copy(e),
+                }
+            }
+        }
+
+        // Program headers and dynamic entries passed to the functions below
+        // are expected to be in host byte order.
+
+        #[allow(dead_code)]
+        pub fn find_dynamic_phdr(phdrs: &[Elf_Phdr]) -> Option<&Elf_Phdr> {
+            phdrs.iter().find(
+                |p| p.get_type() == ElfPhdrType::PT_DYNAMIC.get_value())
+        }
+
+        #[allow(dead_code)]
+        pub fn get_dyn_val(dyns: &[Elf_Dyn], tag: ElfDynTag) -> Option<$xword> {
+            dyns.iter().find(|d| d.get_tag() == tag).map(|d| d.get_val())
+        }
+
+        #[allow(dead_code)]
+        pub fn get_dyn_flags(dyns: &[Elf_Dyn]) -> ElfDynFlags {
+            let flags = get_dyn_val(dyns, ElfDynTag::DT_FLAGS).unwrap_or(0);
+            ElfDynFlags::new(flags as u64)
+        }
+
+        #[allow(dead_code)]
+        pub fn get_dyn_flags_1(dyns: &[Elf_Dyn]) -> ElfDynFlags1 {
+            let flags = get_dyn_val(dyns, ElfDynTag::DT_FLAGS_1).unwrap_or(0);
+            ElfDynFlags1::new(flags as u64)
+        }
+
+        // Text relocations require writing into otherwise read-only (and
+        // usually executable) pages at load time, which defeats W^X.
+        #[allow(dead_code)]
+        pub fn has_text_relocations(dyns: &[Elf_Dyn]) -> bool {
+            get_dyn_val(dyns, ElfDynTag::DT_TEXTREL).is_some()
+            || get_dyn_flags(dyns).is_textrel()
+        }
+
+        // The same for the whole file: false if there is no dynamic section
+        // or it can't be read, which -d reports.
+        #[allow(dead_code)]
+        pub fn has_dynamic_text_relocations<R: Read + Seek>(
+            phdrs: &[Elf_Phdr], e: &Endianness, reader: &mut R) -> bool
+        {
+            match find_dynamic_phdr(phdrs).map(|phdr| read_dyns(phdr, e, reader)) {
+                Some(Ok(dyns)) => has_text_relocations(&dyns),
+                _ => false,
+            }
+        }
+
+        // Translates the address held by an entry such as DT_VERSYM or
+        // DT_VERNEED into a file offset. This is how tables are located in
+        // binaries whose section headers have been stripped.
+        #[allow(dead_code)]
+        pub fn get_dyn_offset(
+            dyns: &[Elf_Dyn], phdrs: &[Elf_Phdr], tag: ElfDynTag) -> Option<$off>
+        {
+            get_dyn_val(dyns, tag)
+                .and_then(|vaddr| vaddr_to_offset(phdrs, vaddr as $addr))
+        }
+
+        // Reads the string table referenced by DT_STRTAB/DT_STRSZ.
+        #[allow(dead_code)]
+        pub fn read_dynstr<R: Read + Seek>(
+            dyns: &[Elf_Dyn], phdrs: &[Elf_Phdr], reader: &mut R)
+            -> Vec<u8>
+        {
+            let strtab = get_dyn_offset(dyns, phdrs, ElfDynTag::DT_STRTAB);
+            let strsz = get_dyn_val(dyns, ElfDynTag::DT_STRSZ);
+            match (strtab, strsz) {
+                (Some(offset), Some(strsz)) =>
+                    read_bytes(reader, offset as u64, strsz as u64).unwrap_or(Vec::new()),
+                _ => Vec::new(),
+            }
+        }
+
+        #[allow(dead_code)]
+        pub fn print_dynamic<R: Read + Seek, W: Write>(
+            phdrs: &[Elf_Phdr], e: &Endianness, reader: &mut R, out: &mut W,
+            max_entries: Option<usize>)
+            -> io::Result<()>
+        {
+            let phdrs: Vec<Elf_Phdr> =
+                phdrs.iter().map(|p| p.to_host_copy(e)).collect();
+
+            let dynamic = match find_dynamic_phdr(&phdrs) {
+                Some(phdr) => phdr,
+                None => {
+                    try!(writeln!(out, ""));
+                    try!(writeln!(out, "There is no dynamic section in this file."));
+                    return Ok(());
+                }
+            };
+
+            let dyns = match read_dyns(dynamic, e, reader) {
+                Ok(dyns) => dyns,
+                Err(err) => {
+                    warning!("the dynamic section can't be read: {}", err);
+                    return Ok(());
+                }
+            };
+            let dynstr = read_dynstr(&dyns, &phdrs, reader);
+            let width = ::std::mem::size_of::<$xword>() * 2;
+
+            try!(heading!(out, ""));
+            try!(heading!(
+                out,
+                "Dynamic section at offset {:#x} contains {} entries:",
+                dynamic.get_offset(), dyns.len()));
+            try!(heading!(out, "  Tag        Type                         Name/Value"));
+            let mut limit = EntryLimit::new(max_entries);
+            for d in &dyns {
+                if !limit.take() {
+                    continue;
+                }
+                let tag = d.get_tag();
+                let val = d.get_val();
+                try!(write!(
+                    out,
+                    " 0x{:02$x} {:<3$}",
+                    d.d_tag as $xword, format!("({})", tag), width, 37 - width));
+                match tag {
+                    ElfDynTag::DT_NEEDED => try!(writeln!(
+                        out,
+                        "Shared library: [{}]", get_str(&dynstr, val as u64))),
+                    ElfDynTag::DT_SONAME => try!(writeln!(
+                        out,
+                        "Library soname: [{}]", get_str(&dynstr, val as u64))),
+                    ElfDynTag::DT_RPATH | ElfDynTag::DT_RUNPATH => {
+                        let path = get_str(&dynstr, val as u64);
+                        let kind = if tag == ElfDynTag::DT_RPATH { "rpath" } else { "runpath" };
+                        try!(writeln!(out, "Library {}: [{}]", kind, path));
+                        // One directory per line, in search order
+                        for (i, dir) in split_search_path(path).iter().enumerate() {
+                            try!(writeln!(out, "      [{}] {}", i, dir));
+                        }
+                    }
+                    ElfDynTag::DT_FLAGS => try!(writeln!(
+                        out,
+                        "{}", ElfDynFlags::new(val as u64))),
+                    ElfDynTag::DT_FLAGS_1 => try!(writeln!(
+                        out,
+                        "Flags: {}", ElfDynFlags1::new(val as u64))),
+                    ElfDynTag::DT_PLTREL => try!(writeln!(
+                        out,
+                        "{}", ElfDynTag::new(val as i64))),
+                    ElfDynTag::DT_PLTRELSZ
+                    | ElfDynTag::DT_RELASZ
+                    | ElfDynTag::DT_RELAENT
+                    | ElfDynTag::DT_STRSZ
+                    | ElfDynTag::DT_SYMENT
+                    | ElfDynTag::DT_RELSZ
+                    | ElfDynTag::DT_RELENT
+                    | ElfDynTag::DT_INIT_ARRAYSZ
+                    | ElfDynTag::DT_FINI_ARRAYSZ
+                    | ElfDynTag::DT_PREINIT_ARRAYSZ => try!(writeln!(
+                        out,
+                        "{} (bytes)", val)),
+                    ElfDynTag::DT_VERDEFNUM
+                    | ElfDynTag::DT_VERNEEDNUM
+                    | ElfDynTag::DT_RELACOUNT
+                    | ElfDynTag::DT_RELCOUNT => try!(writeln!(out, "{}", val)),
+                    _ => try!(writeln!(out, "{:#x}", val)),
+                }
+            }
+            try!(limit.print_omitted(out));
+
+            if has_text_relocations(&dyns) {
+                warning!("this file contains text relocations (DT_TEXTREL)");
+            }
+            Ok(())
+        }
+
+        // The soname and the libraries the object needs, or None if it has
+        // no dynamic section.
+        #[allow(dead_code)]
+        pub fn get_dependencies<R: Read + Seek>(
+            phdrs: &[Elf_Phdr], e: &Endianness, reader: &mut R)
+            -> Result<Option<Dependencies>, ElfError>
+        {
+            let phdrs: Vec<Elf_Phdr> =
+                phdrs.iter().map(|p| p.to_host_copy(e)).collect();
+            let dyns = match find_dynamic_phdr(&phdrs) {
+                Some(dynamic) => try!(read_dyns(dynamic, e, reader)),
+                None => return Ok(None),
+            };
+            let dynstr = read_dynstr(&dyns, &phdrs, reader);
+            Ok(Some(Dependencies {
+                soname: get_dyn_val(&dyns, ElfDynTag::DT_SONAME)
+                    .map(|val| get_str(&dynstr, val as u64).to_string()),
+                needed: dyns.iter()
+                    .filter(|d| d.get_tag() == ElfDynTag::DT_NEEDED)
+                    .map(|d| get_str(&dynstr, d.get_val() as u64).to_string())
+                    .collect(),
+                rpath: get_dyn_val(&dyns, ElfDynTag::DT_RPATH)
+                    .map(|val| get_str(&dynstr, val as u64).to_string()),
+                runpath: get_dyn_val(&dyns, ElfDynTag::DT_RUNPATH)
+                    .map(|val| get_str(&dynstr, val as u64).to_string()),
+            }))
+        }
+
+        // Reads a table referenced from .dynamic whose size isn't recorded
+        // there, up to the end of the segment containing it.
+        fn read_dyn_table<R: Read + Seek>(
+            dyns: &[Elf_Dyn], phdrs: &[Elf_Phdr], tag: ElfDynTag, reader: &mut R)
+            -> Option<Vec<u8>>
+        {
+            let vaddr = match get_dyn_val(dyns, tag) {
+                Some(vaddr) => vaddr as $addr,
+                None => return None,
+            };
+            get_vaddr_extent(phdrs, vaddr)
+                .and_then(|len| read_vaddr(phdrs, vaddr, len as usize, reader))
+        }
+
+        #[allow(dead_code)]
+        pub fn print_histogram<R: Read + Seek, W: Write>(
+            phdrs: &[Elf_Phdr], e: &Endianness, reader: &mut R, out: &mut W)
+            -> io::Result<()>
+        {
+            let phdrs: Vec<Elf_Phdr> =
+                phdrs.iter().map(|p| p.to_host_copy(e)).collect();
+
+            let dyns = match find_dynamic_phdr(&phdrs) {
+                Some(dynamic) => read_dyns(dynamic, e, reader).unwrap_or(Vec::new()),
+                None => return Ok(()),
+            };
+
+            if let Some(data) = read_dyn_table(&dyns, &phdrs, ElfDynTag::DT_HASH, reader) {
+                match get_sysv_bucket_lengths(&data, e) {
+                    Some(lengths) => try!(print_bucket_histogram("", &lengths, out)),
+                    None => warning!("the .hash table is truncated"),
+                }
+            }
+            if let Some(data) = read_dyn_table(&dyns, &phdrs, ElfDynTag::DT_GNU_HASH, reader) {
+                let bloom_word_size = ::std::mem::size_of::<$addr>();
+                match get_gnu_bucket_lengths(&data, e, bloom_word_size) {
+                    Some(lengths) =>
+                        try!(print_bucket_histogram("`.gnu.hash' ", &lengths, out)),
+                    None => warning!("the .gnu.hash table is truncated"),
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use ::std::fmt::{Display, Formatter};
+
+pub const DF_ORIGIN: u64 = 0x1;
+pub const DF_SYMBOLIC: u64 = 0x2;
+pub const DF_TEXTREL: u64 = 0x4;
+pub const DF_BIND_NOW: u64 = 0x8;
+pub const DF_STATIC_TLS: u64 = 0x10;
+
+const DF_NAMES: [(u64, &'static str); 5] = [
+    (DF_ORIGIN, "ORIGIN"),
+    (DF_SYMBOLIC, "SYMBOLIC"),
+    (DF_TEXTREL, "TEXTREL"),
+    (DF_BIND_NOW, "BIND_NOW"),
+    (DF_STATIC_TLS, "STATIC_TLS"),
+];
+
+pub const DF_1_NOW: u64 = 0x1;
+pub const DF_1_GLOBAL: u64 = 0x2;
+pub const DF_1_GROUP: u64 = 0x4;
+pub const DF_1_NODELETE: u64 = 0x8;
+pub const DF_1_LOADFLTR: u64 = 0x10;
+pub const DF_1_INITFIRST: u64 = 0x20;
+pub const DF_1_NOOPEN: u64 = 0x40;
+pub const DF_1_ORIGIN: u64 = 0x80;
+pub const DF_1_DIRECT: u64 = 0x100;
+pub const DF_1_TRANS: u64 = 0x200;
+pub const DF_1_INTERPOSE: u64 = 0x400;
+pub const DF_1_NODEFLIB: u64 = 0x800;
+pub const DF_1_NODUMP: u64 = 0x1000;
+pub const DF_1_CONFALT: u64 = 0x2000;
+pub const DF_1_ENDFILTEE: u64 = 0x4000;
+pub const DF_1_DISPRELDNE: u64 = 0x8000;
+pub const DF_1_DISPRELPND: u64 = 0x10000;
+pub const DF_1_NODIRECT: u64 = 0x20000;
+pub const DF_1_IGNMULDEF: u64 = 0x40000;
+pub const DF_1_NOKSYMS: u64 = 0x80000;
+pub const DF_1_NOHDR: u64 = 0x100000;
+pub const DF_1_EDITED: u64 = 0x200000;
+pub const DF_1_NORELOC: u64 = 0x400000;
+pub const DF_1_SYMINTPOSE: u64 = 0x800000;
+pub const DF_1_GLOBAUDIT: u64 = 0x1000000;
+pub const DF_1_SINGLETON: u64 = 0x2000000;
+pub const DF_1_STUB: u64 = 0x4000000;
+pub const DF_1_PIE: u64 = 0x8000000;
+
+const DF_1_NAMES: [(u64, &'static str); 28] = [
+    (DF_1_NOW, "NOW"),
+    (DF_1_GLOBAL, "GLOBAL"),
+    (DF_1_GROUP, "GROUP"),
+    (DF_1_NODELETE, "NODELETE"),
+    (DF_1_LOADFLTR, "LOADFLTR"),
+    (DF_1_INITFIRST, "INITFIRST"),
+    (DF_1_NOOPEN, "NOOPEN"),
+    (DF_1_ORIGIN, "ORIGIN"),
+    (DF_1_DIRECT, "DIRECT"),
+    (DF_1_TRANS, "TRANS"),
+    (DF_1_INTERPOSE, "INTERPOSE"),
+    (DF_1_NODEFLIB, "NODEFLIB"),
+    (DF_1_NODUMP, "NODUMP"),
+    (DF_1_CONFALT, "CONFALT"),
+    (DF_1_ENDFILTEE, "ENDFILTEE"),
+    (DF_1_DISPRELDNE, "DISPRELDNE"),
+    (DF_1_DISPRELPND, "DISPRELPND"),
+    (DF_1_NODIRECT, "NODIRECT"),
+    (DF_1_IGNMULDEF, "IGNMULDEF"),
+    (DF_1_NOKSYMS, "NOKSYMS"),
+    (DF_1_NOHDR, "NOHDR"),
+    (DF_1_EDITED, "EDITED"),
+    (DF_1_NORELOC, "NORELOC"),
+    (DF_1_SYMINTPOSE, "SYMINTPOSE"),
+    (DF_1_GLOBAUDIT, "GLOBAUDIT"),
+    (DF_1_SINGLETON, "SINGLETON"),
+    (DF_1_STUB, "STUB"),
+    (DF_1_PIE, "PIE"),
+];
+
+fn write_flag_names(
+    fmt: &mut Formatter, flags: u64, names: &[(u64, &'static str)])
+    -> ::std::fmt::Result
+{
+    let mut rest = flags;
+    let mut first = true;
+    for &(flag, name) in names {
+        if (flags & flag) != 0 {
+            if !first {
+                try!(write!(fmt, " "));
+            }
+            try!(write!(fmt, "{}", name));
+            rest &= !flag;
+            first = false;
+        }
+    }
+    if rest != 0 {
+        if !first {
+            try!(write!(fmt, " "));
+        }
+        try!(write!(fmt, "{:#x}", rest));
+    }
+    Ok(())
+}
+
+// Value of DT_FLAGS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElfDynFlags {
+    flags: u64,
+}
+
+#[allow(dead_code)]
+impl ElfDynFlags {
+    pub fn new(flags: u64) -> ElfDynFlags {
+        ElfDynFlags { flags: flags }
+    }
+    pub fn get_value(&self) -> u64 {
+        self.flags
+    }
+    pub fn contains(&self, flag: u64) -> bool {
+        (self.flags & flag) != 0
+    }
+    pub fn is_origin(&self) -> bool {
+        self.contains(DF_ORIGIN)
+    }
+    pub fn is_symbolic(&self) -> bool {
+        self.contains(DF_SYMBOLIC)
+    }
+    pub fn is_textrel(&self) -> bool {
+        self.contains(DF_TEXTREL)
+    }
+    pub fn is_bind_now(&self) -> bool {
+        self.contains(DF_BIND_NOW)
+    }
+    pub fn is_static_tls(&self) -> bool {
+        self.contains(DF_STATIC_TLS)
+    }
+}
+
+impl Display for ElfDynFlags {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        write_flag_names(fmt, self.flags, &DF_NAMES)
+    }
+}
+
+// Value of DT_FLAGS_1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElfDynFlags1 {
+    flags: u64,
+}
+
+#[allow(dead_code)]
+impl ElfDynFlags1 {
+    pub fn new(flags: u64) -> ElfDynFlags1 {
+        ElfDynFlags1 { flags: flags }
+    }
+    pub fn get_value(&self) -> u64 {
+        self.flags
+    }
+    pub fn contains(&self, flag: u64) -> bool {
+        (self.flags & flag) != 0
+    }
+    pub fn is_now(&self) -> bool {
+        self.contains(DF_1_NOW)
+    }
+    pub fn is_pie(&self) -> bool {
+        self.contains(DF_1_PIE)
+    }
+    pub fn is_nodelete(&self) -> bool {
+        self.contains(DF_1_NODELETE)
+    }
+    pub fn is_noopen(&self) -> bool {
+        self.contains(DF_1_NOOPEN)
+    }
+    pub fn is_origin(&self) -> bool {
+        self.contains(DF_1_ORIGIN)
+    }
+}
+
+impl Display for ElfDynFlags1 {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        write_flag_names(fmt, self.flags, &DF_1_NAMES)
+    }
+}
@@ -0,0 +1,121 @@
+use ::std::fmt::{Display, Formatter};
+
+// Dynamic tags are open-ended (OS- and processor-specific ranges are
+// populated by every toolchain), so unlike other enumerations this is a
+// newtype over the raw value rather than a `#[repr]` enum.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ElfDynTag {
+    tag: i64,
+}
+
+#[allow(dead_code)]
+impl ElfDynTag {
+    pub const DT_NULL: ElfDynTag = ElfDynTag { tag: 0 };
+    pub const DT_NEEDED: ElfDynTag = ElfDynTag { tag: 1 };
+    pub const DT_PLTRELSZ: ElfDynTag = ElfDynTag { tag: 2 };
+    pub const DT_PLTGOT: ElfDynTag = ElfDynTag { tag: 3 };
+    pub const DT_HASH: ElfDynTag = ElfDynTag { tag: 4 };
+    pub const DT_STRTAB: ElfDynTag = ElfDynTag { tag: 5 };
+    pub const DT_SYMTAB: ElfDynTag = ElfDynTag { tag: 6 };
+    pub const DT_RELA: ElfDynTag = ElfDynTag { tag: 7 };
+    pub const DT_RELASZ: ElfDynTag = ElfDynTag { tag: 8 };
+    pub const DT_RELAENT: ElfDynTag = ElfDynTag { tag: 9 };
+    pub const DT_STRSZ: ElfDynTag = ElfDynTag { tag: 10 };
+    pub const DT_SYMENT: ElfDynTag = ElfDynTag { tag: 11 };
+    pub const DT_INIT: ElfDynTag = ElfDynTag { tag: 12 };
+    pub const DT_FINI: ElfDynTag = ElfDynTag { tag: 13 };
+    pub const DT_SONAME: ElfDynTag = ElfDynTag { tag: 14 };
+    pub const DT_RPATH: ElfDynTag = ElfDynTag { tag: 15 };
+    pub const DT_SYMBOLIC: ElfDynTag = ElfDynTag { tag: 16 };
+    pub const DT_REL: ElfDynTag = ElfDynTag { tag: 17 };
+    pub const DT_RELSZ: ElfDynTag = ElfDynTag { tag: 18 };
+    pub const DT_RELENT: ElfDynTag = ElfDynTag { tag: 19 };
+    pub const DT_PLTREL: ElfDynTag = ElfDynTag { tag: 20 };
+    pub const DT_DEBUG: ElfDynTag = ElfDynTag { tag: 21 };
+    pub const DT_TEXTREL: ElfDynTag = ElfDynTag { tag: 22 };
+    pub const DT_JMPREL: ElfDynTag = ElfDynTag { tag: 23 };
+    pub const DT_BIND_NOW: ElfDynTag = ElfDynTag { tag: 24 };
+    pub const DT_INIT_ARRAY: ElfDynTag = ElfDynTag { tag: 25 };
+    pub const DT_FINI_ARRAY: ElfDynTag = ElfDynTag { tag: 26 };
+    pub const DT_INIT_ARRAYSZ: ElfDynTag = ElfDynTag { tag: 27 };
+    pub const DT_FINI_ARRAYSZ: ElfDynTag = ElfDynTag { tag: 28 };
+    pub const DT_RUNPATH: ElfDynTag = ElfDynTag { tag: 29 };
+    pub const DT_FLAGS: ElfDynTag = ElfDynTag { tag: 30 };
+    pub const DT_PREINIT_ARRAY: ElfDynTag = ElfDynTag { tag: 32 };
+    pub const DT_PREINIT_ARRAYSZ: ElfDynTag = ElfDynTag { tag: 33 };
+    pub const DT_SYMTAB_SHNDX: ElfDynTag = ElfDynTag { tag: 34 };
+    pub const DT_RELRSZ: ElfDynTag = ElfDynTag { tag: 35 };
+    pub const DT_RELR: ElfDynTag = ElfDynTag { tag: 36 };
+    pub const DT_RELRENT: ElfDynTag = ElfDynTag { tag: 37 };
+    pub const DT_GNU_HASH: ElfDynTag = ElfDynTag { tag: 0x6ffffef5 };
+    pub const DT_VERSYM: ElfDynTag = ElfDynTag { tag: 0x6ffffff0 };
+    pub const DT_RELACOUNT: ElfDynTag = ElfDynTag { tag: 0x6ffffff9 };
+    pub const DT_RELCOUNT: ElfDynTag = ElfDynTag { tag: 0x6ffffffa };
+    pub const DT_FLAGS_1: ElfDynTag = ElfDynTag { tag: 0x6ffffffb };
+    pub const DT_VERDEF: ElfDynTag = ElfDynTag { tag: 0x6ffffffc };
+    pub const DT_VERDEFNUM: ElfDynTag = ElfDynTag { tag: 0x6ffffffd };
+    pub const DT_VERNEED: ElfDynTag = ElfDynTag { tag: 0x6ffffffe };
+    pub const DT_VERNEEDNUM: ElfDynTag = ElfDynTag { tag: 0x6fffffff };
+
+    pub fn new(tag: i64) -> ElfDynTag {
+        ElfDynTag { tag: tag }
+    }
+    pub fn get_value(&self) -> i64 {
+        self.tag
+    }
+}
+
+impl Display for ElfDynTag {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            ElfDynTag::DT_NULL => "NULL",
+            ElfDynTag::DT_NEEDED => "NEEDED",
+            ElfDynTag::DT_PLTRELSZ => "PLTRELSZ",
+            ElfDynTag::DT_PLTGOT => "PLTGOT",
+            ElfDynTag::DT_HASH => "HASH",
+            ElfDynTag::DT_STRTAB => "STRTAB",
+            ElfDynTag::DT_SYMTAB => "SYMTAB",
+            ElfDynTag::DT_RELA => "RELA",
+            ElfDynTag::DT_RELASZ => "RELASZ",
+            ElfDynTag::DT_RELAENT => "RELAENT",
+            ElfDynTag::DT_STRSZ => "STRSZ",
+            ElfDynTag::DT_SYMENT => "SYMENT",
+            ElfDynTag::DT_INIT => "INIT",
+            ElfDynTag::DT_FINI => "FINI",
+            ElfDynTag::DT_SONAME => "SONAME",
+            ElfDynTag::DT_RPATH => "RPATH",
+            ElfDynTag::DT_SYMBOLIC => "SYMBOLIC",
+            ElfDynTag::DT_REL => "REL",
+            ElfDynTag::DT_RELSZ => "RELSZ",
+            ElfDynTag::DT_RELENT => "RELENT",
+            ElfDynTag::DT_PLTREL => "PLTREL",
+            ElfDynTag::DT_DEBUG => "DEBUG",
+            ElfDynTag::DT_TEXTREL => "TEXTREL",
+            ElfDynTag::DT_JMPREL => "JMPREL",
+            ElfDynTag::DT_BIND_NOW => "BIND_NOW",
+            ElfDynTag::DT_INIT_ARRAY => "INIT_ARRAY",
+            ElfDynTag::DT_FINI_ARRAY => "FINI_ARRAY",
+            ElfDynTag::DT_INIT_ARRAYSZ => "INIT_ARRAYSZ",
+            ElfDynTag::DT_FINI_ARRAYSZ => "FINI_ARRAYSZ",
+            ElfDynTag::DT_RUNPATH => "RUNPATH",
+            ElfDynTag::DT_FLAGS => "FLAGS",
+            ElfDynTag::DT_PREINIT_ARRAY => "PREINIT_ARRAY",
+            ElfDynTag::DT_PREINIT_ARRAYSZ => "PREINIT_ARRAYSZ",
+            ElfDynTag::DT_SYMTAB_SHNDX => "SYMTAB_SHNDX",
+            ElfDynTag::DT_RELRSZ => "RELRSZ",
+            ElfDynTag::DT_RELR => "RELR",
+            ElfDynTag::DT_RELRENT => "RELRENT",
+            ElfDynTag::DT_GNU_HASH => "GNU_HASH",
+            ElfDynTag::DT_VERSYM => "VERSYM",
+            ElfDynTag::DT_RELACOUNT => "RELACOUNT",
+            ElfDynTag::DT_RELCOUNT => "RELCOUNT",
+            ElfDynTag::DT_FLAGS_1 => "FLAGS_1",
+            ElfDynTag::DT_VERDEF => "VERDEF",
+            ElfDynTag::DT_VERDEFNUM => "VERDEFNUM",
+            ElfDynTag::DT_VERNEED => "VERNEED",
+            ElfDynTag::DT_VERNEEDNUM => "VERNEEDNUM",
+            ElfDynTag { tag } => return write!(fmt, "{:#x}", tag),
+        };
+        fmt.pad(s)
+    }
+}
@@ -0,0 +1,110 @@
+use ::to_host::Endianness;
+use super::helpers::{get_u32, get_u64};
+
+// .eh_frame_hdr (pointed at by PT_GNU_EH_FRAME) is the index the unwinder
+// uses to find the frame description entries in .eh_frame. It starts with a
+// version and three pointer encodings, then a pointer to .eh_frame and the
+// number of entries in the binary search table which follows.
+
+// DWARF pointer encodings: the low nibble is the format, the high one what
+// the value is relative to.
+const DW_EH_PE_OMIT: u8 = 0xff;
+const DW_EH_PE_UDATA4: u8 = 0x03;
+const DW_EH_PE_UDATA8: u8 = 0x04;
+const DW_EH_PE_SDATA4: u8 = 0x0b;
+const DW_EH_PE_SDATA8: u8 = 0x0c;
+const DW_EH_PE_PCREL: u8 = 0x10;
+const DW_EH_PE_DATAREL: u8 = 0x30;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EhFrameHdr {
+    address: u64,
+    offset: u64,
+    size: u64,
+    version: u8,
+    eh_frame_ptr: Option<u64>,
+    fde_count: Option<u64>,
+}
+
+#[allow(dead_code)]
+impl EhFrameHdr {
+    pub fn get_address(&self) -> u64 {
+        self.address
+    }
+    pub fn get_offset(&self) -> u64 {
+        self.offset
+    }
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+    pub fn get_version(&self) -> u8 {
+        self.version
+    }
+    // The address of .eh_frame, if its encoding is one we can decode
+    pub fn get_eh_frame_ptr(&self) -> Option<u64> {
+        self.eh_frame_ptr
+    }
+    pub fn get_fde_count(&self) -> Option<u64> {
+        self.fde_count
+    }
+}
+
+// Decodes the pointer at `offset` in `data`, which is loaded at `address`.
+// Returns the value and the number of bytes it took up, or None for
+// encodings other than the fixed-size ones linkers use here.
+fn read_encoded(
+    data: &[u8], offset: usize, encoding: u8, address: u64, e: &Endianness)
+    -> Option<(u64, usize)>
+{
+    let (value, size) = match encoding & 0x0f {
+        DW_EH_PE_UDATA4 => (get_u32(data, offset, e).map(|v| v as u64), 4),
+        DW_EH_PE_SDATA4 => (get_u32(data, offset, e).map(|v| v as i32 as i64 as u64), 4),
+        DW_EH_PE_UDATA8 | DW_EH_PE_SDATA8 => (get_u64(data, offset, e), 8),
+        _ => return None,
+    };
+    let base = match encoding & 0x70 {
+        0 => 0,
+        DW_EH_PE_PCREL => address.wrapping_add(offset as u64),
+        DW_EH_PE_DATAREL => address,
+        _ => return None,
+    };
+    value.map(|value| (base.wrapping_add(value), size))
+}
+
+// `address` and `offset` are where `data` is loaded and where it lies in
+// the file. Returns None if it is too short for the fixed part.
+#[allow(dead_code)]
+pub fn parse_eh_frame_hdr(
+    data: &[u8], address: u64, offset: u64, e: &Endianness)
+    -> Option<EhFrameHdr>
+{
+    if data.len() < 4 {
+        return None;
+    }
+    let eh_frame_ptr_enc = data[1];
+    let fde_count_enc = data[2];
+
+    let mut position = 4;
+    let eh_frame_ptr = if eh_frame_ptr_enc == DW_EH_PE_OMIT {
+        None
+    } else {
+        read_encoded(data, position, eh_frame_ptr_enc, address, e)
+    };
+    // The count can only be found if the pointer before it could be read
+    let fde_count = match eh_frame_ptr {
+        Some((_, size)) if fde_count_enc != DW_EH_PE_OMIT => {
+            position += size;
+            read_encoded(data, position, fde_count_enc, address, e).map(|(count, _)| count)
+        }
+        _ => None,
+    };
+
+    Some(EhFrameHdr {
+        address: address,
+        offset: offset,
+        size: data.len() as u64,
+        version: data[0],
+        eh_frame_ptr: eh_frame_ptr.map(|(ptr, _)| ptr),
+        fde_count: fde_count,
+    })
+}
@@ -2,21 +2,31 @@
 macro_rules! elf_ehdr {
     ($half:ty, $word:ty, $addr:ty, $off:ty) => {
         use ::std::fmt::{Display, Formatter};
+        use ::std::io::{self, Write};
         use ::to_host::to_host_copy::ToHostCopy;
         use ::to_host::to_host_in_place::ToHostInPlace;
         use ::to_host::{Endianness, ToHostInPlaceStruct, ToHostCopyStruct};
         use super::super::elf_ident::ElfIdent;
         use super::super::elf_ident_named::ElfIdentNamed;
-        use super::super::elf_ehdr_type::ElfEhdrType;
-        use super::super::elf_ehdr_machine::ElfEhdrMachine;
+        use super::super::elf_ei_class::ElfEiClass;
+        use super::super::elf_ei_data::ElfEiData;
+        use super::super::elf_ei_version::{ElfEiVersion, get_e_version_string};
+        use super::super::elf_ehdr_type::get_type_string;
+        use super::super::elf_ehdr_machine::get_machine_string;
+        use super::super::elf_error::ElfError;
+        use super::super::elf_target::TargetInfo;
         use super::primitive::*;
 
+        // e_phnum when there are too many program headers for it to hold
+        const PN_XNUM: u16 = 0xffff;
+
         #[repr(C)]
         #[derive(Debug, Clone, Copy)]
         pub struct Elf_Ehdr {
             e_ident: ElfIdent,
-            e_type: ElfEhdrType,
-            e_machine: ElfEhdrMachine,
+            e_type: $half,
+            // Raw, as files name machines newer than ElfEhdrMachine
+            e_machine: $half,
             e_version: $word,
             e_entry: $addr,
             e_phoff: $off,
@@ -46,7 +56,7 @@ macro_rules! elf_ehdr {
                         "{}",
                         "  Type:                              {}\n",
                         "  Machine:                           {}\n",
-                        "  Version:                           {:#x}\n",
+                        "  Version:                           {}\n",
                         "  Entry point address:               {:#x}\n",
                         "  Start of program headers:          {} (bytes into file)\n",
                         "  Start of section headers:          {} (bytes into file)\n",
@@ -59,10 +69,10 @@ macro_rules! elf_ehdr {
                         "  Section header string table index: {}\n",
                     ),
                     self.e_ident,
-                    ehdr_ident,
-                    self.e_type.to_host_copy(&e),
-                    self.e_machine.to_host_copy(&e),
-                    self.e_version.to_host_copy(&e),
+                    ehdr_ident.describe(self.e_machine.to_host_copy(&e)),
+                    get_type_string(self.e_type.to_host_copy(&e)),
+                    get_machine_string(self.e_machine.to_host_copy(&e)),
+                    get_e_version_string(self.e_version.to_host_copy(&e) as u32),
                     self.e_entry.to_host_copy(&e),
                     self.e_phoff.to_host_copy(&e),
                     self.e_shoff.to_host_copy(&e),
@@ -103,6 +113,61 @@ macro_rules! elf_ehdr {
             pub fn get_phnum(&self) -> u16 {
                 self.e_phnum
             }
+            // The number of program headers. From PN_XNUM on it is kept in
+            // sh_info of section header 0 instead of e_phnum; if there is no
+            // such section, PN_XNUM is all there is to go by. The header is
+            // expected to be in host byte order.
+            #[allow(dead_code)]
+            pub fn get_program_header_count<R: ::std::io::Read + ::std::io::Seek>(
+                &self, reader: &mut R)
+                -> usize
+            {
+                if self.e_phnum != PN_XNUM || self.e_shoff == 0 {
+                    return self.e_phnum as usize;
+                }
+                match self.read_section_zero(reader) {
+                    Some(shdr) => shdr.get_info() as usize,
+                    None => self.e_phnum as usize,
+                }
+            }
+            // The number of section headers. From SHN_LORESERVE on e_shnum is
+            // 0 and the number is kept in sh_size of section header 0 instead,
+            // as e_shstrndx is kept in its sh_link. The header is expected to
+            // be in host byte order.
+            #[allow(dead_code)]
+            pub fn get_section_header_count<R: ::std::io::Read + ::std::io::Seek>(
+                &self, reader: &mut R)
+                -> u64
+            {
+                if self.e_shnum != 0 || self.e_shoff == 0 {
+                    return self.e_shnum as u64;
+                }
+                match self.read_section_zero(reader) {
+                    Some(shdr) => shdr.get_size() as u64,
+                    None => 0,
+                }
+            }
+            // Section header 0, which holds the counts too large for the
+            // header (extended numbering), in host byte order. None if it
+            // can't be read.
+            fn read_section_zero<R: ::std::io::Read + ::std::io::Seek>(
+                &self, reader: &mut R)
+                -> Option<super::elf_shdr::Elf_Shdr>
+            {
+                use ::std::mem::size_of;
+                use super::elf_shdr::Elf_Shdr;
+
+                let b = super::super::helpers::read_bytes(
+                    reader, self.e_shoff as u64, size_of::<Elf_Shdr>() as u64)
+                    .unwrap_or(Vec::new());
+                if b.len() != size_of::<Elf_Shdr>() {
+                    return None;
+                }
+                let shdr: Elf_Shdr = unsafe {
+                    ::std::ptr::read_unaligned(b.as_ptr() as *const Elf_Shdr)
+                };
+                Some(shdr.to_host_copy(&self.get_endianness()))
+            }
             #[allow(dead_code)]
             pub fn get_phoff(&self) -> $off {
                 self.e_phoff
@@ -118,11 +183,11 @@ macro_rules! elf_ehdr {
                 }
             }
             #[allow(dead_code)]
-            pub fn get_type(&self) -> ElfEhdrType {
+            pub fn get_type(&self) -> u16 {
                 self.e_type
             }
             #[allow(dead_code)]
-            pub fn get_machine(&self) -> ElfEhdrMachine {
+            pub fn get_machine(&self) -> u16 {
                 self.e_machine
             }
             #[allow(dead_code)]
@@ -154,6 +219,248 @@ macro_rules! elf_ehdr {
                 self.e_shstrndx
             }
 
+            #[allow(dead_code)]
+            pub fn get_class(&self) -> ElfEiClass {
+                self.get_ident().get_class()
+            }
+            #[allow(dead_code)]
+            pub fn get_data(&self) -> ElfEiData {
+                self.get_ident().get_data()
+            }
+            // The header is expected to be in host byte order.
+            #[allow(dead_code)]
+            pub fn get_target(&self) -> TargetInfo {
+                let ident = self.get_ident();
+                TargetInfo::new(
+                    self.e_machine,
+                    ident.get_class(),
+                    ident.get_data(),
+                    ident.get_os_abi())
+            }
+            #[allow(dead_code)]
+            pub fn is_64bit(&self) -> bool {
+                self.get_class() == ElfEiClass::ELFCLASS64
+            }
+            #[allow(dead_code)]
+            pub fn is_little_endian(&self) -> bool {
+                self.get_data() == ElfEiData::ELFDATA2LSB
+            }
+
+            // Without a known class and data encoding nothing past the
+            // identification bytes can be interpreted.
+            #[allow(dead_code)]
+            pub fn validate_ident(&self) -> Result<(), ElfError> {
+                let class = self.get_class();
+                if !class.is_valid() {
+                    return Err(ElfError::UnsupportedClass(class.get_value()))
+                }
+                let data = self.get_data();
+                if !data.is_valid() {
+                    return Err(ElfError::UnknownEndianness(data.get_value()))
+                }
+                Ok(())
+            }
+
+            // The end of the last thing in the file that the headers account
+            // for: the header itself, the program and section header tables,
+            // segments and sections. Bytes past it belong to nothing, such as
+            // data appended to the file. Everything is expected to be in host
+            // byte order.
+            #[allow(dead_code)]
+            pub fn get_used_extent(
+                &self, phdrs: &[super::elf_phdr::Elf_Phdr], shdrs: &[super::elf_shdr::Elf_Shdr])
+                -> u64
+            {
+                use super::super::elf_shdr_type::ElfShdrType;
+
+                // phdrs holds the real count when e_phnum is PN_XNUM, and shdrs
+                // when e_shnum is 0
+                let phnum =
+                    if self.e_phnum == PN_XNUM { phdrs.len() } else { self.e_phnum as usize };
+                let shnum =
+                    if self.e_shnum == 0 { shdrs.len() } else { self.e_shnum as usize };
+                let tables = [
+                    (0, self.e_ehsize as u64),
+                    (self.e_phoff as u64, self.e_phentsize as u64 * phnum as u64),
+                    (self.e_shoff as u64, self.e_shentsize as u64 * shnum as u64),
+                ];
+                let segments = phdrs.iter()
+                    .map(|p| (p.get_offset() as u64, p.get_filesz() as u64));
+                let sections = shdrs.iter()
+                    .filter(|s| ElfShdrType::new(s.get_type()) != ElfShdrType::SHT_NOBITS)
+                    .map(|s| (s.get_offset() as u64, s.get_size() as u64));
+                tables.iter().cloned().chain(segments).chain(sections)
+                    .filter(|&(_, size)| size != 0)
+                    .map(|(offset, size)| offset.saturating_add(size))
+                    .max()
+                    .unwrap_or(0)
+            }
+
+            // Signs that the header doesn't really have this class: the entry
+            // sizes it records don't match this class's structures, or its
+            // tables lie past the end of the file. The header is expected to
+            // be in host byte order, `phnum` is get_program_header_count and
+            // `shnum` get_section_header_count.
+            #[allow(dead_code)]
+            pub fn get_layout_problems(
+                &self, file_size: u64, phnum: usize, shnum: u64)
+                -> Vec<String>
+            {
+                use ::std::mem::size_of;
+                use super::elf_phdr::Elf_Phdr;
+                use super::elf_shdr::Elf_Shdr;
+
+                let mut problems = Vec::new();
+                let sizes = [
+                    ("e_ehsize", self.e_ehsize, size_of::<Elf_Ehdr>(), 1),
+                    ("e_phentsize", self.e_phentsize, size_of::<Elf_Phdr>(), self.e_phnum as u64),
+                    ("e_shentsize", self.e_shentsize, size_of::<Elf_Shdr>(), shnum),
+                ];
+                for &(name, size, expected, count) in &sizes {
+                    if count != 0 && size as usize != expected {
+                        problems.push(format!(
+                            "{} is {}, but should be {}", name, size, expected));
+                    }
+                }
+                let tables = [
+                    ("program", self.e_phoff as u64, self.e_phentsize, phnum),
+                    ("section", self.e_shoff as u64, self.e_shentsize, shnum as usize),
+                ];
+                for &(name, offset, entsize, num) in &tables {
+                    let size = entsize as u64 * num as u64;
+                    if num != 0 && (offset > file_size || size > file_size - offset) {
+                        problems.push(format!(
+                            "the {} header table at {:#x} ends past the end of the file",
+                            name, offset));
+                    }
+                }
+                problems
+            }
+
+            // Both version fields must be EV_CURRENT; no other version has
+            // been defined.
+            #[allow(dead_code)]
+            pub fn get_version_problems(&self) -> Vec<String> {
+                let mut problems = Vec::new();
+                let ei_version = self.get_ident().get_version();
+                if ei_version != ElfEiVersion::EV_CURRENT {
+                    problems.push(format!(
+                        "EI_VERSION is {}, but should be {}",
+                        ei_version.get_value(), ElfEiVersion::EV_CURRENT.get_value()));
+                }
+                if self.e_version != ElfEiVersion::EV_CURRENT.get_value() as $word {
+                    problems.push(format!(
+                        "e_version is {}, but should be {}",
+                        self.e_version, ElfEiVersion::EV_CURRENT.get_value()));
+                }
+                problems
+            }
+
+            // Prints the part of the header which doesn't depend on the class
+            // and data encoding, with invalid fields marked as such.
+            #[allow(dead_code)]
+            pub fn print_ident<W: Write>(&self, out: &mut W) -> io::Result<()> {
+                self.e_ident.print(out)
+            }
+
+            // Dumps the header bytes as they are in the file, one field per
+            // line, labelled with its byte range.
+            #[allow(dead_code)]
+            pub fn print_raw<W: Write>(&self, out: &mut W) -> io::Result<()> {
+                use ::std::mem::size_of;
+
+                let fields: [(&'static str, usize); 14] = [
+                    ("e_ident", size_of::<ElfIdent>()),
+                    ("e_type", size_of::<$half>()),
+                    ("e_machine", size_of::<$half>()),
+                    ("e_version", size_of::<$word>()),
+                    ("e_entry", size_of::<$addr>()),
+                    ("e_phoff", size_of::<$off>()),
+                    ("e_shoff", size_of::<$off>()),
+                    ("e_flags", size_of::<$word>()),
+                    ("e_ehsize", size_of::<$half>()),
+                    ("e_phentsize", size_of::<$half>()),
+                    ("e_phnum", size_of::<$half>()),
+                    ("e_shentsize", size_of::<$half>()),
+                    ("e_shnum", size_of::<$half>()),
+                    ("e_shstrndx", size_of::<$half>()),
+                ];
+                let bytes: &[u8] = unsafe {
+                    ::std::slice::from_raw_parts(
+                        self as *const Elf_Ehdr as *const u8,
+                        size_of::<Elf_Ehdr>())
+                };
+
+                try!(writeln!(out, "Raw ELF header ({} bytes):", bytes.len()));
+                try!(writeln!(out, "  Offset  {:<47}  Field", "Bytes"));
+                let print_row = |out: &mut W, offset: usize, field: &[u8], name: &str| {
+                    let hex: Vec<String> = field.iter()
+                        .map(|b| format!("{:02x}", b))
+                        .collect();
+                    writeln!(
+                        out,
+                        "  {:02}..{:02}  {:<47}  {}",
+                        offset, offset + field.len(), hex.join(" "), name)
+                };
+                let mut offset = 0;
+                for &(name, size) in fields.iter() {
+                    if name == "e_ident" {
+                        // One row per field of the identification, so that
+                        // each byte is labeled
+                        for (ei_name, field) in self.e_ident.get_labeled_bytes() {
+                            try!(print_row(
+                                out, offset, field, &format!("e_ident[{}]", ei_name)));
+                            offset += field.len();
+                        }
+                        continue;
+                    }
+                    try!(print_row(out, offset, &bytes[offset..offset + size], name));
+                    offset += size;
+                }
+                Ok(())
+            }
+
+            // Where e_phoff and e_shoff are and where the tables they point
+            // at are, counted from the start of the file rather than of the
+            // ELF image (which starts `base` bytes in with --offset), to go
+            // with a hex editor. The header is expected to be in host byte
+            // order, `phnum` and `shnum` are the real entry counts.
+            #[allow(dead_code)]
+            pub fn print_offsets<W: Write>(
+                &self, base: u64, phnum: usize, shnum: usize, out: &mut W)
+                -> io::Result<()>
+            {
+                use ::std::mem::size_of;
+
+                let phoff_at = (size_of::<ElfIdent>() + 2 * size_of::<$half>()
+                    + size_of::<$word>() + size_of::<$addr>()) as u64;
+                let shoff_at = phoff_at + size_of::<$off>() as u64;
+
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "File offsets:"));
+                try!(writeln!(
+                    out, "  {:#010x}  e_phoff, which holds {:#x}", base + phoff_at, self.e_phoff));
+                try!(writeln!(
+                    out, "  {:#010x}  e_shoff, which holds {:#x}", base + shoff_at, self.e_shoff));
+                let tables = [
+                    ("program", self.e_phoff as u64, self.e_phentsize, phnum),
+                    ("section", self.e_shoff as u64, self.e_shentsize, shnum),
+                ];
+                for &(name, offset, entsize, num) in &tables {
+                    if num == 0 {
+                        try!(writeln!(out, "  {:10}  no {} header table", "", name));
+                        continue;
+                    }
+                    let start = base.saturating_add(offset);
+                    try!(writeln!(
+                        out,
+                        "  {:#010x}  the {} header table, {} entries of {} bytes, to {:#x}",
+                        start, name, num, entsize,
+                        start.saturating_add(entsize as u64 * num as u64)));
+                }
+                Ok(())
+            }
+
             #[allow(dead_code)]
             pub fn get_endianness(&self) -> Endianness {
                 let ehdr_ptr: *mut Elf_Ehdr = unsafe {
@@ -1,4 +1,5 @@
 use ::std::fmt::{Display, Formatter};
+use self::ElfEhdrMachine::*;
 
 #[repr(u16)]
 #[derive(Debug,Clone,Copy)]
@@ -82,104 +83,183 @@ pub enum ElfEhdrMachine {
     EM_OPENRISC,
     EM_ARC_A5,
     EM_XTENSA,
-    EM_AARCH64,
-    EM_TILEPRO,
+
+    EM_AARCH64 = 183,
+
+    EM_TILEPRO = 188,
     EM_MICROBLAZE,
+    EM_CUDA,
     EM_TILEGX,
+
+    EM_AMDGPU = 224,
+
+    EM_RISCV = 243,
+
+    EM_BPF = 247,
+
+    EM_LOONGARCH = 258,
     EM_NUM,
 
     EM_ALPHA = 0x9026,
 }
 
+impl ElfEhdrMachine {
+    // The architecture name as commonly used by toolchains, for the machines
+    // which have one.
+    #[allow(dead_code)]
+    pub fn get_short_name(&self) -> Option<&'static str> {
+        machine_short_name(*self as u16)
+    }
+}
+
+// The architecture names toolchains use, for the machines which have one
+const MACHINE_SHORT_NAMES: &'static [(ElfEhdrMachine, &'static str)] = &[
+    (EM_SPARC, "sparc"),
+    (EM_386, "i386"),
+    (EM_68K, "m68k"),
+    (EM_MIPS, "mips"),
+    (EM_MIPS_RS3_LE, "mips"),
+    (EM_PARISC, "hppa"),
+    (EM_PPC, "powerpc"),
+    (EM_PPC64, "powerpc64"),
+    (EM_S390, "s390"),
+    (EM_ARM, "arm"),
+    (EM_SH, "sh"),
+    (EM_SPARCV9, "sparc64"),
+    (EM_IA_64, "ia64"),
+    (EM_X86_64, "x86-64"),
+    (EM_AVR, "avr"),
+    (EM_XTENSA, "xtensa"),
+    (EM_AARCH64, "aarch64"),
+    (EM_CUDA, "nvptx"),
+    (EM_AMDGPU, "amdgcn"),
+    (EM_RISCV, "riscv"),
+    (EM_BPF, "bpf"),
+    (EM_LOONGARCH, "loongarch"),
+    (EM_ALPHA, "alpha"),
+];
+
+// The toolchain name of an e_machine value, e.g. `x86-64`
+#[allow(dead_code)]
+pub fn machine_short_name(e_machine: u16) -> Option<&'static str> {
+    MACHINE_SHORT_NAMES.iter()
+        .find(|&&(machine, _)| machine as u16 == e_machine)
+        .map(|&(_, name)| name)
+}
+
+// The descriptions of the machines, as the file header shows them
+const MACHINE_NAMES: &'static [(ElfEhdrMachine, &'static str)] = &[
+    (EM_NONE, "No machine"),
+    (EM_M32, "AT&T WE 32100"),
+    (EM_SPARC, "SUN SPARC"),
+    (EM_386, "Intel 80386"),
+    (EM_68K, "Motorola m68k family"),
+    (EM_88K, "Motorola m88k family"),
+    (EM_860, "Intel 80860"),
+    (EM_MIPS, "MIPS R3000 big-endian"),
+    (EM_S370, "IBM System/370"),
+    (EM_MIPS_RS3_LE, "MIPS R3000 little-endian"),
+
+    (EM_PARISC, "HPPA"),
+    (EM_VPP500, "Fujitsu VPP500"),
+    (EM_SPARC32PLUS, "Sun's \"v8plus\""),
+    (EM_960, "Intel 80960"),
+    (EM_PPC, "PowerPC"),
+    (EM_PPC64, "PowerPC 64-bit"),
+    (EM_S390, "IBM S390"),
+
+    (EM_V800, "NEC V800 series"),
+    (EM_FR20, "Fujitsu FR20"),
+    (EM_RH32, "TRW RH-32"),
+    (EM_RCE, "Motorola RCE"),
+    (EM_ARM, "ARM"),
+    (EM_FAKE_ALPHA, "Digital Alpha"),
+    (EM_SH, "Hitachi SH"),
+    (EM_SPARCV9, "SPARC v9 64-bit"),
+    (EM_TRICORE, "Siemens Tricore"),
+    (EM_ARC, "Argonaut RISC Core"),
+    (EM_H8_300, "Hitachi H8/300"),
+    (EM_H8_300H, "Hitachi H8/300H"),
+    (EM_H8S, "Hitachi H8S"),
+    (EM_H8_500, "Hitachi H8/500"),
+    (EM_IA_64, "Intel Merced"),
+    (EM_MIPS_X, "Stanford MIPS-X"),
+    (EM_COLDFIRE, "Motorola Coldfire"),
+    (EM_68HC12, "Motorola M68HC12"),
+    (EM_MMA, "Fujitsu MMA Multimedia Accelerato"),
+    (EM_PCP, "Siemens PCP"),
+    (EM_NCPU, "Sony nCPU embeeded RISC"),
+    (EM_NDR1, "Denso NDR1 microprocessor"),
+    (EM_STARCORE, "Motorola Start*Core processor"),
+    (EM_ME16, "Toyota ME16 processor"),
+    (EM_ST100, "STMicroelectronic ST100 processor"),
+    (EM_TINYJ, "Advanced Logic Corp. Tinyj emb.fa"),
+    (EM_X86_64, "Advanced Micro Devices x86-64"),
+    (EM_PDSP, "Sony DSP Processor"),
+
+    (EM_FX66, "Siemens FX66 microcontroller"),
+    (EM_ST9PLUS, "STMicroelectronics ST9+ 8/16 mc"),
+    (EM_ST7, "STmicroelectronics ST7 8 bit mc"),
+    (EM_68HC16, "Motorola MC68HC16 microcontroller"),
+    (EM_68HC11, "Motorola MC68HC11 microcontroller"),
+    (EM_68HC08, "Motorola MC68HC08 microcontroller"),
+    (EM_68HC05, "Motorola MC68HC05 microcontroller"),
+    (EM_SVX, "Silicon Graphics SVx"),
+    (EM_ST19, "STMicroelectronics ST19 8 bit mc"),
+    (EM_VAX, "Digital VAX"),
+    (EM_CRIS, "Axis Communications 32-bit embedded processor"),
+    (EM_JAVELIN, "Infineon Technologies 32-bit embedded processor"),
+    (EM_FIREPATH, "Element 14 64-bit DSP Processor"),
+    (EM_ZSP, "LSI Logic 16-bit DSP Processor"),
+    (EM_MMIX, "Donald Knuth's educational 64-bit processor"),
+    (EM_HUANY, "Harvard University machine-independent object files"),
+    (EM_PRISM, "SiTera Prism"),
+    (EM_AVR, "Atmel AVR 8-bit microcontroller"),
+    (EM_FR30, "Fujitsu FR30"),
+    (EM_D10V, "Mitsubishi D10V"),
+    (EM_D30V, "Mitsubishi D30V"),
+    (EM_V850, "NEC v850"),
+    (EM_M32R, "Mitsubishi M32R"),
+    (EM_MN10300, "Matsushita MN10300"),
+    (EM_MN10200, "Matsushita MN10200"),
+    (EM_PJ, "picoJava"),
+    (EM_OPENRISC, "OpenRISC 32-bit embedded processor"),
+    (EM_ARC_A5, "ARC Cores Tangent-A5"),
+    (EM_XTENSA, "Tensilica Xtensa Architecture"),
+    (EM_AARCH64, "ARM AARCH64"),
+    (EM_TILEPRO, "Tilera TILEPro"),
+    (EM_MICROBLAZE, "Xilinx MicroBlaze"),
+    (EM_CUDA, "NVIDIA CUDA architecture"),
+    (EM_TILEGX, "Tilera TILE-Gx"),
+    (EM_AMDGPU, "AMD GPU"),
+    (EM_RISCV, "RISC-V"),
+    (EM_BPF, "Linux BPF -- in-kernel virtual machine"),
+    (EM_LOONGARCH, "LoongArch"),
+    (EM_ALPHA, "Alpha"),
+];
+
+// The description of an e_machine value, for code which has the raw value
+// rather than an ElfEhdrMachine. None for machines this crate doesn't know.
+#[allow(dead_code)]
+pub fn machine_name(e_machine: u16) -> Option<&'static str> {
+    MACHINE_NAMES.iter()
+        .find(|&&(machine, _)| machine as u16 == e_machine)
+        .map(|&(_, name)| name)
+}
+
+// The description of any e_machine value, with readelf's fallback for
+// machines which aren't in the table.
+#[allow(dead_code)]
+pub fn get_machine_string(e_machine: u16) -> String {
+    match machine_name(e_machine) {
+        Some(name) => name.to_string(),
+        None => format!("<unknown>: {:#x}", e_machine),
+    }
+}
+
 impl Display for ElfEhdrMachine {
     fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
-        use self::ElfEhdrMachine::*;
-        let s = match *self {
-            EM_NONE => "No machine",
-            EM_M32 => "AT&T WE 32100",
-            EM_SPARC => "SUN SPARC",
-            EM_386 => "Intel 80386",
-            EM_68K => "Motorola m68k family",
-            EM_88K => "Motorola m88k family",
-            EM_860 => "Intel 80860",
-            EM_MIPS => "MIPS R3000 big-endian",
-            EM_S370 => "IBM System/370",
-            EM_MIPS_RS3_LE => "MIPS R3000 little-endian",
-
-            EM_PARISC => "HPPA",
-            EM_VPP500 => "Fujitsu VPP500",
-            EM_SPARC32PLUS => "Sun's \"v8plus\"",
-            EM_960 => "Intel 80960",
-            EM_PPC => "PowerPC",
-            EM_PPC64 => "PowerPC 64-bit",
-            EM_S390 => "IBM S390",
-
-            EM_V800 => "NEC V800 series",
-            EM_FR20 => "Fujitsu FR20",
-            EM_RH32 => "TRW RH-32",
-            EM_RCE => "Motorola RCE",
-            EM_ARM => "ARM",
-            EM_FAKE_ALPHA => "Digital Alpha",
-            EM_SH => "Hitachi SH",
-            EM_SPARCV9 => "SPARC v9 64-bit",
-            EM_TRICORE => "Siemens Tricore",
-            EM_ARC => "Argonaut RISC Core",
-            EM_H8_300 => "Hitachi H8/300",
-            EM_H8_300H => "Hitachi H8/300H",
-            EM_H8S => "Hitachi H8S",
-            EM_H8_500 => "Hitachi H8/500",
-            EM_IA_64 => "Intel Merced",
-            EM_MIPS_X => "Stanford MIPS-X",
-            EM_COLDFIRE => "Motorola Coldfire",
-            EM_68HC12 => "Motorola M68HC12",
-            EM_MMA => "Fujitsu MMA Multimedia Accelerato",
-            EM_PCP => "Siemens PCP",
-            EM_NCPU => "Sony nCPU embeeded RISC",
-            EM_NDR1 => "Denso NDR1 microprocessor",
-            EM_STARCORE => "Motorola Start*Core processor",
-            EM_ME16 => "Toyota ME16 processor",
-            EM_ST100 => "STMicroelectronic ST100 processor",
-            EM_TINYJ => "Advanced Logic Corp. Tinyj emb.fa",
-            EM_X86_64 => "Advanced Micro Devices x86-64",
-            EM_PDSP => "Sony DSP Processor",
-
-            EM_FX66 => "Siemens FX66 microcontroller",
-            EM_ST9PLUS => "STMicroelectronics ST9+ 8/16 mc",
-            EM_ST7 => "STmicroelectronics ST7 8 bit mc",
-            EM_68HC16 => "Motorola MC68HC16 microcontroller",
-            EM_68HC11 => "Motorola MC68HC11 microcontroller",
-            EM_68HC08 => "Motorola MC68HC08 microcontroller",
-            EM_68HC05 => "Motorola MC68HC05 microcontroller",
-            EM_SVX => "Silicon Graphics SVx",
-            EM_ST19 => "STMicroelectronics ST19 8 bit mc",
-            EM_VAX => "Digital VAX",
-            EM_CRIS => "Axis Communications 32-bit embedded processor",
-            EM_JAVELIN => "Infineon Technologies 32-bit embedded processor",
-            EM_FIREPATH => "Element 14 64-bit DSP Processor",
-            EM_ZSP => "LSI Logic 16-bit DSP Processor",
-            EM_MMIX => "Donald Knuth's educational 64-bit processor",
-            EM_HUANY => "Harvard University machine-independent object files",
-            EM_PRISM => "SiTera Prism",
-            EM_AVR => "Atmel AVR 8-bit microcontroller",
-            EM_FR30 => "Fujitsu FR30",
-            EM_D10V => "Mitsubishi D10V",
-            EM_D30V => "Mitsubishi D30V",
-            EM_V850 => "NEC v850",
-            EM_M32R => "Mitsubishi M32R",
-            EM_MN10300 => "Matsushita MN10300",
-            EM_MN10200 => "Matsushita MN10200",
-            EM_PJ => "picoJava",
-            EM_OPENRISC => "OpenRISC 32-bit embedded processor",
-            EM_ARC_A5 => "ARC Cores Tangent-A5",
-            EM_XTENSA => "Tensilica Xtensa Architecture",
-            EM_AARCH64 => "ARM AARCH64",
-            EM_TILEPRO => "Tilera TILEPro",
-            EM_MICROBLAZE => "Xilinx MicroBlaze",
-            EM_TILEGX => "Tilera TILE-Gx",
-            EM_ALPHA => "Alpha",
-            _ => "Unknown machine",
-        };
-        write!(fmt, "{}", s)
+        write!(fmt, "{}", machine_name(*self as u16).unwrap_or("Unknown machine"))
     }
 }
 
@@ -1,5 +1,5 @@
-use ::std::fmt::{Display, Formatter};
-
+// e_type values. The header keeps e_type raw, as operating systems and
+// processors define their own types in the ranges below.
 #[repr(u16)]
 #[derive(Debug,PartialEq,PartialOrd,Eq,Ord,Clone,Copy)]
 #[allow(dead_code)]
@@ -9,27 +9,43 @@ pub enum ElfEhdrType {
     ET_EXEC,
     ET_DYN,
     ET_CORE,
+    ET_LOOS = 0xfe00,
+    ET_HIOS = 0xfeff,
     ET_LOPROC = 0xff00,
     ET_HIPROC = 0xffff,
 }
 
-impl Display for ElfEhdrType {
-    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
-        use self::ElfEhdrType::*;
-        let s = match *self {
-            ET_NONE => "NONE (No file type)",
-            ET_REL => "REL (Relocatable file)",
-            ET_EXEC => "EXEC (Executable file)",
-            ET_DYN => "DYN (Shared object file)",
-            ET_CORE => "CORE (Core file)",
-            ref x if *x >= ET_LOPROC && *x <= ET_HIPROC => "Processor-specific",
-            _ => "Unknown file type",
-        };
-        write!(fmt, "{}", s)
+// The name of an e_type value without its description, e.g. `EXEC`.
+#[allow(dead_code)]
+pub fn get_type_short_name(e_type: u16) -> &'static str {
+    use self::ElfEhdrType::*;
+    match e_type {
+        x if x == ET_NONE as u16 => "NONE",
+        x if x == ET_REL as u16 => "REL",
+        x if x == ET_EXEC as u16 => "EXEC",
+        x if x == ET_DYN as u16 => "DYN",
+        x if x == ET_CORE as u16 => "CORE",
+        x if x >= ET_LOOS as u16 && x <= ET_HIOS as u16 => "OS",
+        x if x >= ET_LOPROC as u16 => "PROC",
+        _ => "UNKNOWN",
     }
 }
 
-swap_in_place_wrapper!(ElfEhdrType, u16);
-to_host_in_place_wrapper!(ElfEhdrType, u16);
-swap_copy_wrapper!(ElfEhdrType, u16);
-to_host_copy_wrapper!(ElfEhdrType, u16);
\ No newline at end of file
+// The description of an e_type value as the file header shows it, the way
+// readelf does.
+#[allow(dead_code)]
+pub fn get_type_string(e_type: u16) -> String {
+    use self::ElfEhdrType::*;
+    let s = match e_type {
+        x if x == ET_NONE as u16 => "NONE (No file type)",
+        x if x == ET_REL as u16 => "REL (Relocatable file)",
+        x if x == ET_EXEC as u16 => "EXEC (Executable file)",
+        x if x == ET_DYN as u16 => "DYN (Shared object file)",
+        x if x == ET_CORE as u16 => "CORE (Core file)",
+        x if x >= ET_LOOS as u16 && x <= ET_HIOS as u16 =>
+            return format!("OS Specific: ({:x})", x),
+        x if x >= ET_LOPROC as u16 => return format!("Processor Specific: ({:x})", x),
+        x => return format!("<unknown>: {:x}", x),
+    };
+    s.to_string()
+}
@@ -1,12 +1,32 @@
 use ::std::fmt::{Display, Formatter};
+use super::elf_ei_os_abi::ElfEiOsAbi;
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[allow(dead_code)]
 pub struct ElfEiAbiVersion {
     data: u8,
 }
 
+#[allow(dead_code)]
+impl ElfEiAbiVersion {
+    pub fn get_value(&self) -> u8 {
+        self.data
+    }
+    // What the version means under `os_abi`, where it means something
+    // beyond the number. System V leaves the byte unspecified and GNU uses
+    // it for the glibc ABI level, so anything but 0 there is unusual.
+    pub fn get_note(&self, os_abi: ElfEiOsAbi) -> Option<&'static str> {
+        match (os_abi, self.data) {
+            (_, 0) => None,
+            (ElfEiOsAbi::ELFOSABI_NONE, _) => Some("unspecified for System V, usually 0"),
+            (ElfEiOsAbi::ELFOSABI_GNU, _) =>
+                Some("unusual for GNU: the glibc ABI level the loader must support"),
+            _ => None,
+        }
+    }
+}
+
 impl Display for ElfEiAbiVersion {
     fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
         write!(fmt, "{}", self.data)
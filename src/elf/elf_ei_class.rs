@@ -1,21 +1,37 @@
 use ::std::fmt::{Display, Formatter};
 
-#[repr(u8)]
-#[derive(Debug, PartialEq, Clone, Copy)]
+// A newtype rather than a `#[repr]` enum, so that a header with an odd class
+// byte can still be read and displayed.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ElfEiClass {
+    data: u8,
+}
+
 #[allow(dead_code)]
-pub enum ElfEiClass {
-    ELFCLASSNONE,
-    ELFCLASS32,
-    ELFCLASS64,
+impl ElfEiClass {
+    pub const ELFCLASSNONE: ElfEiClass = ElfEiClass { data: 0 };
+    pub const ELFCLASS32: ElfEiClass = ElfEiClass { data: 1 };
+    pub const ELFCLASS64: ElfEiClass = ElfEiClass { data: 2 };
+
+    pub fn new(data: u8) -> ElfEiClass {
+        ElfEiClass { data: data }
+    }
+    pub fn get_value(&self) -> u8 {
+        self.data
+    }
+    pub fn is_valid(&self) -> bool {
+        *self == ElfEiClass::ELFCLASS32 || *self == ElfEiClass::ELFCLASS64
+    }
 }
 
 impl Display for ElfEiClass {
     fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
-        use self::ElfEiClass::*;
         let s = match *self {
-            ELFCLASSNONE => "None",
-            ELFCLASS32 => "ELF32",
-            ELFCLASS64 => "ELF64",
+            ElfEiClass::ELFCLASSNONE => "None",
+            ElfEiClass::ELFCLASS32 => "ELF32",
+            ElfEiClass::ELFCLASS64 => "ELF64",
+            ElfEiClass { data } => return write!(fmt, "<invalid: {:#04x}>", data),
         };
         write!(fmt, "{}", s)
     }
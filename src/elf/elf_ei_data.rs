@@ -1,21 +1,37 @@
 use ::std::fmt::{Display, Formatter};
 
-#[repr(u8)]
-#[derive(Debug)]
+// A newtype rather than a `#[repr]` enum, so that a header with an odd data
+// encoding byte can still be read and displayed.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ElfEiData {
+    data: u8,
+}
+
 #[allow(dead_code)]
-pub enum ElfEiData {
-    ELFDATANONE,
-    ELFDATA2LSB,
-    ELFDATA2MSB,
+impl ElfEiData {
+    pub const ELFDATANONE: ElfEiData = ElfEiData { data: 0 };
+    pub const ELFDATA2LSB: ElfEiData = ElfEiData { data: 1 };
+    pub const ELFDATA2MSB: ElfEiData = ElfEiData { data: 2 };
+
+    pub fn new(data: u8) -> ElfEiData {
+        ElfEiData { data: data }
+    }
+    pub fn get_value(&self) -> u8 {
+        self.data
+    }
+    pub fn is_valid(&self) -> bool {
+        *self == ElfEiData::ELFDATA2LSB || *self == ElfEiData::ELFDATA2MSB
+    }
 }
 
 impl Display for ElfEiData {
     fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
-        use self::ElfEiData::*;
         let s = match *self {
-            ELFDATANONE => "None",
-            ELFDATA2LSB => "2's complement, little endian",
-            ELFDATA2MSB => "2's complement, big endian",
+            ElfEiData::ELFDATANONE => "None",
+            ElfEiData::ELFDATA2LSB => "2's complement, little endian",
+            ElfEiData::ELFDATA2MSB => "2's complement, big endian",
+            ElfEiData { data } => return write!(fmt, "<invalid: {:#04x}>", data),
         };
         write!(fmt, "{}", s)
     }
@@ -23,13 +39,12 @@ impl Display for ElfEiData {
 
 impl ElfEiData {
     pub fn get_endianness(&self) -> ::to_host::Endianness {
-        use self::ElfEiData::*;
         use ::to_host::Endianness::*;
 
         match *self {
-            ELFDATA2MSB => BE,
-            ELFDATA2LSB => LE,
-            ELFDATANONE => panic!("Unknown data format"),
+            ElfEiData::ELFDATA2MSB => BE,
+            ElfEiData::ELFDATA2LSB => LE,
+            _ => panic!("Unknown data format"),
         } 
     }
-}
\ No newline at end of file
+}
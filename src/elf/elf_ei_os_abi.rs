@@ -1,48 +1,116 @@
 use ::std::fmt::{Display, Formatter};
+use super::elf_ehdr_machine::ElfEhdrMachine::{EM_AMDGPU, EM_ARM};
 
-#[repr(u8)]
-#[derive(Debug)]
-#[allow(dead_code)]
-pub enum ElfEiOsAbi {
-    ELFOSABI_NONE = 0,
-    ELFOSABI_HPUX = 1,
-    ELFOSABI_NETBSD = 2,
-    ELFOSABI_GNU = 3,
-    ELFOSABI_SOLARIS = 6,
-    ELFOSABI_AIX = 7,
-    ELFOSABI_IRIX = 8,
-    ELFOSABI_FREEBSD = 9,
-    ELFOSABI_TRU64 = 10,
-    ELFOSABI_MODESTO = 11,
-    ELFOSABI_OPENBSD = 12,
-    ELFOSABI_ARM_AEABI = 64,
-    ELFOSABI_ARM = 97,
-    ELFOSABI_STANDALONE = 255
+// The OS/ABI byte is frequently set to values this tool doesn't know about
+// (GPU toolchains, niche operating systems), so it's a newtype over the raw
+// byte rather than a `#[repr]` enum.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ElfEiOsAbi {
+    data: u8,
 }
 
 #[allow(dead_code)]
-const ELFOSABI_SYSV: u8 = ElfEiOsAbi::ELFOSABI_NONE as u8;
-#[allow(dead_code)]
-const ELFOSABI_LINUX: u8 = ElfEiOsAbi::ELFOSABI_GNU as u8;
+impl ElfEiOsAbi {
+    pub const ELFOSABI_NONE: ElfEiOsAbi = ElfEiOsAbi { data: 0 };
+    pub const ELFOSABI_HPUX: ElfEiOsAbi = ElfEiOsAbi { data: 1 };
+    pub const ELFOSABI_NETBSD: ElfEiOsAbi = ElfEiOsAbi { data: 2 };
+    pub const ELFOSABI_GNU: ElfEiOsAbi = ElfEiOsAbi { data: 3 };
+    pub const ELFOSABI_SOLARIS: ElfEiOsAbi = ElfEiOsAbi { data: 6 };
+    pub const ELFOSABI_AIX: ElfEiOsAbi = ElfEiOsAbi { data: 7 };
+    pub const ELFOSABI_IRIX: ElfEiOsAbi = ElfEiOsAbi { data: 8 };
+    pub const ELFOSABI_FREEBSD: ElfEiOsAbi = ElfEiOsAbi { data: 9 };
+    pub const ELFOSABI_TRU64: ElfEiOsAbi = ElfEiOsAbi { data: 10 };
+    pub const ELFOSABI_MODESTO: ElfEiOsAbi = ElfEiOsAbi { data: 11 };
+    pub const ELFOSABI_OPENBSD: ElfEiOsAbi = ElfEiOsAbi { data: 12 };
+    pub const ELFOSABI_OPENVMS: ElfEiOsAbi = ElfEiOsAbi { data: 13 };
+    pub const ELFOSABI_NSK: ElfEiOsAbi = ElfEiOsAbi { data: 14 };
+    pub const ELFOSABI_AROS: ElfEiOsAbi = ElfEiOsAbi { data: 15 };
+    pub const ELFOSABI_FENIXOS: ElfEiOsAbi = ElfEiOsAbi { data: 16 };
+    pub const ELFOSABI_CLOUDABI: ElfEiOsAbi = ElfEiOsAbi { data: 17 };
+    pub const ELFOSABI_OPENVOS: ElfEiOsAbi = ElfEiOsAbi { data: 18 };
+    pub const ELFOSABI_CUDA: ElfEiOsAbi = ElfEiOsAbi { data: 51 };
+    // Values from 64 on are machine-specific and may alias each other.
+    pub const ELFOSABI_ARM_AEABI: ElfEiOsAbi = ElfEiOsAbi { data: 64 };
+    pub const ELFOSABI_AMDGPU_HSA: ElfEiOsAbi = ElfEiOsAbi { data: 64 };
+    pub const ELFOSABI_AMDGPU_PAL: ElfEiOsAbi = ElfEiOsAbi { data: 65 };
+    pub const ELFOSABI_AMDGPU_MESA3D: ElfEiOsAbi = ElfEiOsAbi { data: 66 };
+    pub const ELFOSABI_ARM: ElfEiOsAbi = ElfEiOsAbi { data: 97 };
+    pub const ELFOSABI_STANDALONE: ElfEiOsAbi = ElfEiOsAbi { data: 255 };
+
+    pub const ELFOSABI_SYSV: ElfEiOsAbi = ElfEiOsAbi::ELFOSABI_NONE;
+    pub const ELFOSABI_LINUX: ElfEiOsAbi = ElfEiOsAbi::ELFOSABI_GNU;
+
+    pub fn get_value(&self) -> u8 {
+        self.data
+    }
+    // A one-word name, as opposed to the descriptive one of Display.
+    pub fn get_short_name(&self) -> Option<&'static str> {
+        let s = match *self {
+            ElfEiOsAbi::ELFOSABI_NONE => "SYSV",
+            ElfEiOsAbi::ELFOSABI_HPUX => "HPUX",
+            ElfEiOsAbi::ELFOSABI_NETBSD => "NetBSD",
+            ElfEiOsAbi::ELFOSABI_GNU => "GNU",
+            ElfEiOsAbi::ELFOSABI_SOLARIS => "Solaris",
+            ElfEiOsAbi::ELFOSABI_AIX => "AIX",
+            ElfEiOsAbi::ELFOSABI_IRIX => "IRIX",
+            ElfEiOsAbi::ELFOSABI_FREEBSD => "FreeBSD",
+            ElfEiOsAbi::ELFOSABI_TRU64 => "TRU64",
+            ElfEiOsAbi::ELFOSABI_MODESTO => "Modesto",
+            ElfEiOsAbi::ELFOSABI_OPENBSD => "OpenBSD",
+            ElfEiOsAbi::ELFOSABI_OPENVMS => "OpenVMS",
+            ElfEiOsAbi::ELFOSABI_NSK => "NSK",
+            ElfEiOsAbi::ELFOSABI_AROS => "AROS",
+            ElfEiOsAbi::ELFOSABI_FENIXOS => "FenixOS",
+            ElfEiOsAbi::ELFOSABI_CLOUDABI => "CloudABI",
+            ElfEiOsAbi::ELFOSABI_OPENVOS => "OpenVOS",
+            ElfEiOsAbi::ELFOSABI_CUDA => "CUDA",
+            ElfEiOsAbi::ELFOSABI_STANDALONE => "Standalone",
+            _ => return None,
+        };
+        Some(s)
+    }
+    // The description, as in Display, in a file for the machine `e_machine`.
+    // Values from 64 on mean different things for different machines
+    // (ELFOSABI_ARM_AEABI is ELFOSABI_AMDGPU_HSA), so like readelf this
+    // names them by machine.
+    pub fn get_description(&self, e_machine: u16) -> String {
+        let s = match self.data {
+            64 if e_machine == EM_ARM as u16 => "ARM EABI",
+            97 if e_machine == EM_ARM as u16 => "ARM",
+            64 if e_machine == EM_AMDGPU as u16 => "AMD HSA",
+            65 if e_machine == EM_AMDGPU as u16 => "AMD PAL",
+            66 if e_machine == EM_AMDGPU as u16 => "AMD Mesa3D",
+            _ => return format!("{}", self),
+        };
+        s.to_string()
+    }
+}
 
 impl Display for ElfEiOsAbi {
     fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
-        use self::ElfEiOsAbi::*;
         let s = match *self {
-            ELFOSABI_NONE => "UNIX - System V",
-            ELFOSABI_HPUX => "HP-UX",
-            ELFOSABI_NETBSD => "NetBSD",
-            ELFOSABI_GNU => "GNU ELF",
-            ELFOSABI_SOLARIS => "Sun Solaris",
-            ELFOSABI_AIX => "IBM AIX",
-            ELFOSABI_IRIX => "SGI Irix",
-            ELFOSABI_FREEBSD => "FreeBSD",
-            ELFOSABI_TRU64 => "Compaq TRU64 UNIX",
-            ELFOSABI_MODESTO => "Novell Modesto",
-            ELFOSABI_OPENBSD => "OpenBSD",
-            ELFOSABI_ARM_AEABI => "ARM EABI",
-            ELFOSABI_ARM => "ARM",
-            ELFOSABI_STANDALONE => "Standalone (embedded) application",
+            ElfEiOsAbi::ELFOSABI_NONE => "UNIX - System V",
+            ElfEiOsAbi::ELFOSABI_HPUX => "HP-UX",
+            ElfEiOsAbi::ELFOSABI_NETBSD => "NetBSD",
+            ElfEiOsAbi::ELFOSABI_GNU => "GNU ELF",
+            ElfEiOsAbi::ELFOSABI_SOLARIS => "Sun Solaris",
+            ElfEiOsAbi::ELFOSABI_AIX => "IBM AIX",
+            ElfEiOsAbi::ELFOSABI_IRIX => "SGI Irix",
+            ElfEiOsAbi::ELFOSABI_FREEBSD => "FreeBSD",
+            ElfEiOsAbi::ELFOSABI_TRU64 => "Compaq TRU64 UNIX",
+            ElfEiOsAbi::ELFOSABI_MODESTO => "Novell Modesto",
+            ElfEiOsAbi::ELFOSABI_OPENBSD => "OpenBSD",
+            ElfEiOsAbi::ELFOSABI_OPENVMS => "OpenVMS",
+            ElfEiOsAbi::ELFOSABI_NSK => "HP Non-Stop Kernel",
+            ElfEiOsAbi::ELFOSABI_AROS => "AROS",
+            ElfEiOsAbi::ELFOSABI_FENIXOS => "FenixOS",
+            ElfEiOsAbi::ELFOSABI_CLOUDABI => "Nuxi CloudABI",
+            ElfEiOsAbi::ELFOSABI_OPENVOS => "Stratus Technologies OpenVOS",
+            ElfEiOsAbi::ELFOSABI_CUDA => "NVIDIA CUDA",
+            // Machine-specific values are named by get_description
+            ElfEiOsAbi::ELFOSABI_STANDALONE => "Standalone (embedded) application",
+            ElfEiOsAbi { data } => return write!(fmt, "<unknown: {:#x}>", data),
         };
         write!(fmt, "{}", s)
     }
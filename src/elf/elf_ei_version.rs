@@ -1,20 +1,40 @@
 use ::std::fmt::{Display, Formatter};
 
-#[repr(u8)]
-#[derive(Debug)]
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ElfEiVersion {
+    data: u8,
+}
+
 #[allow(dead_code)]
-pub enum ElfEiVersion {
-    EV_NONE,
-    EV_CURRENT,
+impl ElfEiVersion {
+    pub const EV_NONE: ElfEiVersion = ElfEiVersion { data: 0 };
+    pub const EV_CURRENT: ElfEiVersion = ElfEiVersion { data: 1 };
+
+    pub fn get_value(&self) -> u8 {
+        self.data
+    }
 }
 
 impl Display for ElfEiVersion {
     fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
-        use self::ElfEiVersion::*;
         let s = match *self {
-            EV_NONE => "None",
-            EV_CURRENT => "1 (current)",
+            ElfEiVersion::EV_NONE => "None",
+            ElfEiVersion::EV_CURRENT => "1 (current)",
+            ElfEiVersion { data } => return write!(fmt, "<invalid: {:#04x}>", data),
         };
         write!(fmt, "{}", s)
     }
 }
+
+// e_version in the header proper takes the same values as EI_VERSION, but is
+// a whole word. It's shown in hex like readelf does, with its meaning next to
+// it, so that an unknown version stands out.
+#[allow(dead_code)]
+pub fn get_e_version_string(version: u32) -> String {
+    if version == ElfEiVersion::EV_CURRENT.get_value() as u32 {
+        format!("{:#x} (current)", version)
+    } else {
+        format!("{:#x} (unknown)", version)
+    }
+}
@@ -0,0 +1,48 @@
+use ::std::io::{self, Read, Seek, SeekFrom};
+
+// Wraps a reader so that an ELF image which starts `base` bytes into it,
+// as in a firmware blob, reads as if it were a file of its own: offset 0 is
+// the start of the image and the end is the end of the underlying reader.
+// The offsets in the headers are relative to the image, so they need no
+// adjusting.
+pub struct EmbeddedElf<R> {
+    inner: R,
+    base: u64,
+}
+
+#[allow(dead_code)]
+impl<R: Read + Seek> EmbeddedElf<R> {
+    pub fn new(inner: R, base: u64) -> EmbeddedElf<R> {
+        EmbeddedElf { inner: inner, base: base }
+    }
+    pub fn get_base(&self) -> u64 {
+        self.base
+    }
+}
+
+impl<R: Read> Read for EmbeddedElf<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for EmbeddedElf<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(offset) => match self.base.checked_add(offset) {
+                Some(offset) => SeekFrom::Start(offset),
+                None => return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput, "seek past the largest offset")),
+            },
+            pos => pos,
+        };
+        let offset = try!(self.inner.seek(pos));
+        if offset < self.base {
+            // Don't leave the reader before the image
+            try!(self.inner.seek(SeekFrom::Start(self.base)));
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput, "seek to before the start of the ELF image"));
+        }
+        Ok(offset - self.base)
+    }
+}
@@ -0,0 +1,45 @@
+use ::std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[allow(dead_code)]
+pub enum ElfError {
+    InvalidMagic,
+    UnsupportedClass(u8),
+    UnknownEndianness(u8),
+    UnexpectedEntrySize(u64),
+    Truncated,
+    // How many bytes of the ELF header there are
+    TruncatedHeader(u64),
+    // An offset from the file which can't be seeked to
+    BadOffset(u64),
+    // Reading the header failed, e.g. because the path is a directory
+    Unreadable(::std::io::ErrorKind),
+}
+
+impl Display for ElfError {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        use self::ElfError::*;
+        match *self {
+            InvalidMagic => write!(
+                fmt, "Not an ELF file - it has the wrong magic bytes at the start"),
+            UnsupportedClass(0) => write!(
+                fmt, "File class is ELFCLASSNONE, can't determine its bitness"),
+            UnsupportedClass(c) => write!(
+                fmt, "Unsupported ELF file class: {:#x}", c),
+            UnknownEndianness(0) => write!(
+                fmt, "Data encoding is ELFDATANONE, can't determine its endianness"),
+            UnknownEndianness(d) => write!(
+                fmt, "Unknown ELF data encoding: {:#x}", d),
+            UnexpectedEntrySize(s) => write!(
+                fmt, "Unexpected table entry size: {} (bytes)", s),
+            Truncated => write!(
+                fmt, "File is truncated"),
+            TruncatedHeader(size) => write!(
+                fmt, "File is truncated: the ELF header ends after {} bytes", size),
+            BadOffset(offset) => write!(
+                fmt, "Offset {:#x} can't be read from", offset),
+            Unreadable(kind) => write!(
+                fmt, "The file can't be read: {}", kind),
+        }
+    }
+}
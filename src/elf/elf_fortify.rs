@@ -0,0 +1,72 @@
+use ::std::collections::BTreeSet;
+use ::std::io::{self, Write};
+
+// Functions glibc has a checked (`__<name>_chk`) variant of, which
+// _FORTIFY_SOURCE substitutes when it can tell the size of the buffer.
+const FORTIFIABLE: [&'static str; 70] = [
+    "asprintf", "confstr", "dprintf", "fdelt", "fgets", "fgets_unlocked",
+    "fgetws", "fgetws_unlocked", "fprintf", "fread", "fread_unlocked",
+    "fwprintf", "getcwd", "getdomainname", "getgroups", "gethostname",
+    "getlogin_r", "gets", "getwd", "longjmp", "mbsnrtowcs", "mbsrtowcs",
+    "mbstowcs", "memcpy", "memmove", "mempcpy", "memset", "obstack_printf",
+    "obstack_vprintf", "poll", "ppoll", "pread", "pread64", "printf",
+    "ptsname_r", "read", "readlink", "readlinkat", "realpath", "recv",
+    "recvfrom", "snprintf", "sprintf", "stpcpy", "stpncpy", "strcat",
+    "strcpy", "strncat", "strncpy", "swprintf", "syslog", "ttyname_r",
+    "vasprintf", "vdprintf", "vfprintf", "vfwprintf", "vprintf", "vsnprintf",
+    "vsprintf", "vswprintf", "vsyslog", "vwprintf", "wcpcpy", "wcpncpy",
+    "wcrtomb", "wcscat", "wcscpy", "wcsncat", "wcsncpy", "wmemcpy",
+];
+
+// How far _FORTIFY_SOURCE reached in a file, judged by the functions it
+// imports: checked variants show it was on, and fortifiable functions
+// imported unchecked are calls it couldn't or didn't cover. Statically
+// linked files import nothing and can't be judged this way.
+#[derive(Debug, Clone, Default)]
+pub struct FortifyInfo {
+    checked: BTreeSet<String>,
+    unchecked: BTreeSet<String>,
+}
+
+#[allow(dead_code)]
+impl FortifyInfo {
+    pub fn new<'a, I: Iterator<Item = &'a str>>(imports: I) -> FortifyInfo {
+        let mut info = FortifyInfo::default();
+        for name in imports {
+            if name.starts_with("__") && name.ends_with("_chk") && name.len() > 6 {
+                info.checked.insert(name[2..name.len() - 4].to_string());
+            } else if FORTIFIABLE.contains(&name) {
+                info.unchecked.insert(name.to_string());
+            }
+        }
+        info
+    }
+    pub fn is_fortified(&self) -> bool {
+        !self.checked.is_empty()
+    }
+    // Base names of the checked functions, e.g. `memcpy` for __memcpy_chk.
+    pub fn get_checked(&self) -> &BTreeSet<String> {
+        &self.checked
+    }
+    pub fn get_unchecked(&self) -> &BTreeSet<String> {
+        &self.unchecked
+    }
+
+    pub fn print<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        try!(writeln!(
+            out,
+            "Fortified: {} ({} checked function{})",
+            if self.is_fortified() { "yes" } else { "no" },
+            self.checked.len(),
+            if self.checked.len() == 1 { "" } else { "s" }));
+        if !self.checked.is_empty() {
+            let names: Vec<&str> = self.checked.iter().map(|s| s.as_str()).collect();
+            try!(writeln!(out, "  Checked:   {}", names.join(", ")));
+        }
+        if !self.unchecked.is_empty() {
+            let names: Vec<&str> = self.unchecked.iter().map(|s| s.as_str()).collect();
+            try!(writeln!(out, "  Unchecked: {}", names.join(", ")));
+        }
+        Ok(())
+    }
+}
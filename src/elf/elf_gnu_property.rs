@@ -0,0 +1,185 @@
+use ::std::fmt::{Display, Formatter};
+use ::to_host::Endianness;
+use super::helpers::{get_u32, get_u64};
+
+pub const GNU_PROPERTY_STACK_SIZE: u32 = 1;
+pub const GNU_PROPERTY_NO_COPY_ON_PROTECTED: u32 = 2;
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xc0000000;
+pub const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc0000002;
+pub const GNU_PROPERTY_X86_ISA_1_NEEDED: u32 = 0xc0008002;
+pub const GNU_PROPERTY_X86_FEATURE_2_NEEDED: u32 = 0xc0008001;
+pub const GNU_PROPERTY_X86_ISA_1_USED: u32 = 0xc0010002;
+pub const GNU_PROPERTY_X86_FEATURE_2_USED: u32 = 0xc0010001;
+
+const X86_ISA_1_NAMES: [(u32, &'static str); 4] = [
+    (0x1, "x86-64-baseline"),
+    (0x2, "x86-64-v2"),
+    (0x4, "x86-64-v3"),
+    (0x8, "x86-64-v4"),
+];
+
+const X86_FEATURE_1_NAMES: [(u32, &'static str); 4] = [
+    (0x1, "IBT"),
+    (0x2, "SHSTK"),
+    (0x4, "LAM_U48"),
+    (0x8, "LAM_U57"),
+];
+
+const X86_FEATURE_2_NAMES: [(u32, &'static str); 12] = [
+    (0x1, "x86"),
+    (0x2, "x87"),
+    (0x4, "MMX"),
+    (0x8, "XMM"),
+    (0x10, "YMM"),
+    (0x20, "ZMM"),
+    (0x40, "FXSR"),
+    (0x80, "XSAVE"),
+    (0x100, "XSAVEOPT"),
+    (0x200, "XSAVEC"),
+    (0x400, "TMM"),
+    (0x800, "MASK"),
+];
+
+const AARCH64_FEATURE_1_NAMES: [(u32, &'static str); 3] = [
+    (0x1, "BTI"),
+    (0x2, "PAC"),
+    (0x4, "GCS"),
+];
+
+// One entry of the property array carried in an NT_GNU_PROPERTY_TYPE_0 note,
+// either in .note.gnu.property or in the PT_GNU_PROPERTY segment.
+// The data is already converted to host byte order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GnuProperty {
+    StackSize(u64),
+    NoCopyOnProtected,
+    X86IsaNeeded(u32),
+    X86IsaUsed(u32),
+    X86Feature(u32),
+    X86FeatureNeeded(u32),
+    X86FeatureUsed(u32),
+    AArch64Feature(u32),
+    Unknown(u32),
+}
+
+// Parses the descriptor of an NT_GNU_PROPERTY_TYPE_0 note. Each property is
+// padded to 8 bytes in 64-bit files and to 4 bytes in 32-bit ones.
+#[allow(dead_code)]
+pub fn parse_gnu_properties(
+    desc: &[u8], e: &Endianness, is_64bit: bool) -> Vec<GnuProperty>
+{
+    let align = if is_64bit { 8 } else { 4 };
+    let mut properties = Vec::new();
+    let mut offset = 0;
+
+    while offset < desc.len() {
+        let (pr_type, pr_datasz) = match (
+            get_u32(desc, offset, e), get_u32(desc, offset + 4, e))
+        {
+            (Some(pr_type), Some(pr_datasz)) => (pr_type, pr_datasz as usize),
+            _ => break,
+        };
+        let data_start = offset + 8;
+        if pr_datasz > desc.len() - data_start {
+            break;
+        }
+        let data = &desc[data_start..data_start + pr_datasz];
+        let word = get_u32(data, 0, e);
+
+        let property = match (pr_type, word) {
+            (GNU_PROPERTY_STACK_SIZE, _) => {
+                let size = if pr_datasz == 8 {
+                    get_u64(data, 0, e)
+                } else {
+                    word.map(|w| w as u64)
+                };
+                match size {
+                    Some(size) => GnuProperty::StackSize(size),
+                    None => GnuProperty::Unknown(pr_type),
+                }
+            }
+            (GNU_PROPERTY_NO_COPY_ON_PROTECTED, _) =>
+                GnuProperty::NoCopyOnProtected,
+            (GNU_PROPERTY_X86_ISA_1_NEEDED, Some(w)) =>
+                GnuProperty::X86IsaNeeded(w),
+            (GNU_PROPERTY_X86_ISA_1_USED, Some(w)) =>
+                GnuProperty::X86IsaUsed(w),
+            (GNU_PROPERTY_X86_FEATURE_1_AND, Some(w)) =>
+                GnuProperty::X86Feature(w),
+            (GNU_PROPERTY_X86_FEATURE_2_NEEDED, Some(w)) =>
+                GnuProperty::X86FeatureNeeded(w),
+            (GNU_PROPERTY_X86_FEATURE_2_USED, Some(w)) =>
+                GnuProperty::X86FeatureUsed(w),
+            (GNU_PROPERTY_AARCH64_FEATURE_1_AND, Some(w)) =>
+                GnuProperty::AArch64Feature(w),
+            _ => GnuProperty::Unknown(pr_type),
+        };
+        properties.push(property);
+
+        offset = (data_start + pr_datasz + align - 1) / align * align;
+    }
+    properties
+}
+
+fn write_bit_names(
+    fmt: &mut Formatter, bits: u32, names: &[(u32, &'static str)])
+    -> ::std::fmt::Result
+{
+    if bits == 0 {
+        return write!(fmt, "<None>");
+    }
+    let mut rest = bits;
+    let mut first = true;
+    for &(bit, name) in names {
+        if (bits & bit) != 0 {
+            if !first {
+                try!(write!(fmt, ", "));
+            }
+            try!(write!(fmt, "{}", name));
+            rest &= !bit;
+            first = false;
+        }
+    }
+    if rest != 0 {
+        if !first {
+            try!(write!(fmt, ", "));
+        }
+        try!(write!(fmt, "<unknown: {:x}>", rest));
+    }
+    Ok(())
+}
+
+impl Display for GnuProperty {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        match *self {
+            GnuProperty::StackSize(size) => write!(fmt, "stack size: {:#x}", size),
+            GnuProperty::NoCopyOnProtected => write!(fmt, "no copy on protected"),
+            GnuProperty::X86IsaNeeded(bits) => {
+                try!(write!(fmt, "x86 ISA needed: "));
+                write_bit_names(fmt, bits, &X86_ISA_1_NAMES)
+            }
+            GnuProperty::X86IsaUsed(bits) => {
+                try!(write!(fmt, "x86 ISA used: "));
+                write_bit_names(fmt, bits, &X86_ISA_1_NAMES)
+            }
+            GnuProperty::X86Feature(bits) => {
+                try!(write!(fmt, "x86 feature: "));
+                write_bit_names(fmt, bits, &X86_FEATURE_1_NAMES)
+            }
+            GnuProperty::X86FeatureNeeded(bits) => {
+                try!(write!(fmt, "x86 feature needed: "));
+                write_bit_names(fmt, bits, &X86_FEATURE_2_NAMES)
+            }
+            GnuProperty::X86FeatureUsed(bits) => {
+                try!(write!(fmt, "x86 feature used: "));
+                write_bit_names(fmt, bits, &X86_FEATURE_2_NAMES)
+            }
+            GnuProperty::AArch64Feature(bits) => {
+                try!(write!(fmt, "AArch64 feature: "));
+                write_bit_names(fmt, bits, &AARCH64_FEATURE_1_NAMES)
+            }
+            GnuProperty::Unknown(pr_type) =>
+                write!(fmt, "<unknown type {:#x}>", pr_type),
+        }
+    }
+}
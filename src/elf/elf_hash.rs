@@ -0,0 +1,128 @@
+use ::std::io::{self, Write};
+use ::to_host::Endianness;
+use super::helpers::get_u32;
+
+// Like try!, but for the Option returned by get_u32 on a truncated table.
+macro_rules! try_opt {
+    ($e:expr) => {
+        match $e {
+            Some(v) => v,
+            None => return None,
+        }
+    };
+}
+
+// Bucket chain lengths of a SysV .hash table: nbucket, nchain, the buckets,
+// then one chain link per symbol. A chain ends at index 0 (STN_UNDEF).
+// Returns None if the table is truncated.
+#[allow(dead_code)]
+pub fn get_sysv_bucket_lengths(data: &[u8], e: &Endianness) -> Option<Vec<u64>> {
+    let nbucket = try_opt!(get_u32(data, 0, e)) as usize;
+    let nchain = try_opt!(get_u32(data, 4, e)) as usize;
+    let buckets = 8;
+    let chains = buckets + nbucket.saturating_mul(4);
+    if nbucket > data.len() || nchain > data.len()
+    || chains + nchain * 4 > data.len() {
+        return None;
+    }
+
+    let mut lengths = Vec::with_capacity(nbucket);
+    for b in 0..nbucket {
+        let mut length = 0;
+        let mut i = try_opt!(get_u32(data, buckets + b * 4, e)) as usize;
+        // A corrupt table may contain a cycle, and no chain can be longer
+        // than the number of symbols.
+        while i != 0 && i < nchain && length < nchain as u64 {
+            length += 1;
+            i = try_opt!(get_u32(data, chains + i * 4, e)) as usize;
+        }
+        lengths.push(length);
+    }
+    Some(lengths)
+}
+
+// Bucket chain lengths of a .gnu.hash table: nbuckets, symoffset,
+// bloom_size, bloom_shift, the bloom filter of `bloom_word_size`-byte words,
+// the buckets, then one hash value per symbol starting at symoffset. The
+// lowest bit of a hash value marks the end of a chain.
+// Returns None if the table is truncated.
+#[allow(dead_code)]
+pub fn get_gnu_bucket_lengths(
+    data: &[u8], e: &Endianness, bloom_word_size: usize)
+    -> Option<Vec<u64>>
+{
+    let nbuckets = try_opt!(get_u32(data, 0, e)) as usize;
+    let symoffset = try_opt!(get_u32(data, 4, e)) as usize;
+    let bloom_size = try_opt!(get_u32(data, 8, e)) as usize;
+    if nbuckets > data.len() || bloom_size > data.len() {
+        return None;
+    }
+    let buckets = 16 + bloom_size * bloom_word_size;
+    let chains = buckets + nbuckets * 4;
+    if chains > data.len() {
+        return None;
+    }
+
+    let mut lengths = Vec::with_capacity(nbuckets);
+    for b in 0..nbuckets {
+        let mut i = try_opt!(get_u32(data, buckets + b * 4, e)) as usize;
+        let mut length = 0;
+        if i != 0 {
+            if i < symoffset {
+                return None;
+            }
+            loop {
+                let hash = try_opt!(get_u32(data, chains + (i - symoffset) * 4, e));
+                length += 1;
+                if (hash & 1) != 0 {
+                    break;
+                }
+                i += 1;
+            }
+        }
+        lengths.push(length);
+    }
+    Some(lengths)
+}
+
+// Prints how many buckets have chains of each length, and which share of all
+// hashed symbols is reachable through chains up to that length.
+#[allow(dead_code)]
+pub fn print_bucket_histogram<W: Write>(
+    title: &str, lengths: &[u64], out: &mut W) -> io::Result<()>
+{
+    let max_length = lengths.iter().cloned().max().unwrap_or(0) as usize;
+    let mut counts = vec![0u64; max_length + 1];
+    for &length in lengths {
+        counts[length as usize] += 1;
+    }
+    let nsyms: u64 = lengths.iter().sum();
+
+    try!(heading!(out, ""));
+    try!(heading!(
+        out,
+        "Histogram for {}bucket list length (total of {} {}):",
+        title, lengths.len(),
+        if lengths.len() == 1 { "bucket" } else { "buckets" }));
+    try!(heading!(out, " Length  Number     % of total  Coverage"));
+    let mut covered = 0;
+    for (length, &count) in counts.iter().enumerate() {
+        try!(write!(
+            out,
+            "{:7}  {:<10} ({:5.1}%)",
+            length, count, percent(count, lengths.len() as u64)));
+        if length != 0 {
+            covered += length as u64 * count;
+            try!(write!(out, "    {:5.1}%", percent(covered, nsyms)));
+        }
+        try!(writeln!(out, ""));
+    }
+    Ok(())
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (part * 100) as f64 / total as f64
+}
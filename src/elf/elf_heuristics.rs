@@ -0,0 +1,105 @@
+#[macro_export]
+macro_rules! elf_heuristics {
+    ($addr:ty) => {
+        use ::std::io::{self, Read, Seek, Write};
+        use ::to_host::Endianness;
+        use ::to_host::ToHostCopyStruct;
+        use super::super::elf_phdr_type::ElfPhdrType;
+        use super::super::elf_shdr_flags::{ElfShdrFlags, SHF_ALLOC};
+        use super::super::helpers::{read_bytes, get_entropy};
+        use super::elf_ehdr::Elf_Ehdr;
+        use super::elf_phdr::{Elf_Phdr, get_rwx_segments};
+        use super::elf_shdr::Elf_Shdr;
+        use super::primitive::*;
+
+        // Segments smaller than this don't have enough bytes for their
+        // entropy to mean anything.
+        const MIN_ENTROPY_SIZE: u64 = 1024;
+        const HIGH_ENTROPY: f64 = 7.5;
+
+        // Collects signs that the file has been packed or obfuscated. None of
+        // them is proof: each one also occurs in legitimate binaries.
+        #[allow(dead_code)]
+        pub fn get_packing_signs<R: Read + Seek>(
+            ehdr: &Elf_Ehdr, phdrs: &[Elf_Phdr], shdrs: &[Elf_Shdr],
+            reader: &mut R)
+            -> Vec<String>
+        {
+            let mut signs = Vec::new();
+            let loads: Vec<&Elf_Phdr> = phdrs.iter()
+                .filter(|p| p.get_type() == ElfPhdrType::PT_LOAD.get_value())
+                .collect();
+
+            if shdrs.len() < phdrs.len() {
+                signs.push(format!(
+                    "only {} section headers for {} program headers",
+                    shdrs.len(), phdrs.len()));
+            }
+
+            let entry = ehdr.get_entry();
+            let in_section = shdrs.iter().any(|s| {
+                ElfShdrFlags::new(s.get_flags() as u64).contains(SHF_ALLOC)
+                && entry >= s.get_addr()
+                && entry - s.get_addr() < s.get_size() as $addr
+            });
+            if entry != 0 && !shdrs.is_empty() && !in_section {
+                signs.push(format!(
+                    "entry point {:#x} is not inside any section", entry));
+            }
+
+            let rwx = get_rwx_segments(phdrs);
+            for (i, phdr) in phdrs.iter().enumerate() {
+                if phdr.get_type() != ElfPhdrType::PT_LOAD.get_value() {
+                    continue;
+                }
+                if phdr.get_filesz() as u64 >= MIN_ENTROPY_SIZE {
+                    let data = read_bytes(
+                        reader, phdr.get_offset() as u64, phdr.get_filesz() as u64)
+                        .unwrap_or(Vec::new());
+                    let entropy = get_entropy(&data);
+                    if entropy >= HIGH_ENTROPY {
+                        signs.push(format!(
+                            "LOAD segment {} has high entropy ({:.2} bits/byte)",
+                            i, entropy));
+                    }
+                }
+                if rwx.contains(&i) {
+                    if loads.len() == 1 {
+                        signs.push(format!(
+                            "the only LOAD segment ({}) is readable, writable and executable", i));
+                    } else {
+                        signs.push(format!(
+                            "LOAD segment {} is readable, writable and executable", i));
+                    }
+                }
+            }
+            signs
+        }
+
+        // The ELF header is expected in host byte order, the program and
+        // section headers as read from the file.
+        #[allow(dead_code)]
+        pub fn print_packing_report<R: Read + Seek, W: Write>(
+            ehdr: &Elf_Ehdr, phdrs: &[Elf_Phdr], shdrs: &[Elf_Shdr],
+            e: &Endianness, reader: &mut R, out: &mut W)
+            -> io::Result<()>
+        {
+            let phdrs: Vec<Elf_Phdr> =
+                phdrs.iter().map(|p| p.to_host_copy(e)).collect();
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+
+            let signs = get_packing_signs(ehdr, &phdrs, &shdrs, reader);
+
+            try!(writeln!(out, ""));
+            try!(writeln!(out, "Packing heuristics (not authoritative):"));
+            if signs.is_empty() {
+                try!(writeln!(out, "  No signs of packing found."));
+            }
+            for sign in &signs {
+                try!(writeln!(out, "  suspicious: {}", sign));
+            }
+            Ok(())
+        }
+    }
+}
@@ -1,13 +1,75 @@
 use ::std::fmt::{Display, Formatter};
+use ::std::io::{self, Read, Seek, SeekFrom, Write};
+use super::elf_ident_named::ElfIdentNamed;
 
 const EI_NIDENT : usize = 16;
 
+// Names and sizes of the fields of e_ident, in order.
+const EI_FIELDS: [(&'static str, usize); 10] = [
+    ("EI_MAG0", 1),
+    ("EI_MAG1", 1),
+    ("EI_MAG2", 1),
+    ("EI_MAG3", 1),
+    ("EI_CLASS", 1),
+    ("EI_DATA", 1),
+    ("EI_VERSION", 1),
+    ("EI_OSABI", 1),
+    ("EI_ABIVERSION", 1),
+    ("EI_PAD", 7),
+];
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct ElfIdent {
     data: [u8; EI_NIDENT],
 }
 
+#[allow(dead_code)]
+impl ElfIdent {
+    // The bytes of each field, labeled, for the verbose rendering. Display
+    // keeps to the compact form.
+    pub fn get_labeled_bytes(&self) -> Vec<(&'static str, &[u8])> {
+        let mut r = Vec::with_capacity(EI_FIELDS.len());
+        let mut offset = 0;
+        for &(name, size) in EI_FIELDS.iter() {
+            r.push((name, &self.data[offset..offset + size]));
+            offset += size;
+        }
+        r
+    }
+
+    // Reads the identification alone, which is there even in a file cut
+    // short before the end of its header. None if the file is shorter.
+    pub fn read<R: Read + Seek>(reader: &mut R) -> io::Result<Option<ElfIdent>> {
+        let mut data = [0; EI_NIDENT];
+        try!(reader.seek(SeekFrom::Start(0)));
+        match reader.read_exact(&mut data) {
+            Ok(()) => Ok(Some(ElfIdent { data: data })),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get_named(&self) -> ElfIdentNamed {
+        unsafe {
+            ::std::mem::transmute(self.data)
+        }
+    }
+
+    // The start of the ELF header listing: the magic and the decoded fields.
+    pub fn print<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(
+            out,
+            concat!(
+                "ELF Header:\n",
+                "  Magic:   {}\n",
+                "{}",
+            ),
+            self,
+            self.get_named())
+    }
+}
+
 impl Display for ElfIdent {
     fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
         for b in self.data.iter() {
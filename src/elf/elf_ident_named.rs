@@ -4,6 +4,8 @@ use ::std::io::{Read, Seek};
 use super::prelude::{ElfEiClass, ElfEiData, ElfEiVersion, ElfEiOsAbi, ElfEiAbiVersion};
 
 pub const EI_MAGIC_SIZE: usize = 4;
+pub const EI_CLASS: usize = 4;
+pub const EI_DATA: usize = 5;
 const EI_MAGIC_CLASS_SIZE: usize = EI_MAGIC_SIZE + 1;
 
 type ElfEiMagic = [u8; EI_MAGIC_SIZE]; 
@@ -22,8 +24,20 @@ pub struct ElfIdentNamed {
 
 impl Display for ElfIdentNamed {
     fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
-        write!(
-            fmt,
+        write!(fmt, "{}", self.describe(0))
+    }
+}
+
+impl ElfIdentNamed {
+    // The identification lines of the file header, with the OS/ABI named for
+    // the machine `e_machine`.
+    pub fn describe(&self, e_machine: u16) -> String {
+        // The number comes first either way, so the line still reads as one
+        let abi_version = match self.ei_osabiversion.get_note(self.ei_osabi) {
+            Some(note) => format!("{} ({})", self.ei_osabiversion, note),
+            None => format!("{}", self.ei_osabiversion),
+        };
+        format!(
             concat!(
                 "  Class:                             {}\n",
                 "  Data:                              {}\n",
@@ -34,8 +48,8 @@ impl Display for ElfIdentNamed {
             self.ei_class,
             self.ei_data,
             self.ei_version,
-            self.ei_osabi,
-            self.ei_osabiversion,
+            self.ei_osabi.get_description(e_machine),
+            abi_version,
         )
     }
 }
@@ -47,6 +61,21 @@ impl ElfIdentNamed {
     pub fn get_class(&self) -> ElfEiClass {
         self.ei_class
     }
+    pub fn get_data(&self) -> ElfEiData {
+        self.ei_data
+    }
+    #[allow(dead_code)]
+    pub fn get_version(&self) -> ElfEiVersion {
+        self.ei_version
+    }
+    #[allow(dead_code)]
+    pub fn get_os_abi(&self) -> ElfEiOsAbi {
+        self.ei_osabi
+    }
+    #[allow(dead_code)]
+    pub fn get_abi_version(&self) -> ElfEiAbiVersion {
+        self.ei_osabiversion
+    }
     #[allow(dead_code)]
     pub fn read_class<R: Read + Seek>(mut reader: R) -> ElfEiClass {
         use std::io::SeekFrom;
@@ -57,10 +86,7 @@ impl ElfIdentNamed {
         reader.seek(SeekFrom::Start(offset)).unwrap();
         reader.read_exact(&mut b).unwrap();
 
-        let class: ElfEiClass = unsafe {
-            ::std::mem::transmute(b[EI_MAGIC_SIZE])
-        };
-        class
+        ElfEiClass::new(b[EI_MAGIC_SIZE])
     }
 }
 
@@ -0,0 +1,56 @@
+use ::std::io::{self, Read, Seek, SeekFrom};
+
+use super::elf_ei_class::ElfEiClass;
+use super::elf_ei_data::ElfEiData;
+use super::elf_ident_named::{EI_CLASS, EI_DATA};
+
+// Wraps a reader and replaces bytes of e_ident as they are read, so that a
+// file with a clobbered identification byte can be parsed as if it were
+// right. Everything else is passed through unchanged.
+pub struct IdentOverride<R> {
+    inner: R,
+    patches: Vec<(u64, u8)>,
+}
+
+#[allow(dead_code)]
+impl<R: Read + Seek> IdentOverride<R> {
+    pub fn new(inner: R) -> IdentOverride<R> {
+        IdentOverride { inner: inner, patches: Vec::new() }
+    }
+    pub fn set_class(&mut self, class: ElfEiClass) {
+        self.patch(EI_CLASS as u64, class.get_value());
+    }
+    pub fn set_data(&mut self, data: ElfEiData) {
+        self.patch(EI_DATA as u64, data.get_value());
+    }
+    pub fn is_overridden(&self) -> bool {
+        !self.patches.is_empty()
+    }
+
+    fn patch(&mut self, offset: u64, value: u8) {
+        self.patches.retain(|&(o, _)| o != offset);
+        self.patches.push((offset, value));
+    }
+}
+
+impl<R: Read + Seek> Read for IdentOverride<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.patches.is_empty() {
+            return self.inner.read(buf);
+        }
+        let start = try!(self.inner.seek(SeekFrom::Current(0)));
+        let n = try!(self.inner.read(buf));
+        for &(offset, value) in &self.patches {
+            if offset >= start && offset < start + n as u64 {
+                buf[(offset - start) as usize] = value;
+            }
+        }
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for IdentOverride<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
@@ -0,0 +1,135 @@
+use ::std::io::{self, Write};
+use super::elf_report::{Reporter, JsonReporter};
+use super::elf_target::TargetInfo;
+
+// The JSON report is read by scripts, so its keys and the form of its values
+// are kept stable. NOTES.md lists them; a change to either is a change to
+// the interface. The CSV one has the same columns.
+
+#[derive(Debug, Clone)]
+pub struct JsonSection {
+    pub index: usize,
+    pub name: String,
+    pub section_type: String,
+    pub addr: u64,
+    pub offset: u64,
+    pub size: u64,
+    pub flags: String,
+    pub link: u64,
+    pub info: u64,
+    pub addralign: u64,
+    pub entsize: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct JsonSegment {
+    pub index: usize,
+    pub segment_type: String,
+    pub flags: String,
+    pub offset: u64,
+    pub vaddr: u64,
+    pub paddr: u64,
+    pub filesz: u64,
+    pub memsz: u64,
+    pub align: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct JsonSymbol {
+    // The name of the symbol table
+    pub table: String,
+    pub index: usize,
+    pub name: String,
+    pub value: u64,
+    pub size: u64,
+    pub symbol_type: String,
+    pub bind: String,
+    pub visibility: String,
+    // "UND", "ABS", "COM" or the section index, as readelf -s prints it
+    pub section: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct JsonReport {
+    target: TargetInfo,
+    // e_type
+    file_type: u16,
+    entry: u64,
+    sections: Vec<JsonSection>,
+    // With -S, the section objects carry every field of the header
+    section_details: bool,
+    // With -l
+    segments: Option<Vec<JsonSegment>>,
+    // With -s
+    symbols: Option<Vec<JsonSymbol>>,
+}
+
+#[allow(dead_code)]
+impl JsonReport {
+    pub fn new(
+        target: TargetInfo, file_type: u16, entry: u64,
+        sections: Vec<JsonSection>)
+        -> JsonReport
+    {
+        JsonReport {
+            target: target,
+            file_type: file_type,
+            entry: entry,
+            sections: sections,
+            section_details: false,
+            segments: None,
+            symbols: None,
+        }
+    }
+
+    pub fn set_section_details(&mut self, section_details: bool) {
+        self.section_details = section_details;
+    }
+
+    pub fn set_segments(&mut self, segments: Vec<JsonSegment>) {
+        self.segments = Some(segments);
+    }
+
+    pub fn set_symbols(&mut self, symbols: Vec<JsonSymbol>) {
+        self.symbols = Some(symbols);
+    }
+
+    // Hands the report to `reporter`, table by table.
+    pub fn report<R: Reporter, W: Write>(&self, reporter: &mut R, out: &mut W)
+        -> io::Result<()>
+    {
+        try!(reporter.header(out, &self.target, self.file_type, self.entry));
+        if let Some(ref segments) = self.segments {
+            try!(reporter.segment_table(out, segments));
+        }
+        if let Some(ref symbols) = self.symbols {
+            try!(reporter.symbol_table(out, symbols));
+        }
+        try!(reporter.section_table(out, &self.sections, self.section_details));
+        reporter.finish(out)
+    }
+
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        self.report(&mut JsonReporter, out)
+    }
+}
+
+// Quotes `s` as a JSON string.
+#[allow(dead_code)]
+pub fn get_json_string(s: &str) -> String {
+    let mut r = String::with_capacity(s.len() + 2);
+    r.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => r.push_str("\\\""),
+            '\\' => r.push_str("\\\\"),
+            '\n' => r.push_str("\\n"),
+            '\r' => r.push_str("\\r"),
+            '\t' => r.push_str("\\t"),
+            c if (c as u32) < 0x20 => r.push_str(&format!("\\u{:04x}", c as u32)),
+            c => r.push(c),
+        }
+    }
+    r.push('"');
+    r
+}
@@ -0,0 +1,98 @@
+use ::std::io::{self, Write};
+
+// Where one thing lies in the file, for --relative-offsets: a section, a
+// segment or one of the header tables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutEntry {
+    label: String,
+    offset: u64,
+    size: u64,
+    // Whether gaps are measured to and from it. Things nested in others,
+    // such as PT_PHDR in the first PT_LOAD, would only show up as overlaps.
+    tiles: bool,
+    // The segment it lies in, and its offset from the start of it
+    within: Option<(usize, u64)>,
+}
+
+#[allow(dead_code)]
+impl LayoutEntry {
+    pub fn new(label: String, offset: u64, size: u64, tiles: bool, within: Option<(usize, u64)>)
+        -> LayoutEntry
+    {
+        LayoutEntry {
+            label: label,
+            offset: offset,
+            size: size,
+            tiles: tiles,
+            within: within,
+        }
+    }
+    pub fn get_label(&self) -> &str {
+        &self.label
+    }
+    pub fn get_offset(&self) -> u64 {
+        self.offset
+    }
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+    pub fn get_within(&self) -> Option<(usize, u64)> {
+        self.within
+    }
+}
+
+// The distance from the end of the previous tiling entry to the start of
+// this one: padding if positive, an overlap if negative. None for the first.
+#[allow(dead_code)]
+pub fn get_gaps(entries: &[LayoutEntry]) -> Vec<Option<i128>> {
+    let mut previous_end = None;
+    entries.iter()
+        .map(|entry| {
+            if !entry.tiles {
+                return None;
+            }
+            let gap = previous_end.map(|end: u64| entry.offset as i128 - end as i128);
+            previous_end = Some(entry.offset.saturating_add(entry.size));
+            gap
+        })
+        .collect()
+}
+
+fn format_gap(gap: Option<i128>) -> String {
+    match gap {
+        None => String::new(),
+        Some(0) => "0".to_string(),
+        Some(gap) if gap > 0 => format!("+{:#x}", gap),
+        Some(gap) => format!("-{:#x} (overlap)", -gap),
+    }
+}
+
+// Lists the entries in file order, each with the padding before it and its
+// place in the segment it lies in.
+#[allow(dead_code)]
+pub fn print_layout<W: Write>(title: &str, entries: &[LayoutEntry], out: &mut W)
+    -> io::Result<()>
+{
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let mut entries = entries.to_vec();
+    entries.sort_by_key(|entry| entry.offset);
+    let gaps = get_gaps(&entries);
+
+    try!(heading!(out, ""));
+    try!(heading!(out, "{} in file order:", title));
+    try!(heading!(out, "  {:<28} {:<10} {:<10} {:<18} {}",
+                  "Name", "Offset", "Size", "Gap", "In segment"));
+    for (entry, &gap) in entries.iter().zip(gaps.iter()) {
+        let within = match entry.within {
+            Some((segment, offset)) => format!("[{:02}] +{:#x}", segment, offset),
+            None => String::new(),
+        };
+        let line = format!(
+            "  {:<28} {:#010x} {:#010x} {:<18} {}",
+            entry.label, entry.offset, entry.size, format_gap(gap), within);
+        try!(writeln!(out, "{}", line.trim_end()));
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use ::std::io::{self, Write};
+
+// What .dynamic says about the libraries an object links against: its own
+// DT_SONAME, a DT_NEEDED entry per direct dependency, in order, and the
+// DT_RPATH and DT_RUNPATH search paths.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dependencies {
+    pub soname: Option<String>,
+    pub needed: Vec<String>,
+    pub rpath: Option<String>,
+    pub runpath: Option<String>,
+}
+
+#[allow(dead_code)]
+impl Dependencies {
+    pub fn get_soname(&self) -> Option<&str> {
+        self.soname.as_ref().map(|s| s.as_str())
+    }
+    // The directories of DT_RPATH. The dynamic loader ignores them when
+    // there is a DT_RUNPATH.
+    pub fn get_rpath(&self) -> Vec<&str> {
+        self.rpath.as_ref().map(|s| split_search_path(s)).unwrap_or(Vec::new())
+    }
+    pub fn get_runpath(&self) -> Vec<&str> {
+        self.runpath.as_ref().map(|s| split_search_path(s)).unwrap_or(Vec::new())
+    }
+}
+
+// The directories of a DT_RPATH or DT_RUNPATH string, as written. Dynamic
+// string tokens such as $ORIGIN are left for the loader to expand, and an
+// empty component, which it takes as the current directory, is kept.
+#[allow(dead_code)]
+pub fn split_search_path(path: &str) -> Vec<&str> {
+    path.split(':').collect()
+}
+
+#[allow(dead_code)]
+pub fn print_needed<W: Write>(dependencies: &Dependencies, out: &mut W) -> io::Result<()> {
+    for name in &dependencies.needed {
+        try!(writeln!(out, "{}", name));
+    }
+    Ok(())
+}
+
+// Quotes `s` as a Graphviz ID.
+fn get_dot_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// A digraph with an edge from the object to each library it needs. The
+// root is labeled with its soname, or `file_name` if it has none.
+// Dependencies of dependencies aren't followed, as they aren't loaded.
+#[allow(dead_code)]
+pub fn print_needed_dot<W: Write>(
+    dependencies: &Dependencies, file_name: &str, out: &mut W)
+    -> io::Result<()>
+{
+    let root = get_dot_string(match dependencies.soname {
+        Some(ref soname) => soname,
+        None => file_name,
+    });
+    try!(writeln!(out, "digraph dependencies {{"));
+    try!(writeln!(out, "    {} [shape=box];", root));
+    for name in &dependencies.needed {
+        try!(writeln!(out, "    {} -> {};", root, get_dot_string(name)));
+    }
+    writeln!(out, "}}")
+}
+
+// Whether `file_name` fits the soname. A library is usually installed as
+// its full version (libfoo.so.1.2.3 for libfoo.so.1), with the soname and
+// the link-time name (libfoo.so) as symlinks to it, so either may be the
+// other followed by more version numbers. Any other name means the file was
+// renamed after linking, and programs linked against it will look for the
+// soname rather than for the file.
+#[allow(dead_code)]
+pub fn get_soname_problems(dependencies: &Dependencies, file_name: &str) -> Vec<String> {
+    let soname = match dependencies.soname {
+        Some(ref soname) => soname,
+        None => return Vec::new(),
+    };
+    // `long` is `short` followed by version numbers, as libfoo.so.1.2 is
+    // libfoo.so.1 and libfoo.so.1 is libfoo.so
+    let extends = |long: &str, short: &str| {
+        long.starts_with(short) && long[short.len()..].starts_with('.')
+            && long[short.len() + 1..].split('.')
+                .all(|n| !n.is_empty() && n.bytes().all(|c| c.is_ascii_digit()))
+    };
+    if file_name == soname || extends(file_name, soname) || extends(soname, file_name) {
+        return Vec::new();
+    }
+    vec![format!(
+        "the soname is '{}', but the file is named '{}'; the dynamic loader \
+         looks for the library by its soname",
+        soname, file_name)]
+}
@@ -0,0 +1,392 @@
+use ::std::io::{self, Write};
+use ::std::sync::{Arc, Mutex};
+use ::to_host::Endianness;
+use super::elf_gnu_property::parse_gnu_properties;
+use super::helpers::{get_u32, get_u64, print_hex_lines};
+
+pub const NT_GNU_ABI_TAG: u32 = 1;
+pub const NT_GNU_HWCAP: u32 = 2;
+pub const NT_GNU_BUILD_ID: u32 = 3;
+pub const NT_GNU_GOLD_VERSION: u32 = 4;
+pub const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+
+// The Go toolchain's note, owner "Go", in .note.go.buildid.
+pub const NT_GO_BUILD_ID: u32 = 4;
+
+// A SystemTap/USDT probe, owner "stapsdt", in .note.stapsdt.
+pub const NT_STAPSDT: u32 = 3;
+
+// The identification notes of the BSDs, in .note.tag (FreeBSD),
+// .note.netbsd.ident and .note.openbsd.ident. The descriptor is a word
+// holding the version of the OS the binary was built for.
+pub const NT_FREEBSD_ABI_TAG: u32 = 1;
+pub const NT_NETBSD_IDENT: u32 = 1;
+pub const NT_OPENBSD_IDENT: u32 = 1;
+
+const GNU_ABI_TAG_OS_NAMES: [&'static str; 4] =
+    ["Linux", "Hurd", "Solaris", "FreeBSD"];
+
+// A single entry of a note section or segment. The name has its terminating
+// NUL removed, the descriptor is kept as raw bytes in file byte order.
+#[derive(Debug, Clone)]
+pub struct ElfNote {
+    name: Vec<u8>,
+    n_type: u32,
+    desc: Vec<u8>,
+}
+
+#[allow(dead_code)]
+impl ElfNote {
+    pub fn get_name(&self) -> &[u8] {
+        &self.name
+    }
+    pub fn get_type(&self) -> u32 {
+        self.n_type
+    }
+    pub fn get_desc(&self) -> &[u8] {
+        &self.desc
+    }
+    pub fn is_gnu(&self) -> bool {
+        self.name == b"GNU"
+    }
+    pub fn is_go(&self) -> bool {
+        self.name == b"Go"
+    }
+    pub fn is_stapsdt(&self) -> bool {
+        self.name == b"stapsdt"
+    }
+    // The OS a BSD identification note is for
+    pub fn get_bsd_os(&self) -> Option<&'static str> {
+        match (&self.name[..], self.n_type) {
+            (b"FreeBSD", NT_FREEBSD_ABI_TAG) => Some("FreeBSD"),
+            (b"NetBSD", NT_NETBSD_IDENT) => Some("NetBSD"),
+            (b"OpenBSD", NT_OPENBSD_IDENT) => Some("OpenBSD"),
+            _ => None,
+        }
+    }
+    // The OS version of a BSD identification note, as the OS writes it.
+    // __FreeBSD_version is MMmmppp, with ppp counting changes on a
+    // branch, and __NetBSD_Version__ is MMmmrrpp00, where rr is only used
+    // by releases older than 2.0. OpenBSD leaves the word at 0.
+    pub fn get_bsd_version(&self, e: &Endianness) -> Option<String> {
+        let os = match self.get_bsd_os() {
+            Some(os) => os,
+            None => return None,
+        };
+        let v = match get_u32(&self.desc, 0, e) {
+            Some(v) if self.desc.len() == 4 => v,
+            _ => return None,
+        };
+        let version = match os {
+            "FreeBSD" => format!("{}.{}", v / 100000, v / 1000 % 100),
+            "NetBSD" if v / 100 % 100 != 0 =>
+                format!("{}.{}.{}", v / 100000000, v / 1000000 % 100, v / 100 % 100),
+            "NetBSD" => format!("{}.{}", v / 100000000, v / 1000000 % 100),
+            _ if v == 0 => return Some("unspecified".to_string()),
+            _ => v.to_string(),
+        };
+        Some(format!("{} ({})", version, v))
+    }
+    // The probe a stapsdt note describes. Its descriptor is three addresses
+    // (the probe, .stapsdt.base as the linker saw it and the semaphore,
+    // 0 if there is none) and then the provider, name and argument strings.
+    pub fn get_stapsdt_probe(&self, e: &Endianness, is_64bit: bool) -> Option<StapsdtProbe> {
+        if !self.is_stapsdt() || self.n_type != NT_STAPSDT {
+            return None;
+        }
+        let size = if is_64bit { 8 } else { 4 };
+        let address = |i: usize| if is_64bit {
+            get_u64(&self.desc, i * size, e)
+        } else {
+            get_u32(&self.desc, i * size, e).map(|a| a as u64)
+        };
+        let (location, base, semaphore) = match (address(0), address(1), address(2)) {
+            (Some(location), Some(base), Some(semaphore)) => (location, base, semaphore),
+            _ => return None,
+        };
+        let mut strings = self.desc[3 * size..].split(|&b| b == 0)
+            .map(|s| String::from_utf8_lossy(s).into_owned());
+        match (strings.next(), strings.next(), strings.next()) {
+            (Some(provider), Some(name), Some(arguments)) => Some(StapsdtProbe {
+                provider: provider,
+                name: name,
+                arguments: arguments,
+                location: location,
+                base: base,
+                semaphore: semaphore,
+            }),
+            _ => None,
+        }
+    }
+    // The build ID of a Go binary, which unlike the GNU one is a string.
+    pub fn get_go_build_id(&self) -> Option<String> {
+        if !self.is_go() || self.n_type != NT_GO_BUILD_ID {
+            return None;
+        }
+        let end = self.desc.iter().position(|&b| b == 0).unwrap_or(self.desc.len());
+        Some(String::from_utf8_lossy(&self.desc[..end]).into_owned())
+    }
+    // The version of gold that linked the file, as in "gold 1.16". The
+    // string is NUL-terminated and padded, so it stops at the first NUL.
+    pub fn get_gold_version(&self) -> Option<String> {
+        if !self.is_gnu() || self.n_type != NT_GNU_GOLD_VERSION {
+            return None;
+        }
+        let end = self.desc.iter().position(|&b| b == 0).unwrap_or(self.desc.len());
+        Some(String::from_utf8_lossy(&self.desc[..end]).into_owned())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StapsdtProbe {
+    provider: String,
+    name: String,
+    arguments: String,
+    location: u64,
+    base: u64,
+    semaphore: u64,
+}
+
+#[allow(dead_code)]
+impl StapsdtProbe {
+    pub fn get_provider(&self) -> &str {
+        &self.provider
+    }
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    // As the compiler wrote them for the tracer, e.g. "-4@%edi"
+    pub fn get_arguments(&self) -> &str {
+        &self.arguments
+    }
+    pub fn get_location(&self) -> u64 {
+        self.location
+    }
+    pub fn get_base(&self) -> u64 {
+        self.base
+    }
+    // 0 if the probe has no semaphore
+    pub fn get_semaphore(&self) -> Option<u64> {
+        if self.semaphore == 0 { None } else { Some(self.semaphore) }
+    }
+}
+
+// A decoder for the descriptors of one owner and type of note, such as a
+// vendor's own, which print_notes would otherwise only dump in hex.
+struct NoteDecoder {
+    owner: String,
+    n_type: u32,
+    decode: Arc<dyn Fn(&[u8]) -> String + Send + Sync>,
+}
+
+static NOTE_DECODERS: Mutex<Vec<NoteDecoder>> = Mutex::new(Vec::new());
+
+// Registers `f` to decode the notes of `owner` with type `ntype`. It gets
+// the descriptor in file byte order and returns the text to print under
+// the note, which is indented line by line. Registered decoders are tried
+// before the built-in ones, so they can also replace those, and a later
+// registration for the same owner and type replaces an earlier one.
+#[allow(dead_code)]
+pub fn register_note_decoder<F>(owner: &str, ntype: u32, f: F)
+    where F: Fn(&[u8]) -> String + Send + Sync + 'static
+{
+    let mut decoders = NOTE_DECODERS.lock().unwrap_or_else(|e| e.into_inner());
+    decoders.retain(|d| d.owner != owner || d.n_type != ntype);
+    decoders.push(NoteDecoder {
+        owner: owner.to_string(),
+        n_type: ntype,
+        decode: Arc::new(f),
+    });
+}
+
+// What the decoder registered for the note makes of its descriptor, if
+// there is one.
+fn decode_registered(note: &ElfNote) -> Option<String> {
+    // The lock is released before the decoder runs, so that a decoder which
+    // panics or registers another one doesn't poison or deadlock the registry
+    let decode = {
+        let decoders = NOTE_DECODERS.lock().unwrap_or_else(|e| e.into_inner());
+        match decoders.iter()
+            .find(|d| d.owner.as_bytes() == note.get_name() && d.n_type == note.n_type) {
+            Some(d) => d.decode.clone(),
+            None => return None,
+        }
+    };
+    Some(decode(note.get_desc()))
+}
+
+fn describe_type(note: &ElfNote) -> String {
+    let s = match (note.is_gnu(), note.is_go(), note.n_type) {
+        (true, _, NT_GNU_ABI_TAG) => "NT_GNU_ABI_TAG (ABI version tag)",
+        (true, _, NT_GNU_HWCAP) => "NT_GNU_HWCAP (DSO-supplied software HWCAP info)",
+        (true, _, NT_GNU_BUILD_ID) => "NT_GNU_BUILD_ID (unique build ID bitstring)",
+        (true, _, NT_GNU_GOLD_VERSION) => "NT_GNU_GOLD_VERSION (gold version)",
+        (true, _, NT_GNU_PROPERTY_TYPE_0) => "NT_GNU_PROPERTY_TYPE_0",
+        (_, true, NT_GO_BUILD_ID) => "GO BUILDID",
+        (_, _, NT_STAPSDT) if note.is_stapsdt() => "NT_STAPSDT (SystemTap probe descriptors)",
+        _ if note.get_bsd_os() == Some("FreeBSD") => "NT_FREEBSD_ABI_TAG (OS version)",
+        _ if note.get_bsd_os() == Some("NetBSD") => "NT_NETBSD_IDENT (OS version)",
+        _ if note.get_bsd_os() == Some("OpenBSD") => "NT_OPENBSD_IDENT (OS version)",
+        (_, _, n_type) => return format!("Unknown note type: ({:#010x})", n_type),
+    };
+    s.to_string()
+}
+
+// Prints the notes of one section the way readelf -n does, decoding those
+// with a registered decoder and the GNU, Go, SystemTap and BSD notes it
+// knows. The descriptors of the rest are dumped in hex, and with `raw` so
+// are those of the decoded ones.
+#[allow(dead_code)]
+pub fn print_notes<W: Write>(
+    section_name: &str, notes: &[ElfNote], e: &Endianness, is_64bit: bool,
+    raw: bool, out: &mut W)
+    -> io::Result<()>
+{
+    try!(heading!(out, ""));
+    try!(heading!(out, "Displaying notes found in: {}", section_name));
+    try!(heading!(out, "  Owner                Data size \tDescription"));
+    for note in notes {
+        try!(writeln!(
+            out,
+            "  {:<20} {:#010x}\t{}",
+            String::from_utf8_lossy(note.get_name()), note.get_desc().len(),
+            describe_type(note)));
+
+        let desc = note.get_desc();
+        let registered = decode_registered(note);
+        if let Some(ref text) = registered {
+            for line in text.lines() {
+                try!(writeln!(out, "    {}", line));
+            }
+        }
+        let decoded = registered.is_some() || match (note.is_gnu(), note.is_go(), note.n_type) {
+            (true, _, NT_GNU_ABI_TAG) => {
+                let words: Vec<Option<u32>> =
+                    (0..4).map(|i| get_u32(desc, i * 4, e)).collect();
+                if let (Some(os), Some(major), Some(minor), Some(sub)) =
+                    (words[0], words[1], words[2], words[3])
+                {
+                    let os = match GNU_ABI_TAG_OS_NAMES.get(os as usize) {
+                        Some(name) => name.to_string(),
+                        None => format!("Unknown ({})", os),
+                    };
+                    try!(writeln!(
+                        out, "    OS: {}, ABI: {}.{}.{}", os, major, minor, sub));
+                }
+                true
+            }
+            (true, _, NT_GNU_BUILD_ID) => {
+                try!(write!(out, "    Build ID: "));
+                for b in desc {
+                    try!(write!(out, "{:02x}", b));
+                }
+                try!(writeln!(out, ""));
+                true
+            }
+            (true, _, NT_GNU_GOLD_VERSION) => {
+                if let Some(version) = note.get_gold_version() {
+                    try!(writeln!(out, "    Version: {}", version));
+                }
+                true
+            }
+            (true, _, NT_GNU_PROPERTY_TYPE_0) => {
+                let properties = parse_gnu_properties(desc, e, is_64bit);
+                try!(write!(out, "      Properties: "));
+                for (i, property) in properties.iter().enumerate() {
+                    if i != 0 {
+                        try!(write!(out, "\n\t"));
+                    }
+                    try!(write!(out, "{}", property));
+                }
+                try!(writeln!(out, ""));
+                true
+            }
+            (_, true, NT_GO_BUILD_ID) => {
+                if let Some(build_id) = note.get_go_build_id() {
+                    try!(writeln!(out, "    Build ID: {}", build_id));
+                }
+                true
+            }
+            (true, _, NT_GNU_HWCAP) => true,
+            (_, _, NT_STAPSDT) if note.is_stapsdt() => {
+                match note.get_stapsdt_probe(e, is_64bit) {
+                    Some(probe) => {
+                        let width = if is_64bit { 16 } else { 8 };
+                        try!(writeln!(out, "    Provider: {}", probe.provider));
+                        try!(writeln!(out, "    Name: {}", probe.name));
+                        try!(writeln!(
+                            out,
+                            "    Location: {:#0w$x}, Base: {:#0w$x}, Semaphore: {:#0w$x}",
+                            probe.location, probe.base, probe.semaphore, w = width + 2));
+                        try!(writeln!(out, "    Arguments: {}", probe.arguments));
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ if note.get_bsd_os().is_some() => {
+                match note.get_bsd_version(e) {
+                    Some(version) => {
+                        try!(writeln!(
+                            out, "    OS: {}, version: {}",
+                            note.get_bsd_os().unwrap(), version));
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        };
+        if !desc.is_empty() && (raw || !decoded) {
+            try!(writeln!(out, "    Descriptor:"));
+            try!(print_hex_lines(desc, 0, out));
+        }
+    }
+    Ok(())
+}
+
+fn align_up(value: usize, align: usize) -> usize {
+    (value + align - 1) / align * align
+}
+
+// Splits the contents of a note section or segment into entries. Name and
+// descriptor are padded to `align`, which is 4 for regular notes and 8 for
+// .note.gnu.property on 64-bit. Parsing stops at the first truncated entry.
+#[allow(dead_code)]
+pub fn parse_notes(data: &[u8], e: &Endianness, align: usize) -> Vec<ElfNote> {
+    let align = if align == 8 { 8 } else { 4 };
+    let mut notes = Vec::new();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let (namesz, descsz, n_type) = match (
+            get_u32(data, offset, e),
+            get_u32(data, offset + 4, e),
+            get_u32(data, offset + 8, e))
+        {
+            (Some(namesz), Some(descsz), Some(n_type)) =>
+                (namesz as usize, descsz as usize, n_type),
+            _ => break,
+        };
+
+        let name_start = offset + 12;
+        let desc_start = align_up(name_start + namesz, align);
+        let desc_end = desc_start + descsz;
+        if namesz > data.len() || descsz > data.len() || desc_end > data.len() {
+            break;
+        }
+
+        // The Go toolchain pads its owner to "Go\0\0", so drop every NUL
+        let mut name = data[name_start..name_start + namesz].to_vec();
+        while name.last() == Some(&0) {
+            name.pop();
+        }
+        notes.push(ElfNote {
+            name: name,
+            n_type: n_type,
+            desc: data[desc_start..desc_end].to_vec(),
+        });
+        offset = align_up(desc_end, align);
+    }
+    notes
+}
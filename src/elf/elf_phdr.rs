@@ -1,14 +1,25 @@
 #[macro_export]
 macro_rules! elf_phdr {
-    ($half:ty, $word:ty, $xword:ty, $addr:ty, $off:ty) => {
-        use ::to_host::Endianness;
-        use ::to_host::to_host_copy::ToHostCopy;
-        use ::to_host::ToHostCopyStruct;
-        use super::super::elf_phdr_type::ElfPhdrType;
-        use super::super::elf_phdr_flags::ElfPhdrFlags;
-        use super::primitive::*;
+    // Elf32_Phdr and Elf64_Phdr have the same fields, but p_flags is moved
+    // in the 64-bit variant to keep the wider fields naturally aligned.
+    (ELFCLASS32, $half:ty, $word:ty, $xword:ty, $addr:ty, $off:ty) => {
+        #[derive(Debug, Clone, Copy)]
+        #[repr(C)]
+        pub struct Elf_Phdr {
+            p_type: $word,
+            p_offset: $off,
+            p_vaddr: $addr,
+            p_paddr: $addr,
+            p_filesz: $xword,
+            p_memsz: $xword,
+            p_flags: $word,
+            p_align: $xword,
+        }
 
-        #[derive(Debug)]
+        elf_phdr!($half, $word, $xword, $addr, $off);
+    };
+    (ELFCLASS64, $half:ty, $word:ty, $xword:ty, $addr:ty, $off:ty) => {
+        #[derive(Debug, Clone, Copy)]
         #[repr(C)]
         pub struct Elf_Phdr {
             p_type: $word,
@@ -21,17 +32,33 @@ macro_rules! elf_phdr {
             p_align: $xword,
         }
 
+        elf_phdr!($half, $word, $xword, $addr, $off);
+    };
+    ($half:ty, $word:ty, $xword:ty, $addr:ty, $off:ty) => {
+        use ::std::io::{self, Read, Seek, Write};
+        use ::to_host::Endianness;
+        use ::to_host::to_host_copy::ToHostCopy;
+        use ::to_host::ToHostCopyStruct;
+        use super::super::elf_phdr_type::ElfPhdrType;
+        use super::super::elf_phdr_flags::{ElfPhdrFlags, PF_R, PF_W, PF_X};
+        use super::super::elf_note::{parse_notes, NT_GNU_PROPERTY_TYPE_0};
+        use super::super::elf_gnu_property::{GnuProperty, parse_gnu_properties};
+        use super::super::helpers::read_bytes;
+        use super::super::elf_json::JsonSegment;
+        use super::primitive::*;
+
         impl Elf_Phdr {
             #[allow(dead_code)]
-            pub fn print_with_endianness(&self, e: &Endianness) {
-                let p_type: ElfPhdrType = unsafe {
-                    ::std::mem::transmute(self.p_type.to_host_copy(e))
-                };
+            pub fn print_with_endianness<W: Write>(
+                &self, e: &Endianness, out: &mut W) -> io::Result<()>
+            {
+                let p_type = ElfPhdrType::new(self.p_type.to_host_copy(e));
                 let p_flags: ElfPhdrFlags = unsafe {
                     ::std::mem::transmute(self.p_flags.to_host_copy(e))
                 };
 
-                print!(
+                try!(write!(
+                    out,
                     concat!(
                         "{: <15}",
                         "{:#08x} ",
@@ -50,7 +77,162 @@ macro_rules! elf_phdr {
                     self.p_memsz.to_host_copy(e),
                     p_flags,
                     self.p_align.to_host_copy(e),
-                );
+                ));
+                Ok(())
+            }
+        }
+
+        impl Elf_Phdr {
+            #[allow(dead_code)]
+            pub fn get_type(&self) -> $word {
+                self.p_type
+            }
+            #[allow(dead_code)]
+            pub fn get_flags(&self) -> $word {
+                self.p_flags
+            }
+            #[allow(dead_code)]
+            pub fn get_offset(&self) -> $off {
+                self.p_offset
+            }
+            #[allow(dead_code)]
+            pub fn get_vaddr(&self) -> $addr {
+                self.p_vaddr
+            }
+            #[allow(dead_code)]
+            pub fn get_paddr(&self) -> $addr {
+                self.p_paddr
+            }
+            #[allow(dead_code)]
+            pub fn get_filesz(&self) -> $xword {
+                self.p_filesz
+            }
+            #[allow(dead_code)]
+            pub fn get_memsz(&self) -> $xword {
+                self.p_memsz
+            }
+            #[allow(dead_code)]
+            pub fn get_align(&self) -> $xword {
+                self.p_align
+            }
+
+            // Decodes the GNU property notes of a PT_GNU_PROPERTY segment.
+            // The header is expected to be in host byte order.
+            #[allow(dead_code)]
+            pub fn read_gnu_properties<R: Read + Seek>(
+                &self, e: &Endianness, reader: &mut R) -> Vec<GnuProperty>
+            {
+                let data = read_bytes(
+                    reader, self.p_offset as u64, self.p_filesz as u64)
+                    .unwrap_or(Vec::new());
+                let is_64bit = ::std::mem::size_of::<$addr>() == 8;
+                parse_notes(&data, e, self.p_align as usize)
+                    .iter()
+                    .filter(|n| n.is_gnu() && n.get_type() == NT_GNU_PROPERTY_TYPE_0)
+                    .flat_map(|n| parse_gnu_properties(n.get_desc(), e, is_64bit))
+                    .collect()
+            }
+        }
+
+        // Program headers passed to the functions below are expected to be
+        // in host byte order.
+
+        // Finds the PT_LOAD segment whose file-backed part contains `vaddr`.
+        fn find_load_segment(phdrs: &[Elf_Phdr], vaddr: $addr) -> Option<&Elf_Phdr> {
+            phdrs.iter().find(|phdr| {
+                phdr.p_type == ElfPhdrType::PT_LOAD.get_value()
+                && vaddr >= phdr.p_vaddr
+                && (vaddr - phdr.p_vaddr) < phdr.p_filesz as $addr
+            })
+        }
+
+        // Translates a virtual address into a file offset using PT_LOAD
+        // segments.
+        #[allow(dead_code)]
+        pub fn vaddr_to_offset(phdrs: &[Elf_Phdr], vaddr: $addr) -> Option<$off> {
+            find_load_segment(phdrs, vaddr)
+                .map(|phdr| (vaddr - phdr.p_vaddr) as $off + phdr.p_offset)
+        }
+
+        // Indices of the segments that are readable, writable and executable
+        // at once, which breaks W^X. JIT-heavy programs may want this.
+        #[allow(dead_code)]
+        pub fn get_rwx_segments(phdrs: &[Elf_Phdr]) -> Vec<usize> {
+            let rwx = PF_R | PF_W | PF_X;
+            phdrs.iter().enumerate()
+                .filter(|&(_, phdr)| (phdr.p_flags & rwx) == rwx)
+                .map(|(i, _)| i)
+                .collect()
+        }
+
+        // Program headers are expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn get_json_segments(phdrs: &[Elf_Phdr]) -> Vec<JsonSegment> {
+            phdrs.iter().enumerate()
+                .map(|(i, p)| JsonSegment {
+                    index: i,
+                    segment_type: format!("{}", ElfPhdrType::new(p.p_type)),
+                    flags: format!("{}", ElfPhdrFlags::new(p.p_flags)).replace(' ', ""),
+                    offset: p.p_offset as u64,
+                    vaddr: p.p_vaddr as u64,
+                    paddr: p.p_paddr as u64,
+                    filesz: p.p_filesz as u64,
+                    memsz: p.p_memsz as u64,
+                    align: p.p_align as u64,
+                })
+                .collect()
+        }
+
+        // PT_LOAD entries must be sorted by p_vaddr, and loaders which map
+        // the image from the first and last of them get its extent wrong
+        // otherwise. Program headers are expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn get_load_order_problems(phdrs: &[Elf_Phdr]) -> Vec<String> {
+            let loads: Vec<(usize, &Elf_Phdr)> = phdrs.iter().enumerate()
+                .filter(|&(_, p)| p.p_type == ElfPhdrType::PT_LOAD.get_value())
+                .collect();
+            loads.windows(2)
+                .filter(|pair| pair[1].1.p_vaddr < pair[0].1.p_vaddr)
+                .map(|pair| format!(
+                    "LOAD segment [{:02}] at {:#x} comes after LOAD segment [{:02}] at {:#x}, \
+                     but loadable segments must be in ascending order of address",
+                    pair[1].0, pair[1].1.p_vaddr, pair[0].0, pair[0].1.p_vaddr))
+                .collect()
+        }
+
+        #[allow(dead_code)]
+        pub fn has_rwx_segment(phdrs: &[Elf_Phdr]) -> bool {
+            !get_rwx_segments(phdrs).is_empty()
+        }
+
+        // Returns how many file-backed bytes of the containing PT_LOAD
+        // segment follow `vaddr`, which bounds tables of unknown size.
+        #[allow(dead_code)]
+        pub fn get_vaddr_extent(phdrs: &[Elf_Phdr], vaddr: $addr) -> Option<u64> {
+            find_load_segment(phdrs, vaddr)
+                .map(|phdr| phdr.p_filesz as u64 - (vaddr - phdr.p_vaddr) as u64)
+        }
+
+        // Reads `len` bytes at a virtual address. A range which runs past the
+        // file-backed part of its segment, even if the next segment continues
+        // it, yields None rather than a truncated buffer.
+        #[allow(dead_code)]
+        pub fn read_vaddr<R: Read + Seek>(
+            phdrs: &[Elf_Phdr], vaddr: $addr, len: usize, reader: &mut R)
+            -> Option<Vec<u8>>
+        {
+            let phdr = match find_load_segment(phdrs, vaddr) {
+                Some(phdr) => phdr,
+                None => return None,
+            };
+            let start = (vaddr - phdr.p_vaddr) as u64;
+            if len as u64 > phdr.p_filesz as u64 - start {
+                return None;
+            }
+
+            match read_bytes(reader, phdr.p_offset as u64 + start, len as u64) {
+                Ok(data) if data.len() == len => Some(data),
+                _ => None,
             }
         }
 
@@ -1,11 +1,21 @@
 use ::std::fmt::{Display, Formatter};
 
+pub const PF_X: u32 = 0b001;
+pub const PF_W: u32 = 0b010;
+pub const PF_R: u32 = 0b100;
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct ElfPhdrFlags {
     flags: u32,
 }
 
+impl ElfPhdrFlags {
+    pub fn new(flags: u32) -> ElfPhdrFlags {
+        ElfPhdrFlags { flags: flags }
+    }
+}
+
 impl Display for ElfPhdrFlags {
     fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
         let maybe_r;
@@ -1,49 +1,65 @@
 use ::std::fmt::{Display, Formatter};
 
-#[repr(u32)]
-#[derive(Debug)]
+// Segment types are open-ended like dynamic tags (PT_GNU_PROPERTY and friends
+// keep being added), so this is a newtype over the raw value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ElfPhdrType {
+    p_type: u32,
+}
+
 #[allow(dead_code)]
-pub enum ElfPhdrType {
-    PT_NULL = 0,
-    PT_LOAD = 1,
-    PT_DYNAMIC = 2,
-    PT_INTERP = 3,
-    PT_NOTE = 4,
-    PT_SHLIB = 5,
-    PT_PHDR = 6,
-    PT_TLS = 7,
-    PT_NUM = 8,
-    PT_LOOS = 0x60000000,
-    PT_GNU_EH_FRAME = 0x6474e550,
-    PT_GNU_STACK = 0x6474e551,
-    PT_GNU_RELRO = 0x6474e552,
-    PT_LOSUNW = 0x6ffffffa,
-    PT_SUNWSTACK = 0x6ffffffb,
-    PT_HISUNW = 0x6fffffff,
-    PT_LOPROC = 0x70000000,
+impl ElfPhdrType {
+    pub const PT_NULL: ElfPhdrType = ElfPhdrType { p_type: 0 };
+    pub const PT_LOAD: ElfPhdrType = ElfPhdrType { p_type: 1 };
+    pub const PT_DYNAMIC: ElfPhdrType = ElfPhdrType { p_type: 2 };
+    pub const PT_INTERP: ElfPhdrType = ElfPhdrType { p_type: 3 };
+    pub const PT_NOTE: ElfPhdrType = ElfPhdrType { p_type: 4 };
+    pub const PT_SHLIB: ElfPhdrType = ElfPhdrType { p_type: 5 };
+    pub const PT_PHDR: ElfPhdrType = ElfPhdrType { p_type: 6 };
+    pub const PT_TLS: ElfPhdrType = ElfPhdrType { p_type: 7 };
+    pub const PT_NUM: ElfPhdrType = ElfPhdrType { p_type: 8 };
+    pub const PT_LOOS: ElfPhdrType = ElfPhdrType { p_type: 0x60000000 };
+    pub const PT_GNU_EH_FRAME: ElfPhdrType = ElfPhdrType { p_type: 0x6474e550 };
+    pub const PT_GNU_STACK: ElfPhdrType = ElfPhdrType { p_type: 0x6474e551 };
+    pub const PT_GNU_RELRO: ElfPhdrType = ElfPhdrType { p_type: 0x6474e552 };
+    pub const PT_GNU_PROPERTY: ElfPhdrType = ElfPhdrType { p_type: 0x6474e553 };
+    pub const PT_LOSUNW: ElfPhdrType = ElfPhdrType { p_type: 0x6ffffffa };
+    pub const PT_SUNWSTACK: ElfPhdrType = ElfPhdrType { p_type: 0x6ffffffb };
+    pub const PT_HISUNW: ElfPhdrType = ElfPhdrType { p_type: 0x6fffffff };
+    pub const PT_LOPROC: ElfPhdrType = ElfPhdrType { p_type: 0x70000000 };
+
+    pub fn new(p_type: u32) -> ElfPhdrType {
+        ElfPhdrType { p_type: p_type }
+    }
+    pub fn get_value(&self) -> u32 {
+        self.p_type
+    }
 }
 
 impl Display for ElfPhdrType {
     fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
-        use self::ElfPhdrType::*;
         let s = match *self {
-            PT_NULL => "NULL",
-            PT_LOAD => "LOAD",
-            PT_DYNAMIC => "DYNAMIC",
-            PT_INTERP => "INTERP",
-            PT_NOTE => "NOTE",
-            PT_SHLIB => "SHLIB",
-            PT_PHDR => "PHDR",
-            PT_TLS => "TLS",
-            PT_NUM => "NUM",
-            PT_LOOS => "LOOS",
-            PT_GNU_EH_FRAME => "EH_FRAME",
-            PT_GNU_STACK => "GNU_STACK",
-            PT_GNU_RELRO => "GNU_RELRO",
-            PT_LOSUNW => "LOSUNW",
-            PT_SUNWSTACK => "SUNWBSS",
-            PT_HISUNW => "HISUNW",
-            PT_LOPROC => "LOPROC",
+            ElfPhdrType::PT_NULL => "NULL",
+            ElfPhdrType::PT_LOAD => "LOAD",
+            ElfPhdrType::PT_DYNAMIC => "DYNAMIC",
+            ElfPhdrType::PT_INTERP => "INTERP",
+            ElfPhdrType::PT_NOTE => "NOTE",
+            ElfPhdrType::PT_SHLIB => "SHLIB",
+            ElfPhdrType::PT_PHDR => "PHDR",
+            ElfPhdrType::PT_TLS => "TLS",
+            ElfPhdrType::PT_NUM => "NUM",
+            ElfPhdrType::PT_LOOS => "LOOS",
+            ElfPhdrType::PT_GNU_EH_FRAME => "GNU_EH_FRAME",
+            ElfPhdrType::PT_GNU_STACK => "GNU_STACK",
+            ElfPhdrType::PT_GNU_RELRO => "GNU_RELRO",
+            ElfPhdrType::PT_GNU_PROPERTY => "GNU_PROPERTY",
+            ElfPhdrType::PT_LOSUNW => "LOSUNW",
+            ElfPhdrType::PT_SUNWSTACK => "SUNWBSS",
+            ElfPhdrType::PT_HISUNW => "HISUNW",
+            ElfPhdrType::PT_LOPROC => "LOPROC",
+            ElfPhdrType { p_type } => {
+                return fmt.pad(&format!("{:#x}", p_type))
+            }
         };
         fmt.pad(s)
     }
@@ -0,0 +1,834 @@
+#[macro_export]
+macro_rules! elf_rel {
+    // r_info packs the symbol index above the relocation type, which takes
+    // the low 8 bits in 32-bit files and the low 32 bits in 64-bit ones.
+    ($info:ty, $addend:ty, $addr:ty, $type_bits:expr) => {
+        use ::std::io::{self, Read, Seek, Write};
+        use ::to_host::Endianness;
+        use ::to_host::to_host_copy::ToHostCopy;
+        use ::to_host::ToHostCopyStruct;
+        use super::super::elf_error::ElfError;
+        use super::super::elf_dyn_tag::ElfDynTag;
+        use super::super::elf_rel_type::{EM_386, EM_X86_64, EM_AARCH64};
+        use super::super::elf_rel_type::{get_reloc_type_name, get_relative_type};
+        use super::super::elf_shdr_type::ElfShdrType;
+        use super::super::elf_sym_info::ElfSymType;
+        use super::super::helpers::{read_bytes, get_str, EntryLimit};
+        use super::elf_dyn::{Elf_Dyn, find_dynamic_phdr, get_dyn_val, read_dynstr};
+        use super::elf_phdr::{Elf_Phdr, read_vaddr};
+        use super::elf_shdr::{Elf_Shdr, find_section_by_name, read_section_data, read_shstrtab};
+        use super::elf_sym::Elf_Sym;
+        use super::helpers::{read_dyns, read_syms};
+        use super::primitive::*;
+
+        const EM_ARM: u16 = 40;
+
+        #[derive(Debug, Clone, Copy)]
+        #[repr(C)]
+        pub struct Elf_Rel {
+            r_offset: $addr,
+            r_info: $info,
+        }
+
+        #[derive(Debug, Clone, Copy)]
+        #[repr(C)]
+        pub struct Elf_Rela {
+            r_offset: $addr,
+            r_info: $info,
+            r_addend: $addend,
+        }
+
+        impl Elf_Rel {
+            #[allow(dead_code)]
+            pub fn get_offset(&self) -> $addr {
+                self.r_offset
+            }
+            #[allow(dead_code)]
+            pub fn get_info(&self) -> $info {
+                self.r_info
+            }
+            #[allow(dead_code)]
+            pub fn get_sym(&self) -> u32 {
+                (self.r_info as u64 >> $type_bits) as u32
+            }
+            #[allow(dead_code)]
+            pub fn get_type(&self) -> u32 {
+                (self.r_info as u64 & ((1u64 << $type_bits) - 1)) as u32
+            }
+        }
+
+        impl Elf_Rela {
+            #[allow(dead_code)]
+            pub fn get_offset(&self) -> $addr {
+                self.r_offset
+            }
+            #[allow(dead_code)]
+            pub fn get_info(&self) -> $info {
+                self.r_info
+            }
+            #[allow(dead_code)]
+            pub fn get_sym(&self) -> u32 {
+                (self.r_info as u64 >> $type_bits) as u32
+            }
+            #[allow(dead_code)]
+            pub fn get_type(&self) -> u32 {
+                (self.r_info as u64 & ((1u64 << $type_bits) - 1)) as u32
+            }
+            #[allow(dead_code)]
+            pub fn get_addend(&self) -> $addend {
+                self.r_addend
+            }
+        }
+
+        impl ToHostCopyStruct for Elf_Rel {
+            fn to_host_copy(&self, endianness: &Endianness) -> Self {
+                let e = endianness;
+                Elf_Rel {
+                    r_offset: self.r_offset.to_host_copy(e),
+                    r_info: self.r_info.to_host_copy(e),
+                }
+            }
+        }
+
+        impl ToHostCopyStruct for Elf_Rela {
+            fn to_host_copy(&self, endianness: &Endianness) -> Self {
+                let e = endianness;
+                Elf_Rela {
+                    r_offset: self.r_offset.to_host_copy(e),
+                    r_info: self.r_info.to_host_copy(e),
+                    r_addend: self.r_addend.to_host_copy(e),
+                }
+            }
+        }
+
+        // Reads the entries of a relocation section, stepping by sh_entsize.
+        // The section header is expected in host byte order, the returned
+        // entries are in host byte order.
+        fn read_table<T: Copy + ToHostCopyStruct, R: Read + Seek>(
+            shdr: &Elf_Shdr, e: &Endianness, reader: &mut R)
+            -> Result<Vec<T>, ElfError>
+        {
+            let size = ::std::mem::size_of::<T>();
+            let count = try!(shdr.get_entry_count(size));
+            let stride = match shdr.get_entsize() as usize {
+                0 => size,
+                entsize if entsize < size =>
+                    return Err(ElfError::UnexpectedEntrySize(entsize as u64)),
+                entsize => entsize,
+            };
+            let b = try!(read_bytes(reader, shdr.get_offset() as u64, shdr.get_size() as u64));
+            Ok(parse_table(&b, stride, count as usize, e))
+        }
+
+        fn parse_table<T: Copy + ToHostCopyStruct>(
+            b: &[u8], stride: usize, count: usize, e: &Endianness) -> Vec<T>
+        {
+            let size = ::std::mem::size_of::<T>();
+            b.chunks(stride)
+                .take(count)
+                .filter(|chunk| chunk.len() >= size)
+                .map(|chunk| {
+                    let entry: T = unsafe {
+                        ::std::ptr::read_unaligned(chunk.as_ptr() as *const T)
+                    };
+                    entry.to_host_copy(e)
+                })
+                .collect()
+        }
+
+        #[allow(dead_code)]
+        pub fn read_rels<R: Read + Seek>(
+            shdr: &Elf_Shdr, e: &Endianness, reader: &mut R)
+            -> Result<Vec<Elf_Rel>, ElfError>
+        {
+            read_table(shdr, e, reader)
+        }
+
+        #[allow(dead_code)]
+        pub fn read_relas<R: Read + Seek>(
+            shdr: &Elf_Shdr, e: &Endianness, reader: &mut R)
+            -> Result<Vec<Elf_Rela>, ElfError>
+        {
+            read_table(shdr, e, reader)
+        }
+
+        // Reads the words of an SHT_RELR section. They are address-sized, and
+        // sh_entsize is expected to say so.
+        #[allow(dead_code)]
+        pub fn read_relrs<R: Read + Seek>(
+            shdr: &Elf_Shdr, e: &Endianness, reader: &mut R)
+            -> Result<Vec<$addr>, ElfError>
+        {
+            let size = ::std::mem::size_of::<$addr>();
+            let count = try!(shdr.get_entry_count(size));
+            if shdr.get_entsize() as usize != 0 && shdr.get_entsize() as usize != size {
+                return Err(ElfError::UnexpectedEntrySize(shdr.get_entsize() as u64));
+            }
+            let b = try!(read_bytes(reader, shdr.get_offset() as u64, shdr.get_size() as u64));
+            Ok(parse_relr_words(&b[..::std::cmp::min(b.len(), count as usize * size)], e))
+        }
+
+        fn parse_relr_words(b: &[u8], e: &Endianness) -> Vec<$addr> {
+            let size = ::std::mem::size_of::<$addr>();
+            b.chunks(size)
+                .filter(|chunk| chunk.len() == size)
+                .map(|chunk| {
+                    let word: $addr = unsafe {
+                        ::std::ptr::read_unaligned(chunk.as_ptr() as *const $addr)
+                    };
+                    word.to_host_copy(e)
+                })
+                .collect()
+        }
+
+        // Expands SHT_RELR words into the addresses they relocate. An even
+        // word is an address to relocate. An odd word is a bitmap for the
+        // words following the last address: bit n (n >= 1) set means the
+        // (n - 1)-th of them is relocated too, and the next bitmap continues
+        // where this one's bits end.
+        #[allow(dead_code)]
+        pub fn decode_relr(words: &[$addr]) -> Vec<$addr> {
+            let word_size = ::std::mem::size_of::<$addr>() as $addr;
+            let bits = (word_size * 8) as u32;
+            let mut addresses = Vec::new();
+            let mut base: $addr = 0;
+
+            for &word in words {
+                if (word & 1) == 0 {
+                    addresses.push(word);
+                    base = word.wrapping_add(word_size);
+                } else {
+                    for bit in 1..bits {
+                        if ((word >> bit) & 1) != 0 {
+                            addresses.push(
+                                base.wrapping_add((bit - 1) as $addr * word_size));
+                        }
+                    }
+                    base = base.wrapping_add((bits - 1) as $addr * word_size);
+                }
+            }
+            addresses
+        }
+
+        // Section symbols are named by their section.
+        fn get_symbol_name<'a>(
+            sym: &Elf_Sym, strtab: &'a [u8], shdrs: &[Elf_Shdr], shstrtab: &'a [u8])
+            -> &'a str
+        {
+            if sym.get_type() != ElfSymType::STT_SECTION {
+                return get_str(strtab, sym.get_name() as u64);
+            }
+            match shdrs.get(sym.get_shndx() as usize) {
+                Some(shdr) => get_str(shstrtab, shdr.get_name() as u64),
+                None => "<corrupt>",
+            }
+        }
+
+        fn format_addend(addend: i64) -> String {
+            if addend < 0 {
+                format!("- {:x}", (addend as i128).abs())
+            } else {
+                format!("+ {:x}", addend)
+            }
+        }
+
+        // Prints one REL or RELA entry the way readelf -rW does. Symbols are
+        // named by the symbol table the section links to, section symbols by
+        // their section.
+        fn print_reloc<W: Write>(
+            machine: u16, offset: $addr, info: u64, sym_index: u32, r_type: u32,
+            addend: Option<i64>, syms: &[Elf_Sym], strtab: &[u8],
+            shdrs: &[Elf_Shdr], shstrtab: &[u8], out: &mut W)
+            -> io::Result<()>
+        {
+            let width = ::std::mem::size_of::<$addr>() * 2;
+            let is_32bit = width == 8;
+            let type_name = match get_reloc_type_name(machine, r_type) {
+                Some(name) => name.to_string(),
+                None => format!("unrecognized: {:x}", r_type),
+            };
+
+            try!(write!(
+                out,
+                "{:0w$x}  {:0w$x} {:<22}",
+                offset, info, type_name, w = width));
+            if sym_index != 0 {
+                let (value, name) = match syms.get(sym_index as usize) {
+                    Some(sym) => (
+                        sym.get_value() as u64,
+                        get_symbol_name(sym, strtab, shdrs, shstrtab)),
+                    None => (0, "<corrupt>"),
+                };
+                try!(write!(
+                    out,
+                    " {:0w$x}{}{}",
+                    value, if is_32bit { "   " } else { " " }, name, w = width));
+                if let Some(addend) = addend {
+                    try!(write!(out, " {}", format_addend(addend)));
+                }
+            } else if let Some(addend) = addend {
+                try!(write!(
+                    out,
+                    "{:w$}{:x}",
+                    "", addend, w = if is_32bit { 12 } else { 20 }));
+            }
+            writeln!(out, "")
+        }
+
+        fn is_relocation_type(sh_type: ElfShdrType) -> bool {
+            sh_type == ElfShdrType::SHT_REL
+                || sh_type == ElfShdrType::SHT_RELA
+                || sh_type == ElfShdrType::SHT_RELR
+        }
+
+        // Whether the section called `name` holds relocations, for checking
+        // --only-section. None if there is no such section.
+        #[allow(dead_code)]
+        pub fn is_relocation_section<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R, name: &str)
+            -> Option<bool>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            find_section_by_name(&shdrs, &shstrtab, name)
+                .map(|shdr| is_relocation_type(ElfShdrType::new(shdr.get_type())))
+        }
+
+        // Prints every relocation section (readelf -r), or only the ones
+        // called `only_section`. SHT_RELR sections are expanded into the
+        // addresses they relocate.
+        #[allow(dead_code)]
+        pub fn print_relocations<R: Read + Seek, W: Write>(
+            machine: u16, shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness,
+            reader: &mut R, out: &mut W, max_entries: Option<usize>,
+            only_section: Option<&str>)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let width = ::std::mem::size_of::<$addr>() * 2;
+            let is_32bit = width == 8;
+            let mut found = false;
+
+            for shdr in &shdrs {
+                let sh_type = ElfShdrType::new(shdr.get_type());
+                if !is_relocation_type(sh_type) {
+                    continue;
+                }
+                let name = get_str(&shstrtab, shdr.get_name() as u64);
+                if only_section.map_or(false, |only| only != name) {
+                    continue;
+                }
+                found = true;
+                // readelf says nothing about empty ones either
+                if shdr.get_size() == 0 {
+                    continue;
+                }
+
+                if sh_type == ElfShdrType::SHT_RELR {
+                    let words = match read_relrs(shdr, e, reader) {
+                        Ok(words) => words,
+                        Err(err) => {
+                            warning!("relocation section {}: {}", name, err);
+                            continue;
+                        }
+                    };
+                    let addresses = decode_relr(&words);
+                    try!(heading!(out, ""));
+                    try!(heading!(
+                        out,
+                        "Relocation section '{}' at offset {:#x} contains {} {}:",
+                        name, shdr.get_offset(), words.len(),
+                        if words.len() == 1 { "entry" } else { "entries" }));
+                    try!(heading!(out, "  {} offsets", addresses.len()));
+                    let mut limit = EntryLimit::new(max_entries);
+                    for address in addresses {
+                        if !limit.take() {
+                            continue;
+                        }
+                        try!(writeln!(out, "{:0w$x}", address, w = width));
+                    }
+                    try!(limit.print_omitted(out));
+                    continue;
+                }
+
+                let is_rela = sh_type == ElfShdrType::SHT_RELA;
+                let relocs: Result<Vec<($addr, u64, u32, u32, Option<i64>)>, ElfError> =
+                    if is_rela {
+                        read_relas(shdr, e, reader).map(|r| r.iter().map(|r| (
+                            r.get_offset(), r.get_info() as u64, r.get_sym(),
+                            r.get_type(), Some(r.get_addend() as i64))).collect())
+                    } else {
+                        read_rels(shdr, e, reader).map(|r| r.iter().map(|r| (
+                            r.get_offset(), r.get_info() as u64, r.get_sym(),
+                            r.get_type(), None)).collect())
+                    };
+                let relocs = match relocs {
+                    Ok(relocs) => relocs,
+                    Err(err) => {
+                        warning!("relocation section {}: {}", name, err);
+                        continue;
+                    }
+                };
+
+                let symtab = shdrs.get(shdr.get_link() as usize);
+                let syms: Vec<Elf_Sym> = match symtab {
+                    Some(symtab) if shdr.get_link() != 0 =>
+                        read_syms(symtab, e, reader).unwrap_or(Vec::new()),
+                    _ => Vec::new(),
+                };
+                let strtab = match symtab.and_then(|s| shdrs.get(s.get_link() as usize)) {
+                    Some(strtab) if shdr.get_link() != 0 => read_section_data(strtab, reader).unwrap_or(Vec::new()),
+                    _ => Vec::new(),
+                };
+
+                try!(heading!(out, ""));
+                try!(heading!(
+                    out,
+                    "Relocation section '{}' at offset {:#x} contains {} {}:",
+                    name, shdr.get_offset(), relocs.len(),
+                    if relocs.len() == 1 { "entry" } else { "entries" }));
+                if is_32bit {
+                    try!(heading!(
+                        out,
+                        " Offset     Info    Type                Sym. Value  Symbol's Name{}",
+                        if is_rela { " + Addend" } else { "" }));
+                } else {
+                    try!(heading!(
+                        out,
+                        "    Offset             Info             Type               Symbol's Value  Symbol's Name{}",
+                        if is_rela { " + Addend" } else { "" }));
+                }
+                let mut limit = EntryLimit::new(max_entries);
+                for &(offset, info, sym, r_type, addend) in &relocs {
+                    if !limit.take() {
+                        continue;
+                    }
+                    try!(print_reloc(
+                        machine, offset, info, sym, r_type, addend, &syms, &strtab,
+                        &shdrs, &shstrtab, out));
+                }
+                try!(limit.print_omitted(out));
+            }
+
+            if !found {
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "There are no relocations in this file."));
+            }
+            Ok(())
+        }
+
+        // A relocation with its type and symbol looked up. Entries of RELR
+        // tables have the machine's relative type, and neither a symbol nor
+        // an explicit addend.
+        #[derive(Debug, Clone)]
+        pub struct ResolvedReloc {
+            r_type: u32,
+            type_name: Option<&'static str>,
+            offset: $addr,
+            addend: Option<i64>,
+            symbol: Option<String>,
+        }
+
+        #[allow(dead_code)]
+        impl ResolvedReloc {
+            pub fn get_type(&self) -> u32 {
+                self.r_type
+            }
+            // None for types not known for the machine.
+            pub fn get_type_name(&self) -> Option<&'static str> {
+                self.type_name
+            }
+            pub fn get_offset(&self) -> $addr {
+                self.offset
+            }
+            // None for REL and RELR entries, whose addend is at the target.
+            pub fn get_addend(&self) -> Option<i64> {
+                self.addend
+            }
+            pub fn get_symbol_name(&self) -> Option<&str> {
+                self.symbol.as_ref().map(|s| s.as_str())
+            }
+        }
+
+        fn resolve(
+            machine: u16, offset: $addr, r_type: u32, addend: Option<i64>,
+            symbol: Option<String>)
+            -> ResolvedReloc
+        {
+            ResolvedReloc {
+                r_type: r_type,
+                type_name: get_reloc_type_name(machine, r_type),
+                offset: offset,
+                addend: addend,
+                symbol: symbol,
+            }
+        }
+
+        fn resolve_relrs(machine: u16, words: &[$addr]) -> Vec<ResolvedReloc> {
+            let r_type = get_relative_type(machine).unwrap_or(0);
+            decode_relr(words).into_iter()
+                .map(|offset| resolve(machine, offset, r_type, None, None))
+                .collect()
+        }
+
+        // All relocations of the file. They are found through the section
+        // headers when there are relocation sections, and through the
+        // dynamic section otherwise, which is all a file whose section
+        // headers have been stripped has.
+        #[allow(dead_code)]
+        pub fn get_relocations<R: Read + Seek>(
+            machine: u16, phdrs: &[Elf_Phdr], shdrs: &[Elf_Shdr], shstrndx: u16,
+            e: &Endianness, reader: &mut R)
+            -> impl Iterator<Item = ResolvedReloc>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let relocs = get_section_relocations(machine, &shdrs, shstrndx, e, reader);
+            let relocs = match relocs {
+                Some(relocs) => relocs,
+                None => {
+                    let phdrs: Vec<Elf_Phdr> =
+                        phdrs.iter().map(|p| p.to_host_copy(e)).collect();
+                    get_dynamic_relocations(machine, &phdrs, e, reader)
+                }
+            };
+            relocs.into_iter()
+        }
+
+        // None if there are no relocation sections.
+        fn get_section_relocations<R: Read + Seek>(
+            machine: u16, shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness,
+            reader: &mut R)
+            -> Option<Vec<ResolvedReloc>>
+        {
+            let shstrtab = read_shstrtab(shdrs, shstrndx, reader);
+            let mut found = false;
+            let mut r = Vec::new();
+
+            for shdr in shdrs {
+                let sh_type = ElfShdrType::new(shdr.get_type());
+                if sh_type == ElfShdrType::SHT_RELR {
+                    found = true;
+                    match read_relrs(shdr, e, reader) {
+                        Ok(words) => r.extend(resolve_relrs(machine, &words)),
+                        Err(err) => warning!(
+                            "relocation section {}: {}",
+                            get_str(&shstrtab, shdr.get_name() as u64), err),
+                    }
+                    continue;
+                }
+                if sh_type != ElfShdrType::SHT_REL && sh_type != ElfShdrType::SHT_RELA {
+                    continue;
+                }
+                found = true;
+
+                let entries: Result<Vec<($addr, u32, u32, Option<i64>)>, ElfError> =
+                    if sh_type == ElfShdrType::SHT_RELA {
+                        read_relas(shdr, e, reader).map(|r| r.iter().map(|r| (
+                            r.get_offset(), r.get_sym(), r.get_type(),
+                            Some(r.get_addend() as i64))).collect())
+                    } else {
+                        read_rels(shdr, e, reader).map(|r| r.iter().map(|r| (
+                            r.get_offset(), r.get_sym(), r.get_type(), None)).collect())
+                    };
+                let entries = match entries {
+                    Ok(entries) => entries,
+                    Err(err) => {
+                        warning!(
+                            "relocation section {}: {}",
+                            get_str(&shstrtab, shdr.get_name() as u64), err);
+                        continue;
+                    }
+                };
+
+                let symtab = match shdr.get_link() {
+                    0 => None,
+                    link => shdrs.get(link as usize),
+                };
+                let syms: Vec<Elf_Sym> = match symtab {
+                    Some(symtab) => read_syms(symtab, e, reader).unwrap_or(Vec::new()),
+                    None => Vec::new(),
+                };
+                let strtab = match symtab.and_then(|s| shdrs.get(s.get_link() as usize)) {
+                    Some(strtab) => read_section_data(strtab, reader).unwrap_or(Vec::new()),
+                    None => Vec::new(),
+                };
+
+                for (offset, sym, r_type, addend) in entries {
+                    let symbol = match sym {
+                        0 => None,
+                        sym => syms.get(sym as usize).map(|s| {
+                            get_symbol_name(s, &strtab, shdrs, &shstrtab).to_string()
+                        }),
+                    };
+                    r.push(resolve(machine, offset, r_type, addend, symbol));
+                }
+            }
+
+            if found { Some(r) } else { None }
+        }
+
+        // Reads a table that .dynamic gives the address and size of.
+        fn read_dyn_range<R: Read + Seek>(
+            dyns: &[Elf_Dyn], phdrs: &[Elf_Phdr], addr: ElfDynTag,
+            size: ElfDynTag, reader: &mut R)
+            -> Vec<u8>
+        {
+            match (get_dyn_val(dyns, addr), get_dyn_val(dyns, size)) {
+                (Some(addr), Some(size)) =>
+                    read_vaddr(phdrs, addr as $addr, size as usize, reader)
+                        .unwrap_or(Vec::new()),
+                _ => Vec::new(),
+            }
+        }
+
+        fn get_dynamic_relocations<R: Read + Seek>(
+            machine: u16, phdrs: &[Elf_Phdr], e: &Endianness, reader: &mut R)
+            -> Vec<ResolvedReloc>
+        {
+            let dynamic = match find_dynamic_phdr(phdrs) {
+                Some(dynamic) => dynamic,
+                None => return Vec::new(),
+            };
+            let dyns = read_dyns(dynamic, e, reader).unwrap_or(Vec::new());
+            let dynstr = read_dynstr(&dyns, phdrs, reader);
+            let symtab = get_dyn_val(&dyns, ElfDynTag::DT_SYMTAB);
+            let sym_size = ::std::mem::size_of::<Elf_Sym>();
+            let rel_size = ::std::mem::size_of::<Elf_Rel>();
+            let rela_size = ::std::mem::size_of::<Elf_Rela>();
+
+            // .dynsym has no size in .dynamic, so each symbol is read as needed.
+            let symbol_name = |reader: &mut R, sym: u32| -> Option<String> {
+                if sym == 0 {
+                    return None;
+                }
+                let addr = match symtab {
+                    Some(symtab) => symtab as $addr + (sym as usize * sym_size) as $addr,
+                    None => return None,
+                };
+                read_vaddr(phdrs, addr, sym_size, reader).and_then(|b| {
+                    let syms: Vec<Elf_Sym> = parse_table(&b, sym_size, 1, e);
+                    syms.get(0).map(|s| get_str(&dynstr, s.get_name() as u64).to_string())
+                })
+            };
+
+            let mut r = Vec::new();
+            let rela_ent = get_dyn_val(&dyns, ElfDynTag::DT_RELAENT)
+                .map(|ent| ent as usize).unwrap_or(rela_size);
+            let rel_ent = get_dyn_val(&dyns, ElfDynTag::DT_RELENT)
+                .map(|ent| ent as usize).unwrap_or(rel_size);
+            let plt_is_rela =
+                get_dyn_val(&dyns, ElfDynTag::DT_PLTREL) == Some(ElfDynTag::DT_RELA.get_value() as _);
+
+            let tables = [
+                (ElfDynTag::DT_RELA, ElfDynTag::DT_RELASZ, true),
+                (ElfDynTag::DT_REL, ElfDynTag::DT_RELSZ, false),
+                (ElfDynTag::DT_JMPREL, ElfDynTag::DT_PLTRELSZ, plt_is_rela),
+            ];
+            for &(addr, size, is_rela) in tables.iter() {
+                let b = read_dyn_range(&dyns, phdrs, addr, size, reader);
+                if is_rela {
+                    if rela_ent < rela_size {
+                        continue;
+                    }
+                    let relas: Vec<Elf_Rela> = parse_table(&b, rela_ent, b.len() / rela_ent, e);
+                    for rela in relas {
+                        let symbol = symbol_name(reader, rela.get_sym());
+                        r.push(resolve(
+                            machine, rela.get_offset(), rela.get_type(),
+                            Some(rela.get_addend() as i64), symbol));
+                    }
+                } else {
+                    if rel_ent < rel_size {
+                        continue;
+                    }
+                    let rels: Vec<Elf_Rel> = parse_table(&b, rel_ent, b.len() / rel_ent, e);
+                    for rel in rels {
+                        let symbol = symbol_name(reader, rel.get_sym());
+                        r.push(resolve(machine, rel.get_offset(), rel.get_type(), None, symbol));
+                    }
+                }
+            }
+
+            let b = read_dyn_range(&dyns, phdrs, ElfDynTag::DT_RELR, ElfDynTag::DT_RELRSZ, reader);
+            r.extend(resolve_relrs(machine, &parse_relr_words(&b, e)));
+            r
+        }
+
+        // DT_RELACOUNT and DT_RELCOUNT say how many of the relocations in
+        // DT_RELA and DT_REL are relative. The linker sorts those first, and
+        // the dynamic linker applies that many without looking at their
+        // type, so a wrong count has it skip or misapply relocations.
+        #[allow(dead_code)]
+        pub fn get_relative_count_problems<R: Read + Seek>(
+            machine: u16, phdrs: &[Elf_Phdr], e: &Endianness, reader: &mut R)
+            -> Vec<String>
+        {
+            let mut r = Vec::new();
+            let relative = match get_relative_type(machine) {
+                Some(relative) => relative,
+                None => return r,
+            };
+            let phdrs: Vec<Elf_Phdr> =
+                phdrs.iter().map(|p| p.to_host_copy(e)).collect();
+            let dyns = match find_dynamic_phdr(&phdrs) {
+                Some(dynamic) => read_dyns(dynamic, e, reader).unwrap_or(Vec::new()),
+                None => return r,
+            };
+            let rel_size = ::std::mem::size_of::<Elf_Rel>();
+            let rela_size = ::std::mem::size_of::<Elf_Rela>();
+
+            let tables = [
+                (ElfDynTag::DT_RELACOUNT, ElfDynTag::DT_RELA, ElfDynTag::DT_RELASZ,
+                 ElfDynTag::DT_RELAENT, rela_size),
+                (ElfDynTag::DT_RELCOUNT, ElfDynTag::DT_REL, ElfDynTag::DT_RELSZ,
+                 ElfDynTag::DT_RELENT, rel_size),
+            ];
+            for &(count_tag, addr, size, ent, min_ent) in tables.iter() {
+                let count = match get_dyn_val(&dyns, count_tag) {
+                    Some(count) => count as u64,
+                    None => continue,
+                };
+                let ent = get_dyn_val(&dyns, ent).map(|ent| ent as usize).unwrap_or(min_ent);
+                if ent < min_ent {
+                    continue;
+                }
+                let b = read_dyn_range(&dyns, &phdrs, addr, size, reader);
+                let types: Vec<u32> = if min_ent == rela_size {
+                    let relas: Vec<Elf_Rela> = parse_table(&b, ent, b.len() / ent, e);
+                    relas.iter().map(|r| r.get_type()).collect()
+                } else {
+                    let rels: Vec<Elf_Rel> = parse_table(&b, ent, b.len() / ent, e);
+                    rels.iter().map(|r| r.get_type()).collect()
+                };
+                let found = types.iter().filter(|&&t| t == relative).count() as u64;
+                if count != found {
+                    r.push(format!(
+                        "DT_{} is {}, but DT_{} has {} relative relocations",
+                        count_tag, count, addr, found));
+                }
+            }
+            r
+        }
+
+        // Size of the reserved first PLT entry and of each stub after it.
+        fn get_plt_layout(machine: u16) -> Option<(u64, u64)> {
+            match machine {
+                EM_386 | EM_X86_64 => Some((16, 16)),
+                EM_ARM => Some((20, 12)),
+                EM_AARCH64 => Some((32, 16)),
+                _ => None,
+            }
+        }
+
+        // Lists the PLT stubs with the functions they jump to. The n-th
+        // relocation in .rela.plt (or .rel.plt) belongs to the n-th stub.
+        // With IBT the stubs which are called live in .plt.sec instead,
+        // which has no reserved first entry.
+        #[allow(dead_code)]
+        pub fn print_plt_stubs<R: Read + Seek, W: Write>(
+            machine: u16, shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness,
+            reader: &mut R, out: &mut W, max_entries: Option<usize>)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+
+            let reloc_shdr = find_section_by_name(&shdrs, &shstrtab, ".rela.plt")
+                .or_else(|| find_section_by_name(&shdrs, &shstrtab, ".rel.plt"));
+            let reloc_shdr = match reloc_shdr {
+                Some(shdr) => shdr,
+                None => {
+                    try!(writeln!(out, "There are no PLT relocations in this file."));
+                    return Ok(());
+                }
+            };
+            let relocs: Result<Vec<($addr, u32)>, ElfError> =
+                if ElfShdrType::new(reloc_shdr.get_type()) == ElfShdrType::SHT_RELA {
+                    read_relas(reloc_shdr, e, reader)
+                        .map(|r| r.iter().map(|r| (r.get_offset(), r.get_sym())).collect())
+                } else {
+                    read_rels(reloc_shdr, e, reader)
+                        .map(|r| r.iter().map(|r| (r.get_offset(), r.get_sym())).collect())
+                };
+            let relocs = match relocs {
+                Ok(relocs) => relocs,
+                Err(err) => {
+                    warning!("PLT relocations: {}", err);
+                    return Ok(());
+                }
+            };
+
+            let (plt_name, plt, header_size, entry_size) =
+                match (find_section_by_name(&shdrs, &shstrtab, ".plt.sec"),
+                       find_section_by_name(&shdrs, &shstrtab, ".plt"))
+            {
+                (Some(plt_sec), _) => {
+                    let entry_size = match plt_sec.get_entsize() as u64 {
+                        0 => 16,
+                        entsize => entsize,
+                    };
+                    (".plt.sec", plt_sec, 0, entry_size)
+                }
+                (None, Some(plt)) => match get_plt_layout(machine) {
+                    Some((header_size, entry_size)) => (".plt", plt, header_size, entry_size),
+                    None => {
+                        let entry_size = plt.get_entsize() as u64;
+                        if entry_size == 0 {
+                            warning!("the PLT layout of this machine is unknown");
+                            return Ok(());
+                        }
+                        warning!(
+                            "the PLT layout of this machine is unknown, assuming {}-byte entries",
+                            entry_size);
+                        (".plt", plt, entry_size, entry_size)
+                    }
+                },
+                (None, None) => {
+                    try!(writeln!(out, "There is no .plt section in this file."));
+                    return Ok(());
+                }
+            };
+
+            let syms: Vec<Elf_Sym> = match shdrs.get(reloc_shdr.get_link() as usize) {
+                Some(symtab) => read_syms(symtab, e, reader).unwrap_or(Vec::new()),
+                None => Vec::new(),
+            };
+            let strtab = match shdrs.get(reloc_shdr.get_link() as usize)
+                .and_then(|symtab| shdrs.get(symtab.get_link() as usize))
+            {
+                Some(strtab) => read_section_data(strtab, reader).unwrap_or(Vec::new()),
+                None => Vec::new(),
+            };
+            let width = ::std::mem::size_of::<$addr>() * 2;
+
+            try!(writeln!(out, ""));
+            try!(writeln!(
+                out,
+                "PLT stubs in {} ({} entries of {} bytes):",
+                plt_name, relocs.len(), entry_size));
+            try!(writeln!(
+                out, "  [Nr] {:<w$} {:<w$} Function", "Address", "GOT", w = width));
+            let mut limit = EntryLimit::new(max_entries);
+            for (i, &(got, sym)) in relocs.iter().enumerate() {
+                if !limit.take() {
+                    continue;
+                }
+                let address = plt.get_addr() as u64 + header_size + i as u64 * entry_size;
+                let name = match syms.get(sym as usize) {
+                    Some(sym) => get_str(&strtab, sym.get_name() as u64),
+                    None => "<corrupt>",
+                };
+                try!(writeln!(
+                    out,
+                    "  [{:2}] {:0w$x} {:0w$x} {}",
+                    i, address, got, name, w = width));
+            }
+            limit.print_omitted(out)
+        }
+    }
+}
@@ -0,0 +1,76 @@
+// Relocation types are defined per machine, so a type can only be named
+// together with e_machine.
+
+pub const EM_386: u16 = 3;
+pub const EM_X86_64: u16 = 62;
+pub const EM_AARCH64: u16 = 183;
+
+// Indexed by type.
+const R_386_NAMES: [&'static str; 44] = [
+    "R_386_NONE", "R_386_32", "R_386_PC32", "R_386_GOT32", "R_386_PLT32",
+    "R_386_COPY", "R_386_GLOB_DAT", "R_386_JUMP_SLOT", "R_386_RELATIVE",
+    "R_386_GOTOFF", "R_386_GOTPC", "R_386_32PLT", "", "", "R_386_TLS_TPOFF",
+    "R_386_TLS_IE", "R_386_TLS_GOTIE", "R_386_TLS_LE", "R_386_TLS_GD",
+    "R_386_TLS_LDM", "R_386_16", "R_386_PC16", "R_386_8", "R_386_PC8",
+    "R_386_TLS_GD_32", "R_386_TLS_GD_PUSH", "R_386_TLS_GD_CALL",
+    "R_386_TLS_GD_POP", "R_386_TLS_LDM_32", "R_386_TLS_LDM_PUSH",
+    "R_386_TLS_LDM_CALL", "R_386_TLS_LDM_POP", "R_386_TLS_LDO_32",
+    "R_386_TLS_IE_32", "R_386_TLS_LE_32", "R_386_TLS_DTPMOD32",
+    "R_386_TLS_DTPOFF32", "R_386_TLS_TPOFF32", "R_386_SIZE32",
+    "R_386_TLS_GOTDESC", "R_386_TLS_DESC_CALL", "R_386_TLS_DESC",
+    "R_386_IRELATIVE", "R_386_GOT32X",
+];
+
+const R_X86_64_NAMES: [&'static str; 43] = [
+    "R_X86_64_NONE", "R_X86_64_64", "R_X86_64_PC32", "R_X86_64_GOT32",
+    "R_X86_64_PLT32", "R_X86_64_COPY", "R_X86_64_GLOB_DAT",
+    "R_X86_64_JUMP_SLOT", "R_X86_64_RELATIVE", "R_X86_64_GOTPCREL",
+    "R_X86_64_32", "R_X86_64_32S", "R_X86_64_16", "R_X86_64_PC16",
+    "R_X86_64_8", "R_X86_64_PC8", "R_X86_64_DTPMOD64", "R_X86_64_DTPOFF64",
+    "R_X86_64_TPOFF64", "R_X86_64_TLSGD", "R_X86_64_TLSLD",
+    "R_X86_64_DTPOFF32", "R_X86_64_GOTTPOFF", "R_X86_64_TPOFF32",
+    "R_X86_64_PC64", "R_X86_64_GOTOFF64", "R_X86_64_GOTPC32",
+    "R_X86_64_GOT64", "R_X86_64_GOTPCREL64", "R_X86_64_GOTPC64",
+    "R_X86_64_GOTPLT64", "R_X86_64_PLTOFF64", "R_X86_64_SIZE32",
+    "R_X86_64_SIZE64", "R_X86_64_GOTPC32_TLSDESC", "R_X86_64_TLSDESC_CALL",
+    "R_X86_64_TLSDESC", "R_X86_64_IRELATIVE", "R_X86_64_RELATIVE64", "", "",
+    "R_X86_64_GOTPCRELX", "R_X86_64_REX_GOTPCRELX",
+];
+
+// Only the dynamic relocations, which are the ones a linked file keeps.
+const R_AARCH64_NAMES: [(u32, &'static str); 11] = [
+    (0, "R_AARCH64_NONE"),
+    (257, "R_AARCH64_ABS64"),
+    (258, "R_AARCH64_ABS32"),
+    (1024, "R_AARCH64_COPY"),
+    (1025, "R_AARCH64_GLOB_DAT"),
+    (1026, "R_AARCH64_JUMP_SLOT"),
+    (1027, "R_AARCH64_RELATIVE"),
+    (1028, "R_AARCH64_TLS_DTPMOD"),
+    (1029, "R_AARCH64_TLS_DTPREL"),
+    (1030, "R_AARCH64_TLS_TPREL"),
+    (1032, "R_AARCH64_IRELATIVE"),
+];
+
+// The type a relocation packed into SHT_RELR stands for.
+#[allow(dead_code)]
+pub fn get_relative_type(machine: u16) -> Option<u32> {
+    match machine {
+        EM_386 | EM_X86_64 => Some(8),
+        EM_AARCH64 => Some(1027),
+        _ => None,
+    }
+}
+
+#[allow(dead_code)]
+pub fn get_reloc_type_name(machine: u16, r_type: u32) -> Option<&'static str> {
+    let name = match machine {
+        EM_386 => R_386_NAMES.get(r_type as usize).cloned(),
+        EM_X86_64 => R_X86_64_NAMES.get(r_type as usize).cloned(),
+        EM_AARCH64 => R_AARCH64_NAMES.iter()
+            .find(|&&(t, _)| t == r_type)
+            .map(|&(_, name)| name),
+        _ => None,
+    };
+    name.and_then(|name| if name.is_empty() { None } else { Some(name) })
+}
@@ -0,0 +1,563 @@
+use ::std::io::{self, Write};
+use super::elf_ehdr_machine::{get_machine_string, machine_short_name};
+use super::elf_ehdr_type::get_type_short_name;
+use super::elf_ei_class::ElfEiClass;
+use super::elf_ei_data::ElfEiData;
+use super::elf_json::{JsonSection, JsonSegment, JsonSymbol, get_json_string};
+use super::elf_target::TargetInfo;
+
+// The output formats --format picks from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    // The readelf-style tables, printed table by table as the file is read.
+    // This is the default.
+    Readelf,
+    // The records of the report as readelf-style tables (see TextReporter)
+    Text,
+    Json,
+    Csv,
+    Yaml,
+    Stable,
+    // Only for --needed
+    Dot,
+}
+
+#[allow(dead_code)]
+impl OutputFormat {
+    pub fn parse(value: &str) -> Option<OutputFormat> {
+        match value {
+            "readelf" => Some(OutputFormat::Readelf),
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            "csv" => Some(OutputFormat::Csv),
+            "yaml" => Some(OutputFormat::Yaml),
+            "stable" => Some(OutputFormat::Stable),
+            "dot" => Some(OutputFormat::Dot),
+            _ => None,
+        }
+    }
+    // Whether the headers and tables go into a report (see Reporter)
+    pub fn is_structured(&self) -> bool {
+        match *self {
+            OutputFormat::Text | OutputFormat::Json | OutputFormat::Csv
+                | OutputFormat::Yaml | OutputFormat::Stable => true,
+            OutputFormat::Readelf | OutputFormat::Dot => false,
+        }
+    }
+}
+
+// Renders a report (see JsonReport::report) in one output format. The
+// methods are called in order: the header, the tables that were asked for,
+// the sections and finish. A new format is one more implementation.
+//
+// The default output is not a Reporter: its tables follow readelf column by
+// column, with annotations that take the headers of the file's own class
+// rather than the class-independent records reporters are given.
+// TextReporter prints the records in the same style.
+pub trait Reporter {
+    fn header<W: Write>(
+        &mut self, out: &mut W, target: &TargetInfo, file_type: u16, entry: u64)
+        -> io::Result<()>;
+    fn segment_table<W: Write>(&mut self, out: &mut W, segments: &[JsonSegment])
+        -> io::Result<()>;
+    fn symbol_table<W: Write>(&mut self, out: &mut W, symbols: &[JsonSymbol])
+        -> io::Result<()>;
+    // With `details`, the sections carry every field of the header
+    fn section_table<W: Write>(&mut self, out: &mut W, sections: &[JsonSection], details: bool)
+        -> io::Result<()>;
+    fn finish<W: Write>(&mut self, _out: &mut W) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn get_data_name(target: &TargetInfo) -> String {
+    match target.get_data() {
+        ElfEiData::ELFDATA2LSB => "little".to_string(),
+        ElfEiData::ELFDATA2MSB => "big".to_string(),
+        data => format!("{}", data.get_value()),
+    }
+}
+
+fn get_os_abi_name(target: &TargetInfo) -> String {
+    let os_abi = target.get_os_abi();
+    match os_abi.get_short_name() {
+        Some(name) => name.to_string(),
+        None => os_abi.get_description(target.get_machine()),
+    }
+}
+
+fn get_machine_name(target: &TargetInfo) -> String {
+    let machine = target.get_machine();
+    match machine_short_name(machine) {
+        Some(name) => name.to_string(),
+        None => get_machine_string(machine),
+    }
+}
+
+// One object per file, with the keys NOTES.md lists.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn header<W: Write>(
+        &mut self, out: &mut W, target: &TargetInfo, file_type: u16, entry: u64)
+        -> io::Result<()>
+    {
+        try!(writeln!(out, "{{"));
+        try!(writeln!(out, "  \"class\": {},", get_json_string(&format!("{}", target.get_class()))));
+        try!(writeln!(out, "  \"data\": {},", get_json_string(&get_data_name(target))));
+        try!(writeln!(out, "  \"os_abi\": {},", get_json_string(&get_os_abi_name(target))));
+        try!(writeln!(out, "  \"type\": {},", get_json_string(get_type_short_name(file_type))));
+        try!(writeln!(out, "  \"machine\": {},", get_json_string(&get_machine_name(target))));
+        writeln!(out, "  \"entry\": {},", entry)
+    }
+
+    fn segment_table<W: Write>(&mut self, out: &mut W, segments: &[JsonSegment])
+        -> io::Result<()>
+    {
+        if segments.is_empty() {
+            return writeln!(out, "  \"segments\": [],");
+        }
+        try!(writeln!(out, "  \"segments\": ["));
+        for (i, s) in segments.iter().enumerate() {
+            try!(writeln!(
+                out,
+                "    {{\"index\": {}, \"type\": {}, \"flags\": {}, \"offset\": {}, \"vaddr\": {}, \"paddr\": {}, \"filesz\": {}, \"memsz\": {}, \"align\": {}}}{}",
+                s.index, get_json_string(&s.segment_type), get_json_string(&s.flags),
+                s.offset, s.vaddr, s.paddr, s.filesz, s.memsz, s.align,
+                if i + 1 == segments.len() { "" } else { "," }));
+        }
+        writeln!(out, "  ],")
+    }
+
+    fn symbol_table<W: Write>(&mut self, out: &mut W, symbols: &[JsonSymbol])
+        -> io::Result<()>
+    {
+        if symbols.is_empty() {
+            return writeln!(out, "  \"symbols\": [],");
+        }
+        try!(writeln!(out, "  \"symbols\": ["));
+        for (i, s) in symbols.iter().enumerate() {
+            try!(writeln!(
+                out,
+                "    {{\"table\": {}, \"index\": {}, \"name\": {}, \"value\": {}, \"size\": {}, \"type\": {}, \"bind\": {}, \"visibility\": {}, \"section\": {}}}{}",
+                get_json_string(&s.table), s.index, get_json_string(&s.name), s.value, s.size,
+                get_json_string(&s.symbol_type), get_json_string(&s.bind),
+                get_json_string(&s.visibility), get_json_string(&s.section),
+                if i + 1 == symbols.len() { "" } else { "," }));
+        }
+        writeln!(out, "  ],")
+    }
+
+    fn section_table<W: Write>(&mut self, out: &mut W, sections: &[JsonSection], details: bool)
+        -> io::Result<()>
+    {
+        if sections.is_empty() {
+            return writeln!(out, "  \"sections\": []");
+        }
+        try!(writeln!(out, "  \"sections\": ["));
+        for (i, s) in sections.iter().enumerate() {
+            let details = if details {
+                format!(
+                    ", \"link\": {}, \"info\": {}, \"addralign\": {}, \"entsize\": {}",
+                    s.link, s.info, s.addralign, s.entsize)
+            } else {
+                String::new()
+            };
+            try!(writeln!(
+                out,
+                "    {{\"index\": {}, \"name\": {}, \"type\": {}, \"addr\": {}, \"offset\": {}, \"size\": {}, \"flags\": {}{}}}{}",
+                s.index, get_json_string(&s.name), get_json_string(&s.section_type),
+                s.addr, s.offset, s.size, get_json_string(&s.flags), details,
+                if i + 1 == sections.len() { "" } else { "," }));
+        }
+        writeln!(out, "  ]")
+    }
+
+    fn finish<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "}}")
+    }
+}
+
+// Quotes a CSV field if it has to be (RFC 4180).
+fn get_csv_field(s: &str) -> String {
+    if s.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// One table after another, each with a row of column names and separated
+// by an empty line. The columns are the JSON keys, numbers are decimal.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvReporter {
+    tables: usize,
+}
+
+#[allow(dead_code)]
+impl CsvReporter {
+    pub fn new() -> CsvReporter {
+        CsvReporter { tables: 0 }
+    }
+
+    fn start_table<W: Write>(&mut self, out: &mut W, columns: &str) -> io::Result<()> {
+        if self.tables != 0 {
+            try!(writeln!(out, ""));
+        }
+        self.tables += 1;
+        writeln!(out, "{}", columns)
+    }
+}
+
+impl Reporter for CsvReporter {
+    fn header<W: Write>(
+        &mut self, out: &mut W, target: &TargetInfo, file_type: u16, entry: u64)
+        -> io::Result<()>
+    {
+        try!(self.start_table(out, "class,data,os_abi,type,machine,entry"));
+        writeln!(
+            out, "{},{},{},{},{},{}",
+            get_csv_field(&format!("{}", target.get_class())),
+            get_csv_field(&get_data_name(target)),
+            get_csv_field(&get_os_abi_name(target)),
+            get_csv_field(get_type_short_name(file_type)),
+            get_csv_field(&get_machine_name(target)),
+            entry)
+    }
+
+    fn segment_table<W: Write>(&mut self, out: &mut W, segments: &[JsonSegment])
+        -> io::Result<()>
+    {
+        try!(self.start_table(out, "index,type,flags,offset,vaddr,paddr,filesz,memsz,align"));
+        for s in segments {
+            try!(writeln!(
+                out, "{},{},{},{},{},{},{},{},{}",
+                s.index, get_csv_field(&s.segment_type), get_csv_field(&s.flags),
+                s.offset, s.vaddr, s.paddr, s.filesz, s.memsz, s.align));
+        }
+        Ok(())
+    }
+
+    fn symbol_table<W: Write>(&mut self, out: &mut W, symbols: &[JsonSymbol])
+        -> io::Result<()>
+    {
+        try!(self.start_table(out, "table,index,name,value,size,type,bind,visibility,section"));
+        for s in symbols {
+            try!(writeln!(
+                out, "{},{},{},{},{},{},{},{},{}",
+                get_csv_field(&s.table), s.index, get_csv_field(&s.name), s.value, s.size,
+                get_csv_field(&s.symbol_type), get_csv_field(&s.bind),
+                get_csv_field(&s.visibility), get_csv_field(&s.section)));
+        }
+        Ok(())
+    }
+
+    fn section_table<W: Write>(&mut self, out: &mut W, sections: &[JsonSection], details: bool)
+        -> io::Result<()>
+    {
+        if details {
+            try!(self.start_table(
+                out, "index,name,type,addr,offset,size,flags,link,info,addralign,entsize"));
+        } else {
+            try!(self.start_table(out, "index,name,type,addr,offset,size,flags"));
+        }
+        for s in sections {
+            try!(write!(
+                out, "{},{},{},{},{},{},{}",
+                s.index, get_csv_field(&s.name), get_csv_field(&s.section_type),
+                s.addr, s.offset, s.size, get_csv_field(&s.flags)));
+            if details {
+                try!(write!(out, ",{},{},{},{}", s.link, s.info, s.addralign, s.entsize));
+            }
+            try!(writeln!(out, ""));
+        }
+        Ok(())
+    }
+}
+
+// One document per file, with the keys of the JSON report. Strings are
+// written as JSON strings, which YAML reads as double-quoted scalars.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YamlReporter;
+
+impl YamlReporter {
+    // The start of a table: an empty one is a flow sequence
+    fn start_table<W: Write>(out: &mut W, key: &str, empty: bool) -> io::Result<()> {
+        if empty {
+            writeln!(out, "{}: []", key)
+        } else {
+            writeln!(out, "{}:", key)
+        }
+    }
+}
+
+impl Reporter for YamlReporter {
+    fn header<W: Write>(
+        &mut self, out: &mut W, target: &TargetInfo, file_type: u16, entry: u64)
+        -> io::Result<()>
+    {
+        try!(writeln!(out, "---"));
+        try!(writeln!(out, "class: {}", get_json_string(&format!("{}", target.get_class()))));
+        try!(writeln!(out, "data: {}", get_json_string(&get_data_name(target))));
+        try!(writeln!(out, "os_abi: {}", get_json_string(&get_os_abi_name(target))));
+        try!(writeln!(out, "type: {}", get_json_string(get_type_short_name(file_type))));
+        try!(writeln!(out, "machine: {}", get_json_string(&get_machine_name(target))));
+        writeln!(out, "entry: {}", entry)
+    }
+
+    fn segment_table<W: Write>(&mut self, out: &mut W, segments: &[JsonSegment])
+        -> io::Result<()>
+    {
+        try!(YamlReporter::start_table(out, "segments", segments.is_empty()));
+        for s in segments {
+            try!(writeln!(out, "  - index: {}", s.index));
+            try!(writeln!(out, "    type: {}", get_json_string(&s.segment_type)));
+            try!(writeln!(out, "    flags: {}", get_json_string(&s.flags)));
+            try!(writeln!(out, "    offset: {}", s.offset));
+            try!(writeln!(out, "    vaddr: {}", s.vaddr));
+            try!(writeln!(out, "    paddr: {}", s.paddr));
+            try!(writeln!(out, "    filesz: {}", s.filesz));
+            try!(writeln!(out, "    memsz: {}", s.memsz));
+            try!(writeln!(out, "    align: {}", s.align));
+        }
+        Ok(())
+    }
+
+    fn symbol_table<W: Write>(&mut self, out: &mut W, symbols: &[JsonSymbol])
+        -> io::Result<()>
+    {
+        try!(YamlReporter::start_table(out, "symbols", symbols.is_empty()));
+        for s in symbols {
+            try!(writeln!(out, "  - table: {}", get_json_string(&s.table)));
+            try!(writeln!(out, "    index: {}", s.index));
+            try!(writeln!(out, "    name: {}", get_json_string(&s.name)));
+            try!(writeln!(out, "    value: {}", s.value));
+            try!(writeln!(out, "    size: {}", s.size));
+            try!(writeln!(out, "    type: {}", get_json_string(&s.symbol_type)));
+            try!(writeln!(out, "    bind: {}", get_json_string(&s.bind)));
+            try!(writeln!(out, "    visibility: {}", get_json_string(&s.visibility)));
+            try!(writeln!(out, "    section: {}", get_json_string(&s.section)));
+        }
+        Ok(())
+    }
+
+    fn section_table<W: Write>(&mut self, out: &mut W, sections: &[JsonSection], details: bool)
+        -> io::Result<()>
+    {
+        try!(YamlReporter::start_table(out, "sections", sections.is_empty()));
+        for s in sections {
+            try!(writeln!(out, "  - index: {}", s.index));
+            try!(writeln!(out, "    name: {}", get_json_string(&s.name)));
+            try!(writeln!(out, "    type: {}", get_json_string(&s.section_type)));
+            try!(writeln!(out, "    addr: {}", s.addr));
+            try!(writeln!(out, "    offset: {}", s.offset));
+            try!(writeln!(out, "    size: {}", s.size));
+            try!(writeln!(out, "    flags: {}", get_json_string(&s.flags)));
+            if details {
+                try!(writeln!(out, "    link: {}", s.link));
+                try!(writeln!(out, "    info: {}", s.info));
+                try!(writeln!(out, "    addralign: {}", s.addralign));
+                try!(writeln!(out, "    entsize: {}", s.entsize));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Escapes what would break a value across lines.
+fn get_stable_value(s: &str) -> String {
+    let mut r = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => r.push_str("\\\\"),
+            '\n' => r.push_str("\\n"),
+            '\r' => r.push_str("\\r"),
+            '\t' => r.push_str("\\t"),
+            c if (c as u32) < 0x20 => r.push_str(&format!("\\x{:02x}", c as u32)),
+            c => r.push(c),
+        }
+    }
+    r
+}
+
+// One `key: value` per line and nothing else, for diffing the output for
+// two builds. Every line names the record it is from, so a changed field
+// shows up on its own and in context. Addresses and offsets are hex, the
+// rest decimal; values are not aligned or quoted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StableReporter;
+
+impl Reporter for StableReporter {
+    fn header<W: Write>(
+        &mut self, out: &mut W, target: &TargetInfo, file_type: u16, entry: u64)
+        -> io::Result<()>
+    {
+        try!(writeln!(out, "class: {}", target.get_class()));
+        try!(writeln!(out, "data: {}", get_data_name(target)));
+        try!(writeln!(out, "os_abi: {}", get_stable_value(&get_os_abi_name(target))));
+        try!(writeln!(out, "type: {}", get_type_short_name(file_type)));
+        try!(writeln!(out, "machine: {}", get_stable_value(&get_machine_name(target))));
+        writeln!(out, "entry: {:#x}", entry)
+    }
+
+    fn segment_table<W: Write>(&mut self, out: &mut W, segments: &[JsonSegment])
+        -> io::Result<()>
+    {
+        try!(writeln!(out, "segments: {}", segments.len()));
+        for s in segments {
+            let key = format!("segment[{}]", s.index);
+            try!(writeln!(out, "{}.type: {}", key, get_stable_value(&s.segment_type)));
+            try!(writeln!(out, "{}.flags: {}", key, s.flags));
+            try!(writeln!(out, "{}.offset: {:#x}", key, s.offset));
+            try!(writeln!(out, "{}.vaddr: {:#x}", key, s.vaddr));
+            try!(writeln!(out, "{}.paddr: {:#x}", key, s.paddr));
+            try!(writeln!(out, "{}.filesz: {}", key, s.filesz));
+            try!(writeln!(out, "{}.memsz: {}", key, s.memsz));
+            try!(writeln!(out, "{}.align: {}", key, s.align));
+        }
+        Ok(())
+    }
+
+    fn symbol_table<W: Write>(&mut self, out: &mut W, symbols: &[JsonSymbol])
+        -> io::Result<()>
+    {
+        try!(writeln!(out, "symbols: {}", symbols.len()));
+        for s in symbols {
+            let key = format!("symbol[{}:{}]", get_stable_value(&s.table), s.index);
+            try!(writeln!(out, "{}.name: {}", key, get_stable_value(&s.name)));
+            try!(writeln!(out, "{}.value: {:#x}", key, s.value));
+            try!(writeln!(out, "{}.size: {}", key, s.size));
+            try!(writeln!(out, "{}.type: {}", key, get_stable_value(&s.symbol_type)));
+            try!(writeln!(out, "{}.bind: {}", key, get_stable_value(&s.bind)));
+            try!(writeln!(out, "{}.visibility: {}", key, get_stable_value(&s.visibility)));
+            try!(writeln!(out, "{}.section: {}", key, get_stable_value(&s.section)));
+        }
+        Ok(())
+    }
+
+    fn section_table<W: Write>(&mut self, out: &mut W, sections: &[JsonSection], details: bool)
+        -> io::Result<()>
+    {
+        try!(writeln!(out, "sections: {}", sections.len()));
+        for s in sections {
+            let key = format!("section[{}]", s.index);
+            try!(writeln!(out, "{}.name: {}", key, get_stable_value(&s.name)));
+            try!(writeln!(out, "{}.type: {}", key, get_stable_value(&s.section_type)));
+            try!(writeln!(out, "{}.addr: {:#x}", key, s.addr));
+            try!(writeln!(out, "{}.offset: {:#x}", key, s.offset));
+            try!(writeln!(out, "{}.size: {}", key, s.size));
+            try!(writeln!(out, "{}.flags: {}", key, s.flags));
+            if details {
+                try!(writeln!(out, "{}.link: {}", key, s.link));
+                try!(writeln!(out, "{}.info: {}", key, s.info));
+                try!(writeln!(out, "{}.addralign: {}", key, s.addralign));
+                try!(writeln!(out, "{}.entsize: {}", key, s.entsize));
+            }
+        }
+        Ok(())
+    }
+}
+
+// The records as readelf-style tables: the same columns as -h, -l, -s and
+// -S print, less what the records leave out (the legend, versions and
+// other annotations). Addresses are as wide as the file's class has them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextReporter {
+    width: usize,
+}
+
+#[allow(dead_code)]
+impl TextReporter {
+    pub fn new() -> TextReporter {
+        TextReporter { width: 16 }
+    }
+}
+
+impl Reporter for TextReporter {
+    fn header<W: Write>(
+        &mut self, out: &mut W, target: &TargetInfo, file_type: u16, entry: u64)
+        -> io::Result<()>
+    {
+        self.width = match target.get_class() {
+            ElfEiClass::ELFCLASS32 => 8,
+            _ => 16,
+        };
+        try!(heading!(out, "ELF Header:"));
+        try!(writeln!(out, "  Class:   {}", target.get_class()));
+        try!(writeln!(out, "  Data:    {}", get_data_name(target)));
+        try!(writeln!(out, "  OS/ABI:  {}", get_os_abi_name(target)));
+        try!(writeln!(out, "  Type:    {}", get_type_short_name(file_type)));
+        try!(writeln!(out, "  Machine: {}", get_machine_name(target)));
+        writeln!(out, "  Entry:   {:#x}", entry)
+    }
+
+    fn segment_table<W: Write>(&mut self, out: &mut W, segments: &[JsonSegment])
+        -> io::Result<()>
+    {
+        let w = self.width;
+        try!(heading!(out, ""));
+        try!(heading!(out, "Program Headers:"));
+        try!(heading!(
+            out,
+            "  Type           Offset   {:<w$} {:<w$} FileSiz  MemSiz   Flg Align",
+            "VirtAddr", "PhysAddr", w = w + 2));
+        for s in segments {
+            try!(writeln!(
+                out,
+                "  {:<14} {:#08x} {:#0w$x} {:#0w$x} {:#08x} {:#08x} {:<3} {:#x}",
+                s.segment_type, s.offset, s.vaddr, s.paddr, s.filesz, s.memsz,
+                s.flags, s.align, w = w + 2));
+        }
+        Ok(())
+    }
+
+    fn symbol_table<W: Write>(&mut self, out: &mut W, symbols: &[JsonSymbol])
+        -> io::Result<()>
+    {
+        let w = self.width;
+        let mut rest = symbols;
+        while let Some(first) = rest.first() {
+            let count = rest.iter().take_while(|s| s.table == first.table).count();
+            try!(heading!(out, ""));
+            try!(heading!(
+                out, "Symbol table '{}' contains {} entries:", first.table, count));
+            try!(heading!(
+                out, "   Num:    Value{:w$} Size Type    Bind   Vis      Ndx Name",
+                "", w = w - 7));
+            for s in &rest[..count] {
+                try!(writeln!(
+                    out,
+                    "{:6}: {:0w$x} {:5} {:<7} {:<6} {:<7} {:>4} {}",
+                    s.index, s.value, s.size, s.symbol_type, s.bind, s.visibility,
+                    s.section, s.name, w = w));
+            }
+            rest = &rest[count..];
+        }
+        Ok(())
+    }
+
+    fn section_table<W: Write>(&mut self, out: &mut W, sections: &[JsonSection], details: bool)
+        -> io::Result<()>
+    {
+        let w = self.width;
+        try!(heading!(out, ""));
+        try!(heading!(out, "Section Headers:"));
+        try!(heading!(
+            out, "  [Nr] Name              Type            {:<w$} Off    Size   {}",
+            if w == 8 { "Addr" } else { "Address" }, if details { "ES Flg Lk Inf Al" } else { "Flg" }, w = w));
+        for s in sections {
+            try!(write!(
+                out, "  [{:2}] {:<17} {:<15} {:0w$x} {:06x} {:06x} ",
+                s.index, s.name, s.section_type, s.addr, s.offset, s.size, w = w));
+            if details {
+                try!(writeln!(
+                    out, "{:02x} {:>3} {:2} {:3} {:2}",
+                    s.entsize, s.flags, s.link, s.info, s.addralign));
+            } else {
+                try!(writeln!(out, "{:>3}", s.flags));
+            }
+        }
+        Ok(())
+    }
+}
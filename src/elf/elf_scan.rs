@@ -0,0 +1,50 @@
+use super::elf_ei_class::ElfEiClass;
+use super::elf_ei_data::ElfEiData;
+use super::elf_ident_named::{EI_CLASS, EI_DATA};
+use super::helpers::{get_u16, get_u32};
+
+const ELF_MAGIC: &'static [u8] = b"\x7fELF";
+const EI_VERSION: usize = 6;
+const EV_CURRENT: u8 = 1;
+// Where e_type and e_version are, the same in both classes
+const E_TYPE: usize = 16;
+const E_VERSION: usize = 20;
+const ET_CORE: u16 = 4;
+const ET_LOOS: u16 = 0xfe00;
+
+// The offsets in `data` at which a plausible ELF header starts, for --scan.
+// The magic turns up by chance in compressed or random data, so the class,
+// data encoding, both versions and the file type have to make sense too,
+// and the whole header has to be there.
+#[allow(dead_code)]
+pub fn find_elf_headers(data: &[u8]) -> Vec<u64> {
+    let mut r = Vec::new();
+    let mut start = 0;
+    while let Some(i) = data[start..].windows(ELF_MAGIC.len()).position(|w| w == ELF_MAGIC) {
+        let offset = start + i;
+        if is_plausible_header(&data[offset..]) {
+            r.push(offset as u64);
+        }
+        start = offset + 1;
+    }
+    r
+}
+
+fn is_plausible_header(data: &[u8]) -> bool {
+    if data.len() <= EI_VERSION {
+        return false;
+    }
+    let class = ElfEiClass::new(data[EI_CLASS]);
+    let encoding = ElfEiData::new(data[EI_DATA]);
+    if !class.is_valid() || !encoding.is_valid() || data[EI_VERSION] != EV_CURRENT {
+        return false;
+    }
+    let size = if class == ElfEiClass::ELFCLASS64 { 64 } else { 52 };
+    let e = encoding.get_endianness();
+    let e_type = get_u16(data, E_TYPE, &e);
+    let e_version = get_u32(data, E_VERSION, &e);
+    match (e_type, e_version) {
+        (Some(e_type), Some(1)) => data.len() >= size && (e_type <= ET_CORE || e_type >= ET_LOOS),
+        _ => false,
+    }
+}
@@ -0,0 +1,21 @@
+// The order the section table is printed in (--sort).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionOrder {
+    Index,
+    Address,
+    Size,
+    Name,
+}
+
+#[allow(dead_code)]
+impl SectionOrder {
+    pub fn parse(value: &str) -> Option<SectionOrder> {
+        match value {
+            "index" => Some(SectionOrder::Index),
+            "addr" => Some(SectionOrder::Address),
+            "size" => Some(SectionOrder::Size),
+            "name" => Some(SectionOrder::Name),
+            _ => None,
+        }
+    }
+}
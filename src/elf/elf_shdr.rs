@@ -0,0 +1,1508 @@
+#[macro_export]
+macro_rules! elf_shdr {
+    ($word:ty, $xword:ty, $addr:ty, $off:ty) => {
+        use ::to_host::Endianness;
+        use ::to_host::to_host_copy::ToHostCopy;
+        use ::to_host::ToHostCopyStruct;
+        use ::std::io::{self, Read, Seek, SeekFrom, Write};
+        use super::super::helpers::{read_bytes, get_str, get_entropy, get_strings, EntryLimit};
+        use super::super::helpers::{print_hex_lines, get_alignment_exponent, get_alignment_string};
+        use super::super::elf_shdr_type::ElfShdrType;
+        use super::super::elf_shdr_flags::{ElfShdrFlags, SHF_ALLOC, SHF_COMPRESSED, SHF_TLS};
+        use super::super::elf_shdr_flags::SHF_INFO_LINK;
+        use super::super::elf_debuglink::{DebugLink, parse_debuglink, parse_debugaltlink};
+        use super::super::elf_dwarf::{parse_unit_headers, print_unit_headers};
+        use super::super::elf_dwarf::{parse_line_headers, print_line_headers};
+        use super::super::elf_note::{parse_notes, print_notes};
+        use super::super::elf_eh_frame_hdr::{EhFrameHdr, parse_eh_frame_hdr};
+        use super::super::elf_arm_exidx::{parse_exidx, print_exidx};
+        use super::super::elf_counts::StructureCounts;
+        use super::super::elf_layout::{LayoutEntry, print_layout};
+        use super::super::elf_strtab::StringTable;
+        use super::super::elf_error::ElfError;
+        use super::super::elf_json::JsonSection;
+        use super::super::elf_section_order::SectionOrder;
+        use super::primitive::*;
+
+        // Special values of e_shstrndx
+        const SHN_UNDEF: u16 = 0;
+        const SHN_XINDEX: u16 = 0xffff;
+
+        #[derive(Debug, Clone, Copy)]
+        #[repr(C)]
+        pub struct Elf_Shdr {
+            sh_name: $word,
+            sh_type: $word,
+            sh_flags: $xword,
+            sh_addr: $addr,
+            sh_offset: $off,
+            sh_size: $xword,
+            sh_link: $word,
+            sh_info: $word,
+            sh_addralign: $xword,
+            sh_entsize: $xword,
+        }
+
+        impl Elf_Shdr {
+            #[allow(dead_code)]
+            pub fn get_name(&self) -> $word {
+                self.sh_name
+            }
+            #[allow(dead_code)]
+            pub fn get_type(&self) -> $word {
+                self.sh_type
+            }
+            #[allow(dead_code)]
+            pub fn get_flags(&self) -> $xword {
+                self.sh_flags
+            }
+            #[allow(dead_code)]
+            pub fn get_addr(&self) -> $addr {
+                self.sh_addr
+            }
+            #[allow(dead_code)]
+            pub fn get_offset(&self) -> $off {
+                self.sh_offset
+            }
+            #[allow(dead_code)]
+            pub fn get_size(&self) -> $xword {
+                self.sh_size
+            }
+            #[allow(dead_code)]
+            pub fn get_link(&self) -> $word {
+                self.sh_link
+            }
+            #[allow(dead_code)]
+            pub fn get_info(&self) -> $word {
+                self.sh_info
+            }
+            #[allow(dead_code)]
+            pub fn get_addralign(&self) -> $xword {
+                self.sh_addralign
+            }
+            #[allow(dead_code)]
+            pub fn get_entsize(&self) -> $xword {
+                self.sh_entsize
+            }
+
+            // Number of entries in a table section, sh_size / sh_entsize.
+            // Some producers leave sh_entsize at 0, in which case the size of
+            // the entry structure, `known_size`, is used instead. Without
+            // either there is no way to tell the entries apart.
+            #[allow(dead_code)]
+            pub fn get_entry_count(&self, known_size: usize) -> Result<u64, ElfError> {
+                let entsize = match self.sh_entsize as u64 {
+                    0 => known_size as u64,
+                    entsize => entsize,
+                };
+                if entsize == 0 {
+                    return Err(ElfError::UnexpectedEntrySize(0));
+                }
+                Ok(self.sh_size as u64 / entsize)
+            }
+        }
+
+        impl ToHostCopyStruct for Elf_Shdr {
+            fn to_host_copy(&self, endianness: &Endianness) -> Self {
+                let e = endianness;
+                Elf_Shdr {
+                    sh_name: self.sh_name.to_host_copy(e),
+                    sh_type: self.sh_type.to_host_copy(e),
+                    sh_flags: self.sh_flags.to_host_copy(e),
+                    sh_addr: self.sh_addr.to_host_copy(e),
+                    sh_offset: self.sh_offset.to_host_copy(e),
+                    sh_size: self.sh_size.to_host_copy(e),
+                    sh_link: self.sh_link.to_host_copy(e),
+                    sh_info: self.sh_info.to_host_copy(e),
+                    sh_addralign: self.sh_addralign.to_host_copy(e),
+                    sh_entsize: self.sh_entsize.to_host_copy(e),
+                }
+            }
+        }
+
+        // A section header together with the section header string table,
+        // so that its name can be had without the string table plumbing.
+        // The header is expected to be in host byte order.
+        #[derive(Debug, Clone, Copy)]
+        pub struct Section<'a> {
+            index: usize,
+            shdr: &'a Elf_Shdr,
+            shstrtab: &'a [u8],
+        }
+
+        #[allow(dead_code)]
+        impl<'a> Section<'a> {
+            pub fn new(index: usize, shdr: &'a Elf_Shdr, shstrtab: &'a [u8]) -> Section<'a> {
+                Section { index: index, shdr: shdr, shstrtab: shstrtab }
+            }
+            pub fn index(&self) -> usize {
+                self.index
+            }
+            pub fn header(&self) -> &'a Elf_Shdr {
+                self.shdr
+            }
+            pub fn name(&self) -> &'a str {
+                get_str(self.shstrtab, self.shdr.get_name() as u64)
+            }
+            pub fn section_type(&self) -> ElfShdrType {
+                ElfShdrType::new(self.shdr.get_type())
+            }
+            pub fn flags(&self) -> ElfShdrFlags {
+                ElfShdrFlags::new(self.shdr.get_flags() as u64)
+            }
+            // The bytes of the section, empty for SHT_NOBITS.
+            pub fn data<R: Read + Seek>(&self, reader: &mut R) -> Vec<u8> {
+                if self.section_type() == ElfShdrType::SHT_NOBITS {
+                    return Vec::new();
+                }
+                read_section_data(self.shdr, reader).unwrap_or(Vec::new())
+            }
+        }
+
+        // The names of the sections, resolved through the string table on
+        // first use and kept, for code which looks them up over and over.
+        // None for a name which isn't in the table.
+        pub struct SectionNames<'a> {
+            shdrs: &'a [Elf_Shdr],
+            shstrtab: &'a [u8],
+            names: ::std::cell::RefCell<Option<Vec<Option<&'a str>>>>,
+        }
+
+        #[allow(dead_code)]
+        impl<'a> SectionNames<'a> {
+            pub fn new(shdrs: &'a [Elf_Shdr], shstrtab: &'a [u8]) -> SectionNames<'a> {
+                SectionNames {
+                    shdrs: shdrs,
+                    shstrtab: shstrtab,
+                    names: ::std::cell::RefCell::new(None),
+                }
+            }
+            pub fn get(&self, index: usize) -> Option<&'a str> {
+                let mut names = self.names.borrow_mut();
+                if names.is_none() {
+                    let shstrtab = self.shstrtab;
+                    *names = Some(self.shdrs.iter()
+                        .map(|shdr| match shdr.get_name() as u64 {
+                            offset if offset < shstrtab.len() as u64 =>
+                                Some(get_str(shstrtab, offset)),
+                            _ => None,
+                        })
+                        .collect());
+                }
+                names.as_ref().and_then(|names| names.get(index).cloned().unwrap_or(None))
+            }
+            // Like get_str: "<corrupt>" for a name which isn't in the table.
+            pub fn get_or_corrupt(&self, index: usize) -> &'a str {
+                self.get(index).unwrap_or("<corrupt>")
+            }
+        }
+
+        // Wraps every section header, in table order.
+        #[allow(dead_code)]
+        pub fn get_sections<'a>(
+            shdrs: &'a [Elf_Shdr], shstrtab: &'a [u8]) -> Vec<Section<'a>>
+        {
+            shdrs.iter().enumerate()
+                .map(|(i, shdr)| Section::new(i, shdr, shstrtab))
+                .collect()
+        }
+
+        // The section whose bytes in the file include `offset`. Sections that
+        // take no space in the file (SHT_NULL, SHT_NOBITS or empty ones) hold
+        // no offset, and of overlapping sections the first in the table wins.
+        #[allow(dead_code)]
+        pub fn get_section_at_offset<'a>(
+            shdrs: &'a [Elf_Shdr], shstrtab: &'a [u8], offset: u64) -> Option<Section<'a>>
+        {
+            get_sections(shdrs, shstrtab).into_iter().find(|s| {
+                let start = s.header().get_offset() as u64;
+                s.section_type() != ElfShdrType::SHT_NULL
+                    && s.section_type() != ElfShdrType::SHT_NOBITS
+                    && offset >= start
+                    && offset - start < s.header().get_size() as u64
+            })
+        }
+
+        // The index of the section get_section_at_offset would find for each
+        // offset, worked out once for the whole table, for code which looks
+        // up many offsets. The headers are expected in host byte order.
+        pub struct SectionsByOffset {
+            // Where each run of bytes held by the same section (or by none)
+            // starts, sorted by offset
+            runs: Vec<(u64, Option<usize>)>,
+        }
+
+        #[allow(dead_code)]
+        impl SectionsByOffset {
+            pub fn new(shdrs: &[Elf_Shdr]) -> SectionsByOffset {
+                // (offset, whether a section starts or ends there, its index)
+                let mut bounds = Vec::new();
+                for (index, shdr) in shdrs.iter().enumerate() {
+                    let shdr_type = ElfShdrType::new(shdr.get_type());
+                    if shdr_type == ElfShdrType::SHT_NULL
+                        || shdr_type == ElfShdrType::SHT_NOBITS
+                        || shdr.get_size() == 0 {
+                        continue;
+                    }
+                    let start = shdr.get_offset() as u64;
+                    bounds.push((start, true, index));
+                    bounds.push((start.saturating_add(shdr.get_size() as u64), false, index));
+                }
+                bounds.sort();
+
+                // Of the sections holding a run, the first in the table wins
+                let mut holding = ::std::collections::BTreeSet::new();
+                let mut runs = Vec::new();
+                let mut i = 0;
+                while i < bounds.len() {
+                    let offset = bounds[i].0;
+                    while i < bounds.len() && bounds[i].0 == offset {
+                        let (_, starts, index) = bounds[i];
+                        if starts {
+                            holding.insert(index);
+                        } else {
+                            holding.remove(&index);
+                        }
+                        i += 1;
+                    }
+                    runs.push((offset, holding.iter().next().cloned()));
+                }
+                SectionsByOffset { runs: runs }
+            }
+            pub fn get(&self, offset: u64) -> Option<usize> {
+                match self.runs.binary_search_by(|&(start, _)| start.cmp(&offset)) {
+                    Ok(i) => self.runs[i].1,
+                    Err(0) => None,
+                    Err(i) => self.runs[i - 1].1,
+                }
+            }
+        }
+
+        // Every SHT_STRTAB section with its index, in table order: the
+        // section name table, .strtab, .dynstr and any others. The headers
+        // are expected in file byte order.
+        #[allow(dead_code)]
+        pub fn get_string_tables<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R)
+            -> impl Iterator<Item = (usize, StringTable)>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let tables: Vec<(usize, StringTable)> = get_sections(&shdrs, &shstrtab).into_iter()
+                .filter(|s| s.section_type() == ElfShdrType::SHT_STRTAB)
+                .map(|s| (s.index(), StringTable::new(s.name().to_string(), s.data(reader))))
+                .collect();
+            tables.into_iter()
+        }
+
+        // The sections as they appear in the JSON report.
+        #[allow(dead_code)]
+        pub fn get_json_sections<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R)
+            -> Vec<JsonSection>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+
+            get_sections(&shdrs, &shstrtab).iter()
+                .map(|s| JsonSection {
+                    index: s.index(),
+                    name: s.name().to_string(),
+                    section_type: format!("{}", s.section_type()),
+                    addr: s.header().get_addr() as u64,
+                    offset: s.header().get_offset() as u64,
+                    size: s.header().get_size() as u64,
+                    flags: format!("{}", s.flags()),
+                    link: s.header().get_link() as u64,
+                    info: s.header().get_info() as u64,
+                    addralign: s.header().get_addralign() as u64,
+                    entsize: s.header().get_entsize() as u64,
+                })
+                .collect()
+        }
+
+        // The TLS template is the image every thread's TLS block starts as:
+        // the SHF_TLS sections, where .tdata is copied from the file and
+        // .tbss after it is zero-filled and takes no space in the file.
+        // Returns its address and its sizes in the file and in memory, which
+        // PT_TLS should match.
+        #[allow(dead_code)]
+        pub fn get_tls_template(shdrs: &[Elf_Shdr]) -> Option<(u64, u64, u64)> {
+            let tls: Vec<&Elf_Shdr> = shdrs.iter()
+                .filter(|s| ElfShdrFlags::new(s.get_flags() as u64).contains(SHF_TLS))
+                .collect();
+            let start = match tls.iter().map(|s| s.get_addr() as u64).min() {
+                Some(start) => start,
+                None => return None,
+            };
+            let end = |s: &&Elf_Shdr| (s.get_addr() as u64).saturating_add(s.get_size() as u64);
+            let file_end = tls.iter()
+                .filter(|s| ElfShdrType::new(s.get_type()) != ElfShdrType::SHT_NOBITS)
+                .map(&end)
+                .max()
+                .unwrap_or(start);
+            let mem_end = tls.iter().map(&end).max().unwrap_or(start);
+            Some((start, file_end - start, mem_end - start))
+        }
+
+        // Whether PT_TLS describes the TLS sections. Both are expected to be
+        // in host byte order.
+        #[allow(dead_code)]
+        pub fn get_tls_problems(
+            phdrs: &[super::elf_phdr::Elf_Phdr], shdrs: &[Elf_Shdr])
+            -> Vec<String>
+        {
+            use super::super::elf_phdr_type::ElfPhdrType;
+
+            let mut problems = Vec::new();
+            // Relocatable objects have no segments to check against
+            let (_, file_size, mem_size) = match get_tls_template(shdrs) {
+                Some(template) if !phdrs.is_empty() => template,
+                _ => return problems,
+            };
+            match phdrs.iter().find(|p| p.get_type() == ElfPhdrType::PT_TLS.get_value()) {
+                None => problems.push(
+                    "there are TLS sections, but no PT_TLS segment".to_string()),
+                Some(p) => if p.get_filesz() as u64 != file_size || p.get_memsz() as u64 != mem_size {
+                    problems.push(format!(
+                        "PT_TLS has {:#x} bytes in the file and {:#x} in memory, \
+                         but the TLS sections make up {:#x} and {:#x}",
+                        p.get_filesz(), p.get_memsz(), file_size, mem_size));
+                },
+            }
+            problems
+        }
+
+        // Whether .text is loaded into a writable segment, which a linker
+        // script mistake can do, leaving the code open to being overwritten.
+        // Both tables are expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn get_text_segment_problems<R: Read + Seek>(
+            phdrs: &[super::elf_phdr::Elf_Phdr], shdrs: &[Elf_Shdr], shstrndx: u16,
+            reader: &mut R)
+            -> Vec<String>
+        {
+            use super::super::elf_phdr_type::ElfPhdrType;
+            use super::super::elf_phdr_flags::PF_W;
+
+            let mut problems = Vec::new();
+            let shstrtab = read_shstrtab(shdrs, shstrndx, reader);
+            let text = match find_section_by_name(shdrs, &shstrtab, ".text") {
+                Some(s) if ElfShdrFlags::new(s.get_flags() as u64).contains(SHF_ALLOC) => s,
+                _ => return problems,
+            };
+            let address = text.get_addr() as u64;
+            let segment = phdrs.iter().enumerate().find(|&(_, p)| {
+                p.get_type() == ElfPhdrType::PT_LOAD.get_value()
+                    && address >= p.get_vaddr() as u64
+                    && address - (p.get_vaddr() as u64) < p.get_memsz() as u64
+            });
+            if let Some((i, p)) = segment {
+                if p.get_flags() & PF_W != 0 {
+                    problems.push(format!(
+                        ".text is in LOAD segment [{:02}], which is writable", i));
+                }
+            }
+            problems
+        }
+
+        // The exception frame header, found through PT_GNU_EH_FRAME or, when
+        // there are no program headers, the .eh_frame_hdr section. Both
+        // tables are expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn get_eh_frame_hdr<R: Read + Seek>(
+            phdrs: &[super::elf_phdr::Elf_Phdr], shdrs: &[Elf_Shdr], shstrndx: u16,
+            e: &Endianness, reader: &mut R)
+            -> Option<EhFrameHdr>
+        {
+            use super::super::elf_phdr_type::ElfPhdrType;
+
+            let segment = phdrs.iter()
+                .find(|p| p.get_type() == ElfPhdrType::PT_GNU_EH_FRAME.get_value());
+            let (address, offset, size) = match segment {
+                Some(p) => (p.get_vaddr() as u64, p.get_offset() as u64, p.get_filesz() as u64),
+                None => {
+                    let shstrtab = read_shstrtab(shdrs, shstrndx, reader);
+                    match find_section_by_name(shdrs, &shstrtab, ".eh_frame_hdr") {
+                        Some(s) => (s.get_addr() as u64, s.get_offset() as u64, s.get_size() as u64),
+                        None => return None,
+                    }
+                }
+            };
+            match read_bytes(reader, offset, size) {
+                Ok(data) => parse_eh_frame_hdr(&data, address, offset, e),
+                Err(_) => None,
+            }
+        }
+
+        // Whether PT_GNU_EH_FRAME and .eh_frame_hdr describe the same bytes,
+        // and whether the header points at .eh_frame. Both tables are
+        // expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn get_eh_frame_hdr_problems<R: Read + Seek>(
+            phdrs: &[super::elf_phdr::Elf_Phdr], shdrs: &[Elf_Shdr], shstrndx: u16,
+            e: &Endianness, reader: &mut R)
+            -> Vec<String>
+        {
+            use super::super::elf_phdr_type::ElfPhdrType;
+
+            let mut problems = Vec::new();
+            let shstrtab = read_shstrtab(shdrs, shstrndx, reader);
+            let section = find_section_by_name(shdrs, &shstrtab, ".eh_frame_hdr");
+            let segment = phdrs.iter()
+                .find(|p| p.get_type() == ElfPhdrType::PT_GNU_EH_FRAME.get_value());
+            match (segment, section) {
+                (Some(p), Some(s)) => {
+                    if p.get_vaddr() as u64 != s.get_addr() as u64
+                    || p.get_offset() as u64 != s.get_offset() as u64
+                    || p.get_filesz() as u64 != s.get_size() as u64 {
+                        problems.push(format!(
+                            "PT_GNU_EH_FRAME covers {:#x} bytes at {:#x} (offset {:#x}), \
+                             but .eh_frame_hdr has {:#x} bytes at {:#x} (offset {:#x})",
+                            p.get_filesz(), p.get_vaddr(), p.get_offset(),
+                            s.get_size(), s.get_addr(), s.get_offset()));
+                    }
+                }
+                (Some(_), None) if !shdrs.is_empty() => problems.push(
+                    "there is a PT_GNU_EH_FRAME segment, but no .eh_frame_hdr section".to_string()),
+                // Relocatable objects have no segments to check against
+                (None, Some(_)) if !phdrs.is_empty() => problems.push(
+                    "there is an .eh_frame_hdr section, but no PT_GNU_EH_FRAME segment".to_string()),
+                _ => {}
+            }
+
+            let eh_frame = find_section_by_name(shdrs, &shstrtab, ".eh_frame");
+            let hdr = get_eh_frame_hdr(phdrs, shdrs, shstrndx, e, reader);
+            if let (Some(eh_frame), Some(hdr)) = (eh_frame, hdr) {
+                match hdr.get_eh_frame_ptr() {
+                    Some(ptr) if ptr != eh_frame.get_addr() as u64 && !phdrs.is_empty() =>
+                        problems.push(format!(
+                            "the exception frame header points at {:#x}, but .eh_frame is at {:#x}",
+                            ptr, eh_frame.get_addr())),
+                    _ => {}
+                }
+            }
+            problems
+        }
+
+        // The path in PT_INTERP or .interp, up to the first NUL.
+        fn read_interpreter<R: Read + Seek>(reader: &mut R, offset: u64, size: u64) -> String {
+            let b = read_bytes(reader, offset, size).unwrap_or(Vec::new());
+            let end = b.iter().position(|&c| c == 0).unwrap_or(b.len());
+            String::from_utf8_lossy(&b[..end]).into_owned()
+        }
+
+        // The program interpreter, found through PT_INTERP or, when there
+        // are no program headers, the .interp section. Both tables are
+        // expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn get_interpreter<R: Read + Seek>(
+            phdrs: &[super::elf_phdr::Elf_Phdr], shdrs: &[Elf_Shdr], shstrndx: u16,
+            reader: &mut R)
+            -> Option<String>
+        {
+            use super::super::elf_phdr_type::ElfPhdrType;
+
+            let segment = phdrs.iter()
+                .find(|p| p.get_type() == ElfPhdrType::PT_INTERP.get_value());
+            let (offset, size) = match segment {
+                Some(p) => (p.get_offset() as u64, p.get_filesz() as u64),
+                None => {
+                    let shstrtab = read_shstrtab(shdrs, shstrndx, reader);
+                    match find_section_by_name(shdrs, &shstrtab, ".interp") {
+                        Some(s) if ElfShdrType::new(s.get_type()) != ElfShdrType::SHT_NOBITS =>
+                            (s.get_offset() as u64, s.get_size() as u64),
+                        _ => return None,
+                    }
+                }
+            };
+            Some(read_interpreter(reader, offset, size))
+        }
+
+        // Whether PT_INTERP and .interp name the same interpreter. The
+        // kernel only looks at the segment, so a section left pointing at
+        // the original path hides a replaced interpreter from tools that
+        // read sections. Both tables are expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn get_interpreter_problems<R: Read + Seek>(
+            phdrs: &[super::elf_phdr::Elf_Phdr], shdrs: &[Elf_Shdr], shstrndx: u16,
+            reader: &mut R)
+            -> Vec<String>
+        {
+            use super::super::elf_phdr_type::ElfPhdrType;
+
+            let mut problems = Vec::new();
+            let shstrtab = read_shstrtab(shdrs, shstrndx, reader);
+            let section = find_section_by_name(shdrs, &shstrtab, ".interp");
+            let segment = phdrs.iter()
+                .find(|p| p.get_type() == ElfPhdrType::PT_INTERP.get_value());
+            if let (Some(p), Some(s)) = (segment, section) {
+                let from_segment =
+                    read_interpreter(reader, p.get_offset() as u64, p.get_filesz() as u64);
+                let from_section =
+                    read_interpreter(reader, s.get_offset() as u64, s.get_size() as u64);
+                if from_segment != from_section {
+                    problems.push(format!(
+                        "PT_INTERP requests '{}', but .interp holds '{}'",
+                        from_segment, from_section));
+                }
+            }
+            problems
+        }
+
+        // Structural anomalies of the section table: tables whose entry size
+        // doesn't match their entries, sections overlapping in the file and
+        // sh_link fields pointing past the table.
+        #[allow(dead_code)]
+        pub fn get_section_problems<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R)
+            -> Vec<String>
+        {
+            use ::std::mem::size_of;
+            use super::elf_dyn::Elf_Dyn;
+            use super::elf_rel::{Elf_Rel, Elf_Rela};
+            use super::elf_sym::Elf_Sym;
+
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let mut problems = Vec::new();
+            let name = |shdr: &Elf_Shdr| get_str(&shstrtab, shdr.get_name() as u64).to_string();
+
+            if !shdrs.is_empty() && shstrndx != SHN_UNDEF && get_shstrndx(&shdrs, shstrndx).is_none() {
+                let index = if shstrndx == SHN_XINDEX {
+                    shdrs[0].get_link() as u64
+                } else {
+                    shstrndx as u64
+                };
+                problems.push(format!(
+                    "e_shstrndx points at section {}, but there are only {}; \
+                     sections are named by their index",
+                    index, shdrs.len()));
+            }
+
+            for shdr in &shdrs {
+                let sh_type = ElfShdrType::new(shdr.get_type());
+                let expected = match sh_type {
+                    ElfShdrType::SHT_SYMTAB | ElfShdrType::SHT_DYNSYM => size_of::<Elf_Sym>(),
+                    ElfShdrType::SHT_REL => size_of::<Elf_Rel>(),
+                    ElfShdrType::SHT_RELA => size_of::<Elf_Rela>(),
+                    ElfShdrType::SHT_DYNAMIC => size_of::<Elf_Dyn>(),
+                    ElfShdrType::SHT_RELR => size_of::<$addr>(),
+                    _ => 0,
+                };
+                if expected != 0 && shdr.get_entsize() as u64 != expected as u64 {
+                    problems.push(format!(
+                        "section {} has sh_entsize {}, but should have {}",
+                        name(shdr), shdr.get_entsize(), expected));
+                }
+
+                let uses_link = sh_type == ElfShdrType::SHT_SYMTAB
+                    || sh_type == ElfShdrType::SHT_DYNSYM
+                    || sh_type == ElfShdrType::SHT_REL
+                    || sh_type == ElfShdrType::SHT_RELA
+                    || sh_type == ElfShdrType::SHT_DYNAMIC
+                    || sh_type == ElfShdrType::SHT_HASH
+                    || sh_type == ElfShdrType::SHT_GNU_HASH
+                    || sh_type == ElfShdrType::SHT_GROUP
+                    || sh_type == ElfShdrType::SHT_SYMTAB_SHNDX
+                    || sh_type == ElfShdrType::SHT_GNU_VERSYM
+                    || sh_type == ElfShdrType::SHT_GNU_VERDEF
+                    || sh_type == ElfShdrType::SHT_GNU_VERNEED;
+                if uses_link && shdr.get_link() as usize >= shdrs.len() {
+                    problems.push(format!(
+                        "section {} links to section {}, but there are only {}",
+                        name(shdr), shdr.get_link(), shdrs.len()));
+                } else if sh_type == ElfShdrType::SHT_SYMTAB_SHNDX
+                       && ElfShdrType::new(shdrs[shdr.get_link() as usize].get_type())
+                           != ElfShdrType::SHT_SYMTAB {
+                    problems.push(format!(
+                        "section {} holds extended section indices, but links to section {}, \
+                         which isn't a symbol table",
+                        name(shdr), shdr.get_link()));
+                }
+
+                let align = shdr.get_addralign() as u64;
+                if get_alignment_exponent(align).is_none() {
+                    problems.push(format!(
+                        "section {} has sh_addralign {}, which is not a power of two",
+                        name(shdr), align));
+                } else if align > 1 && shdr.get_addr() as u64 % align != 0 {
+                    problems.push(format!(
+                        "section {} at {:#x} is not aligned to its sh_addralign {}",
+                        name(shdr), shdr.get_addr(), align));
+                }
+            }
+
+            // Sections which take up space in the file, by offset
+            let mut extents: Vec<(u64, u64, &Elf_Shdr)> = shdrs.iter()
+                .filter(|s| ElfShdrType::new(s.get_type()) != ElfShdrType::SHT_NOBITS
+                         && ElfShdrType::new(s.get_type()) != ElfShdrType::SHT_NULL
+                         && s.get_size() != 0)
+                .map(|s| (s.get_offset() as u64,
+                          (s.get_offset() as u64).saturating_add(s.get_size() as u64), s))
+                .collect();
+            extents.sort_by_key(|&(start, end, _)| (start, end));
+            let mut last: Option<(u64, &Elf_Shdr)> = None;
+            for &(start, end, shdr) in &extents {
+                if let Some((last_end, last_shdr)) = last {
+                    if start < last_end {
+                        problems.push(format!(
+                            "sections {} and {} overlap in the file",
+                            name(last_shdr), name(shdr)));
+                    }
+                    if end <= last_end {
+                        continue;
+                    }
+                }
+                last = Some((end, shdr));
+            }
+            problems
+        }
+
+        // Section headers passed to the functions below are expected to be in
+        // host byte order.
+
+        // SHT_NOBITS sections (.bss, .tbss) have an offset and a size but no
+        // bytes in the file: what lies at their offset belongs to something
+        // else, so they read as empty.
+        #[allow(dead_code)]
+        pub fn read_section_data<R: Read + Seek>(
+            shdr: &Elf_Shdr, reader: &mut R)
+            -> Result<Vec<u8>, ElfError>
+        {
+            if ElfShdrType::new(shdr.get_type()) == ElfShdrType::SHT_NOBITS {
+                return Ok(Vec::new());
+            }
+            read_bytes(reader, shdr.sh_offset as u64, shdr.sh_size as u64)
+        }
+
+        // Hex dumps of the sections named or numbered in `sections`, in the
+        // order of the section table, like readelf -x.
+        #[allow(dead_code)]
+        pub fn print_hex_dumps<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R,
+            out: &mut W, sections: &[&str])
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let is_requested = |i: usize, shdr: &Elf_Shdr, section: &str| {
+                section == get_str(&shstrtab, shdr.get_name() as u64)
+                    || section.parse::<usize>().ok() == Some(i)
+            };
+
+            for section in sections {
+                if !shdrs.iter().enumerate().any(|(i, s)| is_requested(i, s, section)) {
+                    warning!("section '{}' was not dumped because it does not exist", section);
+                }
+            }
+            for (i, shdr) in shdrs.iter().enumerate() {
+                if !sections.iter().any(|section| is_requested(i, shdr, section)) {
+                    continue;
+                }
+                let name = get_str(&shstrtab, shdr.get_name() as u64);
+                if ElfShdrType::new(shdr.get_type()) == ElfShdrType::SHT_NOBITS {
+                    try!(writeln!(out, "Section '{}' has no data in the file.", name));
+                    continue;
+                }
+                let data = match read_section_data(shdr, reader) {
+                    Ok(data) => data,
+                    Err(err) => {
+                        warning!("section '{}' was not dumped: {}", name, err);
+                        continue;
+                    }
+                };
+                if data.is_empty() {
+                    try!(writeln!(out, "Section '{}' has no data to dump.", name));
+                    continue;
+                }
+
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "Hex dump of section '{}':", name));
+                let relocated = shdrs.iter().any(|s| {
+                    let sh_type = ElfShdrType::new(s.get_type());
+                    (sh_type == ElfShdrType::SHT_REL || sh_type == ElfShdrType::SHT_RELA)
+                        && s.get_info() as usize == i
+                });
+                if relocated {
+                    try!(writeln!(
+                        out,
+                        " NOTE: This section has relocations against it, \
+                         but these have NOT been applied to this dump."));
+                }
+                try!(print_hex_lines(&data, shdr.get_addr() as u64, out));
+                try!(writeln!(out, ""));
+            }
+            Ok(())
+        }
+
+        // Reads the section header string table, or returns an empty table
+        // if `shstrndx` doesn't point at a section.
+        #[allow(dead_code)]
+        pub fn read_shstrtab<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, reader: &mut R)
+            -> Vec<u8>
+        {
+            match get_shstrndx(shdrs, shstrndx) {
+                Some(i) => read_section_data(&shdrs[i], reader).unwrap_or(Vec::new()),
+                None => Vec::new(),
+            }
+        }
+
+        // The index of the section name string table. e_shstrndx holds
+        // SHN_XINDEX when the index doesn't fit, and then the index is in
+        // sh_link of section 0. None if there is no such table, or if the
+        // index is past the end of the section table.
+        #[allow(dead_code)]
+        pub fn get_shstrndx(shdrs: &[Elf_Shdr], shstrndx: u16) -> Option<usize> {
+            let index = match shstrndx {
+                SHN_UNDEF => return None,
+                SHN_XINDEX => match shdrs.first() {
+                    Some(shdr) => shdr.get_link() as usize,
+                    None => return None,
+                },
+                index => index as usize,
+            };
+            if index < shdrs.len() { Some(index) } else { None }
+        }
+
+        // Section headers are expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn find_section_by_name<'a>(
+            shdrs: &'a [Elf_Shdr], shstrtab: &[u8], name: &str)
+            -> Option<&'a Elf_Shdr>
+        {
+            shdrs.iter().find(|s| get_str(shstrtab, s.get_name() as u64) == name)
+        }
+
+        // Section indices in the given order. Ties keep index order.
+        #[allow(dead_code)]
+        pub fn get_section_order(
+            shdrs: &[Elf_Shdr], shstrtab: &[u8], order: SectionOrder) -> Vec<usize>
+        {
+            let mut indices: Vec<usize> = (0..shdrs.len()).collect();
+            let names = SectionNames::new(shdrs, shstrtab);
+            match order {
+                SectionOrder::Index => {}
+                SectionOrder::Address =>
+                    indices.sort_by_key(|&i| shdrs[i].get_addr()),
+                SectionOrder::Size =>
+                    indices.sort_by_key(|&i| shdrs[i].get_size()),
+                SectionOrder::Name =>
+                    indices.sort_by_key(|&i| names.get_or_corrupt(i)),
+            }
+            indices
+        }
+
+        #[allow(dead_code)]
+        pub fn print_sections<R: Read + Seek, W: Write>(
+            machine: u16, shdrs: &[Elf_Shdr], shoff: $off, shstrndx: u16, e: &Endianness,
+            reader: &mut R, out: &mut W, alloc_only: bool, order: SectionOrder,
+            max_entries: Option<usize>, pow2_align: bool)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+
+            if shdrs.is_empty() {
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "There are no sections in this file."));
+                return Ok(());
+            }
+
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let width = ::std::mem::size_of::<$addr>() * 2;
+
+            try!(heading!(
+                out,
+                "There are {} section headers, starting at offset {:#x}:",
+                shdrs.len(), shoff));
+            try!(heading!(out, ""));
+            try!(heading!(out, "Section Headers:"));
+            try!(heading!(
+                out,
+                "  [Nr] Name              Type            {:<w$} Off    Size   ES Flg Lk Inf Al",
+                if width == 8 { "Addr" } else { "Address" }, w = width));
+            let mut limit = EntryLimit::new(max_entries);
+            for i in get_section_order(&shdrs, &shstrtab, order) {
+                let shdr = &shdrs[i];
+                let flags = ElfShdrFlags::new(shdr.get_flags() as u64);
+                if alloc_only && !flags.contains(SHF_ALLOC) {
+                    continue;
+                }
+                if !limit.take() {
+                    continue;
+                }
+                try!(writeln!(
+                    out,
+                    "  [{:2}] {:<17} {:<15} {:0w$x} {:06x} {:06x} {:02x} {:>3} {:2} {:3} {:>2}",
+                    i,
+                    get_display_name(&shstrtab, i, shdr),
+                    ElfShdrType::new(shdr.get_type()).get_name_for(machine),
+                    shdr.get_addr(),
+                    shdr.get_offset(),
+                    shdr.get_size(),
+                    shdr.get_entsize(),
+                    flags,
+                    shdr.get_link(),
+                    shdr.get_info(),
+                    get_alignment_string(shdr.get_addralign() as u64, pow2_align),
+                    w = width));
+            }
+            try!(limit.print_omitted(out));
+            try!(heading!(out, "Key to Flags:"));
+            try!(heading!(out, "  W (write), A (alloc), X (execute), M (merge), S (strings), I (info),"));
+            try!(heading!(out, "  L (link order), O (extra OS processing required), G (group), T (TLS),"));
+            try!(heading!(out, "  C (compressed), x (unknown), o (OS specific), E (exclude),"));
+            try!(heading!(out, "  D (mbind), p (processor specific)"));
+
+            if !::heading::is_shown() {
+                return Ok(());
+            }
+            if let Some((start, file_size, mem_size)) = get_tls_template(&shdrs) {
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "TLS sections (the template of each thread's TLS block):"));
+                for (i, shdr) in shdrs.iter().enumerate() {
+                    if !ElfShdrFlags::new(shdr.get_flags() as u64).contains(SHF_TLS) {
+                        continue;
+                    }
+                    let contents =
+                        if ElfShdrType::new(shdr.get_type()) == ElfShdrType::SHT_NOBITS {
+                            "zero-filled, takes no space in the file"
+                        } else {
+                            "copied from the file"
+                        };
+                    try!(writeln!(
+                        out,
+                        "  [{:2}] {:<17} {:#x} bytes, {}",
+                        i, get_str(&shstrtab, shdr.get_name() as u64), shdr.get_size(), contents));
+                }
+                // In relocatable objects the sections aren't laid out yet
+                if start != 0 {
+                    try!(writeln!(
+                        out,
+                        "  Template size: {:#x} bytes in the file, {:#x} in memory",
+                        file_size, mem_size));
+                }
+            }
+            Ok(())
+        }
+
+        // The same table as print_sections, spread over several lines per
+        // section with the flags spelled out (readelf -t).
+        #[allow(dead_code)]
+        pub fn print_section_details<R: Read + Seek, W: Write>(
+            machine: u16, shdrs: &[Elf_Shdr], shoff: $off, shstrndx: u16, e: &Endianness,
+            reader: &mut R, out: &mut W, alloc_only: bool, order: SectionOrder,
+            max_entries: Option<usize>, pow2_align: bool)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+
+            if shdrs.is_empty() {
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "There are no sections in this file."));
+                return Ok(());
+            }
+
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let width = ::std::mem::size_of::<$addr>() * 2;
+
+            try!(heading!(
+                out,
+                "There are {} section headers, starting at offset {:#x}:",
+                shdrs.len(), shoff));
+            try!(heading!(out, ""));
+            try!(heading!(out, "Section Headers:"));
+            try!(heading!(out, "  [Nr] Name"));
+            try!(heading!(
+                out,
+                "       Type            {:<w$} Off    Size   ES   Lk Inf Al",
+                if width == 8 { "Addr" } else { "Address" }, w = width));
+            try!(heading!(out, "       Flags"));
+            let mut limit = EntryLimit::new(max_entries);
+            for i in get_section_order(&shdrs, &shstrtab, order) {
+                let shdr = &shdrs[i];
+                let flags = ElfShdrFlags::new(shdr.get_flags() as u64);
+                if alloc_only && !flags.contains(SHF_ALLOC) {
+                    continue;
+                }
+                if !limit.take() {
+                    continue;
+                }
+                try!(writeln!(
+                    out,
+                    "  [{:2}] {}", i, get_display_name(&shstrtab, i, shdr)));
+                try!(writeln!(
+                    out,
+                    "       {:<15} {:0w$x} {:06x} {:06x} {:02x} {:3} {:3} {:>2}",
+                    ElfShdrType::new(shdr.get_type()).get_name_for(machine),
+                    shdr.get_addr(),
+                    shdr.get_offset(),
+                    shdr.get_size(),
+                    shdr.get_entsize(),
+                    shdr.get_link(),
+                    shdr.get_info(),
+                    get_alignment_string(shdr.get_addralign() as u64, pow2_align),
+                    w = width));
+                try!(writeln!(
+                    out,
+                    "       [{:0w$x}]: {}",
+                    flags.get_value(), flags.describe(), w = width));
+            }
+            limit.print_omitted(out)
+        }
+
+        fn get_section_name<'a>(
+            shdrs: &[Elf_Shdr], shstrtab: &'a [u8], index: u64) -> String
+        {
+            match shdrs.get(index as usize) {
+                Some(shdr) => get_display_name(shstrtab, index as usize, shdr),
+                None => format!("<corrupt: {}>", index),
+            }
+        }
+
+        // Without a section name string table sections are named by their
+        // index.
+        fn get_display_name(shstrtab: &[u8], index: usize, shdr: &Elf_Shdr) -> String {
+            if shstrtab.is_empty() {
+                format!("<section {}>", index)
+            } else {
+                get_str(shstrtab, shdr.get_name() as u64).to_string()
+            }
+        }
+
+        // Spells out what sh_link and sh_info mean for this kind of section.
+        // Fields which are zero and carry no meaning are left out.
+        fn describe_link_info(
+            shdr: &Elf_Shdr, shdrs: &[Elf_Shdr], shstrtab: &[u8]) -> String
+        {
+            let sh_type = ElfShdrType::new(shdr.get_type());
+            let link = shdr.get_link() as u64;
+            let info = shdr.get_info() as u64;
+            let mut parts = Vec::new();
+
+            if sh_type == ElfShdrType::SHT_SYMTAB_SHNDX {
+                parts.push(format!(
+                    "link: {} (symbol table it extends)", get_section_name(shdrs, shstrtab, link)));
+            } else if link != 0 {
+                parts.push(format!("link: {}", get_section_name(shdrs, shstrtab, link)));
+            }
+            if sh_type == ElfShdrType::SHT_SYMTAB || sh_type == ElfShdrType::SHT_DYNSYM {
+                // One past the last local symbol.
+                parts.push(format!("info: {} (first non-local symbol)", info));
+            } else if sh_type == ElfShdrType::SHT_GROUP {
+                parts.push(format!("info: {} (signature symbol)", info));
+            } else if sh_type == ElfShdrType::SHT_GNU_VERDEF
+                   || sh_type == ElfShdrType::SHT_GNU_VERNEED {
+                parts.push(format!("info: {} (number of entries)", info));
+            } else if info != 0 {
+                let is_section = sh_type == ElfShdrType::SHT_REL
+                    || sh_type == ElfShdrType::SHT_RELA
+                    || ElfShdrFlags::new(shdr.get_flags() as u64).contains(SHF_INFO_LINK);
+                if is_section {
+                    parts.push(format!("info: {}", get_section_name(shdrs, shstrtab, info)));
+                } else {
+                    parts.push(format!("info: {}", info));
+                }
+            }
+            parts.join(", ")
+        }
+
+        // The section table with sh_link and sh_info resolved to what they
+        // refer to, instead of bare numbers.
+        #[allow(dead_code)]
+        pub fn print_section_links<R: Read + Seek, W: Write>(
+            machine: u16, shdrs: &[Elf_Shdr], shoff: $off, shstrndx: u16, e: &Endianness,
+            reader: &mut R, out: &mut W, alloc_only: bool, order: SectionOrder,
+            max_entries: Option<usize>)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+
+            if shdrs.is_empty() {
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "There are no sections in this file."));
+                return Ok(());
+            }
+
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+
+            try!(heading!(
+                out,
+                "There are {} section headers, starting at offset {:#x}:",
+                shdrs.len(), shoff));
+            try!(heading!(out, ""));
+            try!(heading!(out, "Section Headers:"));
+            try!(heading!(out, "  [Nr] Name              Type            Link and Info"));
+            let mut limit = EntryLimit::new(max_entries);
+            for i in get_section_order(&shdrs, &shstrtab, order) {
+                let shdr = &shdrs[i];
+                let flags = ElfShdrFlags::new(shdr.get_flags() as u64);
+                if alloc_only && !flags.contains(SHF_ALLOC) {
+                    continue;
+                }
+                if !limit.take() {
+                    continue;
+                }
+                let line = format!(
+                    "  [{:2}] {:<17} {:<15} {}",
+                    i,
+                    get_display_name(&shstrtab, i, shdr),
+                    ElfShdrType::new(shdr.get_type()).get_name_for(machine),
+                    describe_link_info(shdr, &shdrs, &shstrtab));
+                try!(writeln!(out, "{}", line.trim_end()));
+            }
+            limit.print_omitted(out)
+        }
+
+        // Entropy of a section's contents in bits per byte. Sections that
+        // occupy no space in the file (SHT_NOBITS) have none.
+        #[allow(dead_code)]
+        pub fn get_section_entropy<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], index: usize, reader: &mut R) -> Option<f64>
+        {
+            match shdrs.get(index) {
+                Some(shdr) if ElfShdrType::new(shdr.get_type()) != ElfShdrType::SHT_NOBITS =>
+                    read_section_data(shdr, reader).ok().map(|data| get_entropy(&data)),
+                _ => None,
+            }
+        }
+
+        #[allow(dead_code)]
+        pub fn print_section_entropy<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R,
+            out: &mut W, max_entries: Option<usize>)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+
+            if shdrs.is_empty() {
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "There are no sections in this file."));
+                return Ok(());
+            }
+
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+
+            try!(heading!(out, ""));
+            try!(heading!(out, "Section entropy (bits per byte, 8.0 is random):"));
+            try!(heading!(out, "  [Nr]       Size Entropy Name"));
+            let mut limit = EntryLimit::new(max_entries);
+            for (i, shdr) in shdrs.iter().enumerate().skip(1) {
+                if !limit.take() {
+                    continue;
+                }
+                let entropy = match get_section_entropy(&shdrs, i, reader) {
+                    Some(entropy) => format!("{:.3}", entropy),
+                    None => "-".to_string(),
+                };
+                try!(writeln!(
+                    out,
+                    "  [{:2}] {:>10} {:>7} {}",
+                    i,
+                    shdr.get_size(),
+                    entropy,
+                    get_str(&shstrtab, shdr.get_name() as u64)));
+            }
+            limit.print_omitted(out)
+        }
+
+        // Printable strings with their file offsets and the sections they're
+        // in, like strings(1). The whole file is scanned, so strings in the
+        // headers are found too, unless `alloc_only` limits it to sections
+        // which are loaded at run time.
+        #[allow(dead_code)]
+        pub fn print_strings<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R,
+            out: &mut W, min_len: usize, alloc_only: bool, max_entries: Option<usize>)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let in_file = |shdr: &Elf_Shdr| {
+                ElfShdrType::new(shdr.get_type()) != ElfShdrType::SHT_NOBITS
+                    && ElfShdrType::new(shdr.get_type()) != ElfShdrType::SHT_NULL
+                    && shdr.get_size() != 0
+            };
+
+            // (file offset, contents) of each range to scan
+            let ranges: Vec<(u64, Vec<u8>)> = if alloc_only {
+                shdrs.iter()
+                    .filter(|s| in_file(s)
+                            && ElfShdrFlags::new(s.get_flags() as u64).contains(SHF_ALLOC))
+                    .map(|s| (s.get_offset() as u64, read_section_data(s, reader).unwrap_or(Vec::new())))
+                    .collect()
+            } else {
+                let size = try!(reader.seek(SeekFrom::End(0)));
+                vec![(0, read_bytes(reader, 0, size).unwrap_or(Vec::new()))]
+            };
+
+            try!(writeln!(out, ""));
+            try!(writeln!(out, "Strings of at least {} characters:", min_len));
+            try!(writeln!(out, "  {:>8} {:<20} String", "Offset", "Section"));
+            let names = SectionNames::new(&shdrs, &shstrtab);
+            let sections = SectionsByOffset::new(&shdrs);
+            let mut limit = EntryLimit::new(max_entries);
+            for &(base, ref data) in &ranges {
+                for (offset, s) in get_strings(data, min_len) {
+                    if !limit.take() {
+                        continue;
+                    }
+                    let offset = base + offset as u64;
+                    let section = sections.get(offset)
+                        .map(|index| names.get_or_corrupt(index))
+                        .unwrap_or("-");
+                    try!(writeln!(out, "  {:>8x} {:<20} {}", offset, section, s));
+                }
+            }
+            limit.print_omitted(out)
+        }
+
+        // Returns the parsed .gnu_debuglink section, if there is a valid one.
+        #[allow(dead_code)]
+        pub fn read_debuglink<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R)
+            -> Option<DebugLink>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+
+            find_section_by_name(&shdrs, &shstrtab, ".gnu_debuglink")
+                .and_then(|shdr| read_section_data(shdr, reader).ok())
+                .and_then(|data| parse_debuglink(&data, e))
+        }
+
+        #[allow(dead_code)]
+        pub fn print_debuglink<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R,
+            out: &mut W)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+
+            if let Some(shdr) = find_section_by_name(&shdrs, &shstrtab, ".gnu_debugaltlink") {
+                let data = read_section_data(shdr, reader).unwrap_or(Vec::new());
+                try!(writeln!(out, "Contents of the .gnu_debugaltlink section:"));
+                try!(writeln!(out, ""));
+                match parse_debugaltlink(&data) {
+                    Some(link) => {
+                        let build_id = link.get_build_id();
+                        try!(writeln!(
+                            out, "  Separate debug info file: {}", link.get_filename()));
+                        try!(writeln!(out, "  Build-ID ({:#x} bytes):", build_id.len()));
+                        for b in build_id {
+                            try!(write!(out, " {:02x}", b));
+                        }
+                        try!(writeln!(out, ""));
+                    }
+                    None => warning!(
+                        "the .gnu_debugaltlink file name is not terminated"),
+                }
+                try!(writeln!(out, ""));
+            }
+
+            if let Some(shdr) = find_section_by_name(&shdrs, &shstrtab, ".gnu_debuglink") {
+                let data = read_section_data(shdr, reader).unwrap_or(Vec::new());
+                try!(writeln!(out, "Contents of the .gnu_debuglink section:"));
+                try!(writeln!(out, ""));
+                match parse_debuglink(&data, e) {
+                    Some(link) => {
+                        try!(writeln!(
+                            out, "  Separate debug info file: {}", link.get_filename()));
+                        try!(writeln!(out, "  CRC value: {:#x}", link.get_crc()));
+                    }
+                    None => warning!(
+                        "the .gnu_debuglink section is too short for an aligned CRC"),
+                }
+                try!(writeln!(out, ""));
+            }
+            Ok(())
+        }
+
+        // Reads a DWARF section by name. Compressed sections are skipped with
+        // a warning, as there is no decompressor here.
+        fn read_debug_section<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], shstrtab: &[u8], name: &str, reader: &mut R,
+            out: &mut W)
+            -> io::Result<Option<Vec<u8>>>
+        {
+            let shdr = match find_section_by_name(shdrs, shstrtab, name) {
+                Some(shdr) => shdr,
+                None => {
+                    try!(writeln!(out, "There is no {} section in this file.", name));
+                    return Ok(None);
+                }
+            };
+            if ElfShdrFlags::new(shdr.get_flags() as u64).contains(SHF_COMPRESSED) {
+                warning!("the {} section is compressed, which is not supported", name);
+                return Ok(None);
+            }
+            match read_section_data(shdr, reader) {
+                Ok(data) => Ok(Some(data)),
+                Err(err) => {
+                    warning!("the {} section can't be read: {}", name, err);
+                    Ok(None)
+                }
+            }
+        }
+
+        // Lists the compilation unit headers in .debug_info.
+        #[allow(dead_code)]
+        pub fn print_debug_info<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R,
+            out: &mut W)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+
+            let data = match try!(read_debug_section(
+                &shdrs, &shstrtab, ".debug_info", reader, out))
+            {
+                Some(data) => data,
+                None => return Ok(()),
+            };
+            let units = parse_unit_headers(&data, e);
+            try!(writeln!(out, "Contents of the .debug_info section:"));
+            try!(writeln!(out, ""));
+            try!(print_unit_headers(&units, out));
+            let parsed: u64 = units.iter()
+                .map(|u| u.get_length() + if u.is_dwarf64() { 12 } else { 4 })
+                .sum();
+            if parsed < data.len() as u64 {
+                warning!(
+                    "the .debug_info section has {} bytes after the last valid unit",
+                    data.len() as u64 - parsed);
+            }
+            Ok(())
+        }
+
+        // Prints the line number program headers in .debug_line, with their
+        // directory and file name tables.
+        #[allow(dead_code)]
+        pub fn print_debug_line<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R,
+            out: &mut W)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+
+            let data = match try!(read_debug_section(
+                &shdrs, &shstrtab, ".debug_line", reader, out))
+            {
+                Some(data) => data,
+                None => return Ok(()),
+            };
+            let headers = parse_line_headers(&data, e);
+            try!(writeln!(out, "Contents of the .debug_line section:"));
+            try!(writeln!(out, ""));
+            try!(print_line_headers(&headers, out));
+            let parsed: u64 = headers.iter()
+                .map(|h| h.get_length() + if h.is_dwarf64() { 12 } else { 4 })
+                .sum();
+            if parsed < data.len() as u64 {
+                warning!(
+                    "the .debug_line section has {} bytes after the last valid header",
+                    data.len() as u64 - parsed);
+            }
+            Ok(())
+        }
+
+        // Prints where the sections and segments lie in the file, with the
+        // padding between them and their offsets in the PT_LOAD segments
+        // that hold them. Everything is expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn print_relative_offsets<R: Read + Seek, W: Write>(
+            ehdr: &super::elf_ehdr::Elf_Ehdr, phdrs: &[super::elf_phdr::Elf_Phdr],
+            shdrs: &[Elf_Shdr], reader: &mut R, out: &mut W)
+            -> io::Result<()>
+        {
+            use super::super::elf_phdr_type::ElfPhdrType;
+
+            let shstrtab = read_shstrtab(shdrs, ehdr.get_shstrndx(), reader);
+            let is_load =
+                |i: usize| ElfPhdrType::new(phdrs[i].get_type()) == ElfPhdrType::PT_LOAD;
+            // The first PT_LOAD whose bytes in the file include the range
+            let get_within = |offset: u64, size: u64| {
+                (0..phdrs.len()).filter(|&i| is_load(i)).find(|&i| {
+                    let start = phdrs[i].get_offset() as u64;
+                    let end = start.saturating_add(phdrs[i].get_filesz() as u64);
+                    offset >= start && offset.saturating_add(size) <= end
+                        && (size != 0 || offset < end)
+                }).map(|i| (i, offset - phdrs[i].get_offset() as u64))
+            };
+            let tables = [
+                ("ELF header", 0, ehdr.get_ehsize() as u64),
+                ("program headers", ehdr.get_phoff() as u64,
+                 ehdr.get_phentsize() as u64 * phdrs.len() as u64),
+                ("section headers", ehdr.get_shoff() as u64,
+                 ehdr.get_shentsize() as u64 * shdrs.len() as u64),
+            ];
+
+            let mut sections: Vec<LayoutEntry> = tables.iter()
+                .filter(|&&(_, _, size)| size != 0)
+                .map(|&(label, offset, size)| LayoutEntry::new(
+                    label.to_string(), offset, size, true, get_within(offset, size)))
+                .collect();
+            for (i, shdr) in shdrs.iter().enumerate() {
+                let sh_type = ElfShdrType::new(shdr.get_type());
+                // Neither takes up any of the file
+                if sh_type == ElfShdrType::SHT_NULL || sh_type == ElfShdrType::SHT_NOBITS {
+                    continue;
+                }
+                let (offset, size) = (shdr.get_offset() as u64, shdr.get_size() as u64);
+                sections.push(LayoutEntry::new(
+                    format!("[{:2}] {}", i, get_display_name(&shstrtab, i, shdr)),
+                    offset, size, true, get_within(offset, size)));
+            }
+            try!(print_layout("Sections", &sections, out));
+
+            // Other segments lie inside the loaded ones, so only those are
+            // measured against each other
+            let segments: Vec<LayoutEntry> = phdrs.iter().enumerate()
+                .filter(|&(_, phdr)| phdr.get_filesz() != 0)
+                .map(|(i, phdr)| {
+                    let (offset, size) = (phdr.get_offset() as u64, phdr.get_filesz() as u64);
+                    LayoutEntry::new(
+                        format!("[{:02}] {}", i, ElfPhdrType::new(phdr.get_type())),
+                        offset, size, is_load(i),
+                        if is_load(i) { None } else { get_within(offset, size) })
+                })
+                .collect();
+            print_layout("Segments", &segments, out)
+        }
+
+        // Counts the structures of the file without decoding them. Both
+        // tables are expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn get_structure_counts<R: Read + Seek>(
+            phdrs: &[super::elf_phdr::Elf_Phdr], shdrs: &[Elf_Shdr], e: &Endianness,
+            reader: &mut R)
+            -> StructureCounts
+        {
+            use ::std::mem::size_of;
+            use super::super::elf_phdr_type::ElfPhdrType;
+            use super::elf_rel::{Elf_Rel, Elf_Rela, read_relrs, decode_relr};
+            use super::elf_sym::Elf_Sym;
+            use super::helpers::read_dyns;
+
+            let mut counts = StructureCounts::default();
+            counts.sections = shdrs.len();
+            counts.segments = phdrs.len();
+            for shdr in shdrs {
+                let entries = |size: usize| shdr.get_entry_count(size).unwrap_or(0);
+                match ElfShdrType::new(shdr.get_type()) {
+                    ElfShdrType::SHT_SYMTAB => counts.symtab_symbols += entries(size_of::<Elf_Sym>()),
+                    ElfShdrType::SHT_DYNSYM => counts.dynsym_symbols += entries(size_of::<Elf_Sym>()),
+                    ElfShdrType::SHT_REL => counts.relocations += entries(size_of::<Elf_Rel>()),
+                    ElfShdrType::SHT_RELA => counts.relocations += entries(size_of::<Elf_Rela>()),
+                    ElfShdrType::SHT_RELR => if let Ok(words) = read_relrs(shdr, e, reader) {
+                        counts.relocations += decode_relr(&words).len() as u64;
+                    },
+                    ElfShdrType::SHT_NOTE => {
+                        let data = read_section_data(shdr, reader).unwrap_or(Vec::new());
+                        counts.notes += parse_notes(&data, e, shdr.get_addralign() as usize).len();
+                    }
+                    _ => {}
+                }
+            }
+            // Without section headers the notes are still in PT_NOTE
+            if shdrs.is_empty() {
+                for phdr in phdrs.iter().filter(|p| p.get_type() == ElfPhdrType::PT_NOTE.get_value()) {
+                    let data = read_bytes(reader, phdr.get_offset() as u64, phdr.get_filesz() as u64)
+                        .unwrap_or(Vec::new());
+                    counts.notes += parse_notes(&data, e, phdr.get_align() as usize).len();
+                }
+            }
+            if let Some(dynamic) = phdrs.iter()
+                .find(|p| p.get_type() == ElfPhdrType::PT_DYNAMIC.get_value())
+            {
+                counts.dynamic_entries =
+                    read_dyns(dynamic, e, reader).map(|d| d.len()).unwrap_or(0);
+            }
+            counts
+        }
+
+        // Lists the entries of every ARM exception index table.
+        #[allow(dead_code)]
+        pub fn print_arm_exidx<R: Read + Seek, W: Write>(
+            machine: u16, shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness,
+            reader: &mut R, out: &mut W)
+            -> io::Result<()>
+        {
+            use super::super::elf_shdr_type::EM_ARM;
+
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let tables: Vec<&Elf_Shdr> = shdrs.iter()
+                .filter(|s| machine == EM_ARM
+                         && ElfShdrType::new(s.get_type()) == ElfShdrType::SHT_ARM_EXIDX)
+                .collect();
+
+            if tables.is_empty() {
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "There are no ARM exception index tables in this file."));
+            }
+            for shdr in tables {
+                let data = read_section_data(shdr, reader).unwrap_or(Vec::new());
+                let entries = parse_exidx(&data, shdr.get_addr() as u64, e);
+                try!(writeln!(out, ""));
+                try!(writeln!(
+                    out,
+                    "ARM exception index table '{}' at offset {:#x} contains {} entries:",
+                    get_str(&shstrtab, shdr.get_name() as u64), shdr.get_offset(),
+                    entries.len()));
+                try!(print_exidx(&entries, out));
+            }
+            Ok(())
+        }
+
+        // Prints the contents of every SHT_NOTE section (readelf -n).
+        #[allow(dead_code)]
+        pub fn print_section_notes<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R,
+            out: &mut W, raw: bool)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let is_64bit = ::std::mem::size_of::<$addr>() == 8;
+
+            for shdr in &shdrs {
+                if ElfShdrType::new(shdr.get_type()) != ElfShdrType::SHT_NOTE {
+                    continue;
+                }
+                let data = match read_section_data(shdr, reader) {
+                    Ok(data) => data,
+                    Err(err) => {
+                        warning!("note section {}: {}",
+                                 get_str(&shstrtab, shdr.get_name() as u64), err);
+                        continue;
+                    }
+                };
+                let notes = parse_notes(&data, e, shdr.get_addralign() as usize);
+                try!(print_notes(
+                    get_str(&shstrtab, shdr.get_name() as u64), &notes, e, is_64bit,
+                    raw, out));
+            }
+            Ok(())
+        }
+    }
+}
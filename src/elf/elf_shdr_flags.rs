@@ -0,0 +1,114 @@
+use ::std::fmt::{Display, Formatter};
+
+pub const SHF_WRITE: u64 = 0x1;
+pub const SHF_ALLOC: u64 = 0x2;
+pub const SHF_EXECINSTR: u64 = 0x4;
+pub const SHF_MERGE: u64 = 0x10;
+pub const SHF_STRINGS: u64 = 0x20;
+pub const SHF_INFO_LINK: u64 = 0x40;
+pub const SHF_LINK_ORDER: u64 = 0x80;
+pub const SHF_OS_NONCONFORMING: u64 = 0x100;
+pub const SHF_GROUP: u64 = 0x200;
+pub const SHF_TLS: u64 = 0x400;
+pub const SHF_COMPRESSED: u64 = 0x800;
+pub const SHF_GNU_RETAIN: u64 = 0x200000;
+pub const SHF_GNU_MBIND: u64 = 0x01000000;
+pub const SHF_MASKOS: u64 = 0x0ff00000;
+pub const SHF_EXCLUDE: u64 = 0x80000000;
+pub const SHF_MASKPROC: u64 = 0xf0000000;
+
+const SHF_LETTERS: [(u64, char); 14] = [
+    (SHF_WRITE, 'W'),
+    (SHF_ALLOC, 'A'),
+    (SHF_EXECINSTR, 'X'),
+    (SHF_MERGE, 'M'),
+    (SHF_STRINGS, 'S'),
+    (SHF_INFO_LINK, 'I'),
+    (SHF_LINK_ORDER, 'L'),
+    (SHF_OS_NONCONFORMING, 'O'),
+    (SHF_GROUP, 'G'),
+    (SHF_TLS, 'T'),
+    (SHF_COMPRESSED, 'C'),
+    (SHF_GNU_RETAIN, 'R'),
+    (SHF_GNU_MBIND, 'D'),
+    (SHF_EXCLUDE, 'E'),
+];
+
+const SHF_NAMES: [(u64, &'static str); 14] = [
+    (SHF_WRITE, "WRITE"),
+    (SHF_ALLOC, "ALLOC"),
+    (SHF_EXECINSTR, "EXEC"),
+    (SHF_MERGE, "MERGE"),
+    (SHF_STRINGS, "STRINGS"),
+    (SHF_INFO_LINK, "INFO LINK"),
+    (SHF_LINK_ORDER, "LINK ORDER"),
+    (SHF_OS_NONCONFORMING, "OS NONCONF"),
+    (SHF_GROUP, "GROUP"),
+    (SHF_TLS, "TLS"),
+    (SHF_COMPRESSED, "COMPRESSED"),
+    (SHF_GNU_RETAIN, "GNU_RETAIN"),
+    (SHF_GNU_MBIND, "GNU_MBIND"),
+    (SHF_EXCLUDE, "EXCLUDE"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElfShdrFlags {
+    flags: u64,
+}
+
+#[allow(dead_code)]
+impl ElfShdrFlags {
+    pub fn new(flags: u64) -> ElfShdrFlags {
+        ElfShdrFlags { flags: flags }
+    }
+    pub fn get_value(&self) -> u64 {
+        self.flags
+    }
+    pub fn contains(&self, flag: u64) -> bool {
+        (self.flags & flag) != 0
+    }
+    // Spells the flags out in full, as opposed to the letters of Display.
+    pub fn describe(&self) -> String {
+        let mut names = Vec::new();
+        let mut rest = self.flags;
+        for &(flag, name) in SHF_NAMES.iter() {
+            if self.contains(flag) {
+                names.push(name.to_string());
+                rest &= !flag;
+            }
+        }
+        if (rest & SHF_MASKOS) != 0 {
+            names.push(format!("OS ({:#x})", rest & SHF_MASKOS));
+        }
+        if (rest & SHF_MASKPROC) != 0 {
+            names.push(format!("PROC ({:#x})", rest & SHF_MASKPROC));
+        }
+        if (rest & !(SHF_MASKOS | SHF_MASKPROC)) != 0 {
+            names.push(format!("UNKNOWN ({:#x})", rest & !(SHF_MASKOS | SHF_MASKPROC)));
+        }
+        names.join(", ")
+    }
+}
+
+impl Display for ElfShdrFlags {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        let mut s = String::new();
+        let mut rest = self.flags;
+        for &(flag, letter) in SHF_LETTERS.iter() {
+            if self.contains(flag) {
+                s.push(letter);
+                rest &= !flag;
+            }
+        }
+        if (rest & SHF_MASKOS) != 0 {
+            s.push('o');
+        }
+        if (rest & SHF_MASKPROC) != 0 {
+            s.push('p');
+        }
+        if (rest & !(SHF_MASKOS | SHF_MASKPROC)) != 0 {
+            s.push('x');
+        }
+        fmt.pad(&s)
+    }
+}
@@ -0,0 +1,147 @@
+use ::std::fmt::{Display, Formatter};
+
+// The machines which define section types of their own
+const EM_MIPS: u16 = 8;
+const EM_MIPS_RS3_LE: u16 = 10;
+pub const EM_ARM: u16 = 40;
+const EM_X86_64: u16 = 62;
+const EM_AARCH64: u16 = 183;
+const EM_RISCV: u16 = 243;
+
+// SHT_LOPROC..SHT_HIPROC is for processor-specific types, which mean
+// different things on different machines.
+const SHT_LOPROC: u32 = 0x70000000;
+const SHT_HIPROC: u32 = 0x7fffffff;
+
+const MIPS_TYPE_NAMES: [(u32, &'static str); 10] = [
+    (0x70000000, "MIPS_LIBLIST"),
+    (0x70000001, "MIPS_MSYM"),
+    (0x70000002, "MIPS_CONFLICT"),
+    (0x70000003, "MIPS_GPTAB"),
+    (0x70000004, "MIPS_UCODE"),
+    (0x70000005, "MIPS_DEBUG"),
+    (0x70000006, "MIPS_REGINFO"),
+    (0x7000000d, "MIPS_OPTIONS"),
+    (0x7000001e, "MIPS_DWARF"),
+    (0x7000002a, "MIPS_ABIFLAGS"),
+];
+
+const ARM_TYPE_NAMES: [(u32, &'static str); 5] = [
+    (0x70000001, "ARM_EXIDX"),
+    (0x70000002, "ARM_PREEMPTMAP"),
+    (0x70000003, "ARM_ATTRIBUTES"),
+    (0x70000004, "ARM_DEBUGOVERLAY"),
+    (0x70000005, "ARM_OVERLAYSECTION"),
+];
+
+const X86_64_TYPE_NAMES: [(u32, &'static str); 1] = [
+    (0x70000001, "X86_64_UNWIND"),
+];
+
+const AARCH64_TYPE_NAMES: [(u32, &'static str); 1] = [
+    (0x70000003, "AARCH64_ATTRIBUTES"),
+];
+
+const RISCV_TYPE_NAMES: [(u32, &'static str); 1] = [
+    (0x70000003, "RISCV_ATTRIBUTES"),
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ElfShdrType {
+    sh_type: u32,
+}
+
+#[allow(dead_code)]
+impl ElfShdrType {
+    pub const SHT_NULL: ElfShdrType = ElfShdrType { sh_type: 0 };
+    pub const SHT_PROGBITS: ElfShdrType = ElfShdrType { sh_type: 1 };
+    pub const SHT_SYMTAB: ElfShdrType = ElfShdrType { sh_type: 2 };
+    pub const SHT_STRTAB: ElfShdrType = ElfShdrType { sh_type: 3 };
+    pub const SHT_RELA: ElfShdrType = ElfShdrType { sh_type: 4 };
+    pub const SHT_HASH: ElfShdrType = ElfShdrType { sh_type: 5 };
+    pub const SHT_DYNAMIC: ElfShdrType = ElfShdrType { sh_type: 6 };
+    pub const SHT_NOTE: ElfShdrType = ElfShdrType { sh_type: 7 };
+    pub const SHT_NOBITS: ElfShdrType = ElfShdrType { sh_type: 8 };
+    pub const SHT_REL: ElfShdrType = ElfShdrType { sh_type: 9 };
+    pub const SHT_SHLIB: ElfShdrType = ElfShdrType { sh_type: 10 };
+    pub const SHT_DYNSYM: ElfShdrType = ElfShdrType { sh_type: 11 };
+    pub const SHT_INIT_ARRAY: ElfShdrType = ElfShdrType { sh_type: 14 };
+    pub const SHT_FINI_ARRAY: ElfShdrType = ElfShdrType { sh_type: 15 };
+    pub const SHT_PREINIT_ARRAY: ElfShdrType = ElfShdrType { sh_type: 16 };
+    pub const SHT_GROUP: ElfShdrType = ElfShdrType { sh_type: 17 };
+    // The upper bits of st_shndx, for symbols of a table with more sections
+    // than fit in 16 bits
+    pub const SHT_SYMTAB_SHNDX: ElfShdrType = ElfShdrType { sh_type: 18 };
+    pub const SHT_RELR: ElfShdrType = ElfShdrType { sh_type: 19 };
+    pub const SHT_GNU_HASH: ElfShdrType = ElfShdrType { sh_type: 0x6ffffff6 };
+    pub const SHT_GNU_VERDEF: ElfShdrType = ElfShdrType { sh_type: 0x6ffffffd };
+    pub const SHT_GNU_VERNEED: ElfShdrType = ElfShdrType { sh_type: 0x6ffffffe };
+    pub const SHT_GNU_VERSYM: ElfShdrType = ElfShdrType { sh_type: 0x6fffffff };
+    // Only on EM_ARM
+    pub const SHT_ARM_EXIDX: ElfShdrType = ElfShdrType { sh_type: 0x70000001 };
+
+    pub fn new(sh_type: u32) -> ElfShdrType {
+        ElfShdrType { sh_type: sh_type }
+    }
+    pub fn get_value(&self) -> u32 {
+        self.sh_type
+    }
+    // The name of a processor-specific type on the given machine.
+    pub fn get_machine_name(&self, machine: u16) -> Option<&'static str> {
+        if self.sh_type < SHT_LOPROC || self.sh_type > SHT_HIPROC {
+            return None;
+        }
+        let names: &[(u32, &'static str)] = match machine {
+            EM_MIPS | EM_MIPS_RS3_LE => &MIPS_TYPE_NAMES,
+            EM_ARM => &ARM_TYPE_NAMES,
+            EM_X86_64 => &X86_64_TYPE_NAMES,
+            EM_AARCH64 => &AARCH64_TYPE_NAMES,
+            EM_RISCV => &RISCV_TYPE_NAMES,
+            _ => return None,
+        };
+        names.iter()
+            .find(|&&(sh_type, _)| sh_type == self.sh_type)
+            .map(|&(_, name)| name)
+    }
+    // The type as it is displayed in a file of the given machine: the
+    // processor-specific name if there is one, Display otherwise.
+    pub fn get_name_for(&self, machine: u16) -> String {
+        match self.get_machine_name(machine) {
+            Some(name) => name.to_string(),
+            None => format!("{}", self),
+        }
+    }
+}
+
+impl Display for ElfShdrType {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            ElfShdrType::SHT_NULL => "NULL",
+            ElfShdrType::SHT_PROGBITS => "PROGBITS",
+            ElfShdrType::SHT_SYMTAB => "SYMTAB",
+            ElfShdrType::SHT_STRTAB => "STRTAB",
+            ElfShdrType::SHT_RELA => "RELA",
+            ElfShdrType::SHT_HASH => "HASH",
+            ElfShdrType::SHT_DYNAMIC => "DYNAMIC",
+            ElfShdrType::SHT_NOTE => "NOTE",
+            ElfShdrType::SHT_NOBITS => "NOBITS",
+            ElfShdrType::SHT_REL => "REL",
+            ElfShdrType::SHT_SHLIB => "SHLIB",
+            ElfShdrType::SHT_DYNSYM => "DYNSYM",
+            ElfShdrType::SHT_INIT_ARRAY => "INIT_ARRAY",
+            ElfShdrType::SHT_FINI_ARRAY => "FINI_ARRAY",
+            ElfShdrType::SHT_PREINIT_ARRAY => "PREINIT_ARRAY",
+            ElfShdrType::SHT_GROUP => "GROUP",
+            ElfShdrType::SHT_SYMTAB_SHNDX => "SYMTAB SECTION INDICES",
+            ElfShdrType::SHT_RELR => "RELR",
+            ElfShdrType::SHT_GNU_HASH => "GNU_HASH",
+            ElfShdrType::SHT_GNU_VERDEF => "VERDEF",
+            ElfShdrType::SHT_GNU_VERNEED => "VERNEED",
+            ElfShdrType::SHT_GNU_VERSYM => "VERSYM",
+            ElfShdrType { sh_type } => {
+                return fmt.pad(&format!("{:08x}", sh_type))
+            }
+        };
+        fmt.pad(s)
+    }
+}
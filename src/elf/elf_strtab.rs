@@ -0,0 +1,54 @@
+// The contents of one SHT_STRTAB section: NUL-terminated strings which
+// names in the section, symbol and dynamic tables are offsets into.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringTable {
+    name: String,
+    data: Vec<u8>,
+}
+
+#[allow(dead_code)]
+impl StringTable {
+    pub fn new(name: String, data: Vec<u8>) -> StringTable {
+        StringTable { name: name, data: data }
+    }
+    // The name of the section the table is in
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+    // The string starting at `offset`. None if the offset is past the end
+    // of the table or the string isn't valid UTF-8. A string which runs
+    // into the end of the table without a NUL is cut there.
+    pub fn get(&self, offset: u64) -> Option<&str> {
+        if offset >= self.data.len() as u64 {
+            return None;
+        }
+        let s = &self.data[offset as usize..];
+        let end = s.iter().position(|&b| b == 0).unwrap_or(s.len());
+        ::std::str::from_utf8(&s[..end]).ok()
+    }
+    // The offset of the first string equal to `s`. Only whole strings
+    // match, not the tails of longer ones that a linker may share.
+    pub fn find(&self, s: &str) -> Option<usize> {
+        self.get_entries().into_iter()
+            .find(|&(_, entry)| entry == s)
+            .map(|(offset, _)| offset)
+    }
+    // Every non-empty string in the table with its offset, in table order.
+    // Strings which aren't valid UTF-8 are left out.
+    pub fn get_entries(&self) -> Vec<(usize, &str)> {
+        let mut r = Vec::new();
+        let mut start = 0;
+        for chunk in self.data.split(|&b| b == 0) {
+            if !chunk.is_empty() {
+                if let Ok(s) = ::std::str::from_utf8(chunk) {
+                    r.push((start, s));
+                }
+            }
+            start += chunk.len() + 1;
+        }
+        r
+    }
+}
@@ -0,0 +1,847 @@
+#[macro_export]
+macro_rules! elf_sym {
+    // Elf32_Sym and Elf64_Sym have the same fields in a different order.
+    (ELFCLASS32, $half:ty, $word:ty, $xword:ty, $addr:ty) => {
+        #[derive(Debug, Clone, Copy)]
+        #[repr(C)]
+        pub struct Elf_Sym {
+            st_name: $word,
+            st_value: $addr,
+            st_size: $xword,
+            st_info: u8,
+            st_other: u8,
+            st_shndx: $half,
+        }
+
+        elf_sym!($half, $word, $xword, $addr);
+    };
+    (ELFCLASS64, $half:ty, $word:ty, $xword:ty, $addr:ty) => {
+        #[derive(Debug, Clone, Copy)]
+        #[repr(C)]
+        pub struct Elf_Sym {
+            st_name: $word,
+            st_info: u8,
+            st_other: u8,
+            st_shndx: $half,
+            st_value: $addr,
+            st_size: $xword,
+        }
+
+        elf_sym!($half, $word, $xword, $addr);
+    };
+    ($half:ty, $word:ty, $xword:ty, $addr:ty) => {
+        use ::std::collections::{BTreeMap, BTreeSet};
+        use ::std::io::{self, Read, Seek, Write};
+        use ::to_host::Endianness;
+        use ::to_host::to_host_copy::ToHostCopy;
+        use ::to_host::ToHostCopyStruct;
+        use super::super::elf_shdr_type::ElfShdrType;
+        use super::super::elf_shdr_flags::{ElfShdrFlags, SHF_ALLOC, SHF_WRITE, SHF_EXECINSTR};
+        use super::super::elf_sym_info::{ElfSymType, ElfSymBind, ElfSymVis, get_ppc64_local_entry};
+        use super::super::elf_fortify::FortifyInfo;
+        use super::super::elf_json::JsonSymbol;
+        use super::super::elf_sym_dump::{SymbolBytes, SymbolLookup};
+        use super::super::elf_version::{SymbolVersions, parse_versym};
+        use super::super::elf_version::{parse_verdef, parse_verneed};
+        use super::super::elf_version::{print_versym, print_verdef, print_verneed};
+        use super::super::helpers::{get_str, get_u32, read_bytes, EntryLimit};
+        use super::elf_shdr::{Elf_Shdr, Section, read_section_data, read_shstrtab};
+        use super::helpers::read_syms;
+        use super::primitive::*;
+
+        pub const SHN_UNDEF: u16 = 0;
+        pub const SHN_ABS: u16 = 0xfff1;
+        pub const SHN_COMMON: u16 = 0xfff2;
+        pub const SHN_XINDEX: u16 = 0xffff;
+
+        impl Elf_Sym {
+            #[allow(dead_code)]
+            pub fn get_name(&self) -> $word {
+                self.st_name
+            }
+            #[allow(dead_code)]
+            pub fn get_value(&self) -> $addr {
+                self.st_value
+            }
+            #[allow(dead_code)]
+            pub fn get_size(&self) -> $xword {
+                self.st_size
+            }
+            #[allow(dead_code)]
+            pub fn get_info(&self) -> u8 {
+                self.st_info
+            }
+            #[allow(dead_code)]
+            pub fn get_other(&self) -> u8 {
+                self.st_other
+            }
+            #[allow(dead_code)]
+            pub fn get_shndx(&self) -> $half {
+                self.st_shndx
+            }
+            #[allow(dead_code)]
+            pub fn get_type(&self) -> ElfSymType {
+                ElfSymType::from_info(self.st_info)
+            }
+            #[allow(dead_code)]
+            pub fn get_bind(&self) -> ElfSymBind {
+                ElfSymBind::from_info(self.st_info)
+            }
+            #[allow(dead_code)]
+            pub fn get_visibility(&self) -> ElfSymVis {
+                ElfSymVis::from_other(self.st_other)
+            }
+        }
+
+        impl ToHostCopyStruct for Elf_Sym {
+            fn to_host_copy(&self, endianness: &Endianness) -> Self {
+                let e = endianness;
+                Elf_Sym {
+                    st_name: self.st_name.to_host_copy(e),
+                    st_value: self.st_value.to_host_copy(e),
+                    st_size: self.st_size.to_host_copy(e),
+                    st_info: self.st_info,
+                    st_other: self.st_other,
+                    st_shndx: self.st_shndx.to_host_copy(e),
+                }
+            }
+        }
+
+        // A symbol together with its string table and the sections of the
+        // file, so that its name and section can be had directly. The symbol
+        // is expected to be in host byte order. `xindex` is its entry in the
+        // SHT_SYMTAB_SHNDX section of the table, if the table has one.
+        #[derive(Debug, Clone, Copy)]
+        pub struct Symbol<'a> {
+            sym: &'a Elf_Sym,
+            xindex: Option<u32>,
+            strtab: &'a [u8],
+            sections: &'a [Section<'a>],
+        }
+
+        #[allow(dead_code)]
+        impl<'a> Symbol<'a> {
+            pub fn new(
+                sym: &'a Elf_Sym, xindex: Option<u32>, strtab: &'a [u8],
+                sections: &'a [Section<'a>])
+                -> Symbol<'a>
+            {
+                Symbol { sym: sym, xindex: xindex, strtab: strtab, sections: sections }
+            }
+            pub fn entry(&self) -> &'a Elf_Sym {
+                self.sym
+            }
+            pub fn name(&self) -> &'a str {
+                get_str(self.strtab, self.sym.get_name() as u64)
+            }
+            pub fn kind(&self) -> ElfSymType {
+                self.sym.get_type()
+            }
+            pub fn binding(&self) -> ElfSymBind {
+                self.sym.get_bind()
+            }
+            pub fn visibility(&self) -> ElfSymVis {
+                self.sym.get_visibility()
+            }
+            pub fn value(&self) -> $addr {
+                self.sym.get_value()
+            }
+            pub fn size(&self) -> $xword {
+                self.sym.get_size()
+            }
+            // The section the symbol is defined in. Undefined, absolute and
+            // common symbols, and out-of-range indices, have none.
+            pub fn section(&self) -> Option<Section<'a>> {
+                match self.sym.get_shndx() {
+                    SHN_UNDEF | SHN_ABS | SHN_COMMON => None,
+                    shndx => self.sections.get(resolve_shndx(shndx, self.xindex)).cloned(),
+                }
+            }
+        }
+
+        // The extended section indices of the symbol table at `index`, one
+        // per symbol, from the SHT_SYMTAB_SHNDX section that links to it.
+        // Empty if the table has none.
+        fn read_xindex<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], index: usize, e: &Endianness, reader: &mut R) -> Vec<u32>
+        {
+            let shndx = shdrs.iter().find(|s| {
+                ElfShdrType::new(s.get_type()) == ElfShdrType::SHT_SYMTAB_SHNDX
+                    && s.get_link() as usize == index
+            });
+            let data = match shndx {
+                Some(shdr) => read_section_data(shdr, reader).unwrap_or(Vec::new()),
+                None => return Vec::new(),
+            };
+            (0..data.len() / 4).filter_map(|i| get_u32(&data, i * 4, e)).collect()
+        }
+
+        // st_shndx is SHN_XINDEX when the index doesn't fit, and then the
+        // index is the symbol's entry in SHT_SYMTAB_SHNDX.
+        fn resolve_shndx(shndx: $half, xindex: Option<u32>) -> usize {
+            match (shndx, xindex) {
+                (SHN_XINDEX, Some(index)) => index as usize,
+                _ => shndx as usize,
+            }
+        }
+
+        fn format_shndx(shndx: $half, xindex: Option<u32>) -> String {
+            match shndx {
+                SHN_UNDEF => "UND".to_string(),
+                SHN_ABS => "ABS".to_string(),
+                SHN_COMMON => "COM".to_string(),
+                _ => format!("{}", resolve_shndx(shndx, xindex)),
+            }
+        }
+
+        // Section symbols, which relocatable objects are full of, have no
+        // name of their own and go by the name of their section.
+        fn get_display_name<'a>(
+            sym: &Elf_Sym, xindex: Option<u32>, strtab: &'a [u8], shdrs: &[Elf_Shdr],
+            shstrtab: &'a [u8])
+            -> &'a str
+        {
+            let name = get_str(strtab, sym.get_name() as u64);
+            if !name.is_empty() || sym.get_type() != ElfSymType::STT_SECTION {
+                return name;
+            }
+            match shdrs.get(resolve_shndx(sym.get_shndx(), xindex)) {
+                Some(shdr) => get_str(shstrtab, shdr.get_name() as u64),
+                None => name,
+            }
+        }
+
+        // The value as an offset into the symbol's section, for --sym-relative,
+        // in the style of objdump: `.text+0x10`, or `*ABS*+0x10`, `*UND*` and
+        // `*COM*` for the special section indices. Values of symbols in
+        // other reserved or out-of-range indices are left absolute.
+        fn format_relative_value(
+            sym: &Elf_Sym, xindex: Option<u32>, shdrs: &[Elf_Shdr], shstrtab: &[u8]) -> String
+        {
+            let value = sym.get_value() as u64;
+            match sym.get_shndx() {
+                SHN_UNDEF => "*UND*".to_string(),
+                SHN_ABS => format!("*ABS*+{:#x}", value),
+                SHN_COMMON => "*COM*".to_string(),
+                shndx => match shdrs.get(resolve_shndx(shndx, xindex)) {
+                    Some(shdr) => format!(
+                        "{}+{:#x}", get_str(shstrtab, shdr.get_name() as u64),
+                        value.wrapping_sub(shdr.get_addr() as u64)),
+                    None => format!("{:#x}", value),
+                },
+            }
+        }
+
+        // The Vis column, with the other bits of st_other the machine gives
+        // a meaning to as readelf shows them.
+        fn format_visibility(machine: u16, sym: &Elf_Sym) -> String {
+            match get_ppc64_local_entry(machine, sym.get_other()) {
+                Some(offset) =>
+                    format!("{:<7} [<localentry>: {}] ", sym.get_visibility(), offset),
+                None => format!("{:<7}", sym.get_visibility()),
+            }
+        }
+
+        #[allow(dead_code)]
+        pub fn print_symbols<R: Read + Seek, W: Write>(
+            machine: u16, shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R,
+            out: &mut W, legend: bool, relative: bool, max_entries: Option<usize>)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let width = ::std::mem::size_of::<$addr>() * 2;
+
+            let mut types = BTreeSet::new();
+            let mut binds = BTreeSet::new();
+            let mut visibilities = BTreeSet::new();
+            let mut tables = 0;
+
+            for (index, shdr) in shdrs.iter().enumerate() {
+                let sh_type = ElfShdrType::new(shdr.get_type());
+                if sh_type != ElfShdrType::SHT_SYMTAB
+                && sh_type != ElfShdrType::SHT_DYNSYM {
+                    continue;
+                }
+
+                let syms = match read_syms(shdr, e, reader) {
+                    Ok(syms) => syms,
+                    Err(err) => {
+                        warning!(
+                            "symbol table {}: {}",
+                            get_str(&shstrtab, shdr.get_name() as u64), err);
+                        continue;
+                    }
+                };
+                let strtab = match shdrs.get(shdr.get_link() as usize) {
+                    Some(link) => read_section_data(link, reader).unwrap_or(Vec::new()),
+                    None => Vec::new(),
+                };
+                let xindex = read_xindex(&shdrs, index, e, reader);
+
+                // Without the titles an empty line still tells one table
+                // from the next
+                if tables == 0 {
+                    try!(heading!(out, ""));
+                } else {
+                    try!(writeln!(out, ""));
+                }
+                tables += 1;
+                try!(heading!(
+                    out,
+                    "Symbol table '{}' contains {} entries:",
+                    get_str(&shstrtab, shdr.get_name() as u64), syms.len()));
+                // The locations make a column as wide as the longest
+                let locations: Vec<String> = if relative {
+                    syms.iter().enumerate()
+                        .map(|(i, sym)| format_relative_value(
+                            sym, xindex.get(i).cloned(), &shdrs, &shstrtab))
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                let location_width = locations.iter().map(|l| l.len()).max().unwrap_or(0).max(width);
+                if relative {
+                    try!(heading!(
+                        out,
+                        "   Num: {:<w$} Size Type    Bind   Vis      Ndx Name",
+                        "Location", w = location_width));
+                } else {
+                    try!(heading!(
+                        out,
+                        "   Num:    Value{:w$} Size Type    Bind   Vis      Ndx Name",
+                        "", w = width - 7));
+                }
+                let mut limit = EntryLimit::new(max_entries);
+                for (i, sym) in syms.iter().enumerate() {
+                    types.insert(sym.get_type());
+                    binds.insert(sym.get_bind());
+                    visibilities.insert(sym.get_visibility());
+                    if !limit.take() {
+                        continue;
+                    }
+                    let value = if relative {
+                        format!("{:<w$}", locations[i], w = location_width)
+                    } else {
+                        format!("{:0w$x}", sym.get_value(), w = width)
+                    };
+                    try!(writeln!(
+                        out,
+                        "{:6}: {} {:5} {:<7} {:<6} {} {:>4} {}",
+                        i,
+                        value,
+                        sym.get_size(),
+                        sym.get_type(),
+                        sym.get_bind(),
+                        format_visibility(machine, sym),
+                        format_shndx(sym.get_shndx(), xindex.get(i).cloned()),
+                        get_display_name(
+                            sym, xindex.get(i).cloned(), &strtab, &shdrs, &shstrtab)));
+                }
+                try!(limit.print_omitted(out));
+            }
+
+            if legend && !types.is_empty() {
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "Key to symbol codes:"));
+                try!(writeln!(out, "  Type:"));
+                for t in &types {
+                    try!(writeln!(out, "    {:<9} {}", t, t.describe()));
+                }
+                try!(writeln!(out, "  Bind:"));
+                for b in &binds {
+                    try!(writeln!(out, "    {:<9} {}", b, b.describe()));
+                }
+                try!(writeln!(out, "  Vis:"));
+                for v in &visibilities {
+                    try!(writeln!(out, "    {:<9} {}", v, v.describe()));
+                }
+            }
+            Ok(())
+        }
+
+        // The bytes of the symbol called `name`, for --dump-symbol. .symtab
+        // is searched before .dynsym, and a definition is preferred to an
+        // undefined reference of the same name.
+        #[allow(dead_code)]
+        pub fn get_symbol_bytes<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R, name: &str)
+            -> SymbolLookup
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let mut tables: Vec<(usize, &Elf_Shdr)> = shdrs.iter().enumerate()
+                .filter(|&(_, s)| {
+                    let sh_type = ElfShdrType::new(s.get_type());
+                    sh_type == ElfShdrType::SHT_SYMTAB || sh_type == ElfShdrType::SHT_DYNSYM
+                })
+                .collect();
+            tables.sort_by_key(|&(_, s)| ElfShdrType::new(s.get_type()) != ElfShdrType::SHT_SYMTAB);
+
+            let mut found = None;
+            let mut undefined = false;
+            for (index, shdr) in tables {
+                let syms = match read_syms(shdr, e, reader) {
+                    Ok(syms) => syms,
+                    Err(_) => continue,
+                };
+                let strtab = match shdrs.get(shdr.get_link() as usize) {
+                    Some(link) => read_section_data(link, reader).unwrap_or(Vec::new()),
+                    None => Vec::new(),
+                };
+                let xindex = read_xindex(&shdrs, index, e, reader);
+                let (defined, references): (Vec<(Elf_Sym, Option<u32>)>, Vec<_>) = syms.into_iter()
+                    .enumerate()
+                    .filter(|&(_, sym)| get_str(&strtab, sym.get_name() as u64) == name)
+                    .map(|(i, sym)| (sym, xindex.get(i).cloned()))
+                    .partition(|&(sym, _)| sym.get_shndx() != SHN_UNDEF);
+                undefined |= !references.is_empty();
+                if let Some(&found_sym) = defined.first() {
+                    found = Some(found_sym);
+                    break;
+                }
+            }
+            let (sym, xindex) = match (found, undefined) {
+                (Some(sym), _) => sym,
+                (None, true) => return SymbolLookup::NotInFile,
+                (None, false) => return SymbolLookup::NotFound,
+            };
+
+            let size = sym.get_size() as u64;
+            if size == 0 {
+                return SymbolLookup::NoSize;
+            }
+            let shdr = match sym.get_shndx() {
+                SHN_UNDEF | SHN_ABS | SHN_COMMON => return SymbolLookup::NotInFile,
+                shndx => match shdrs.get(resolve_shndx(shndx, xindex)) {
+                    Some(shdr) => shdr,
+                    None => return SymbolLookup::NotInFile,
+                },
+            };
+            if ElfShdrType::new(shdr.get_type()) == ElfShdrType::SHT_NOBITS {
+                return SymbolLookup::NotInFile;
+            }
+            // Relocatable objects have section-relative values and sections
+            // at address 0, so this is the offset into the section either way
+            let offset = (sym.get_value() as u64).wrapping_sub(shdr.get_addr() as u64);
+            match offset.checked_add(size) {
+                Some(end) if end <= shdr.get_size() as u64 => {}
+                _ => return SymbolLookup::OutsideSection,
+            }
+            SymbolLookup::Found(SymbolBytes {
+                name: name.to_string(),
+                symbol_type: sym.get_type(),
+                section: get_str(&shstrtab, shdr.get_name() as u64).to_string(),
+                address: sym.get_value() as u64,
+                data: match read_bytes(reader, shdr.get_offset() as u64 + offset, size) {
+                    Ok(data) => data,
+                    Err(err) => return SymbolLookup::Unreadable(err),
+                },
+            })
+        }
+
+        // The symbols of every symbol table as they appear in the JSON and
+        // CSV reports, table by table.
+        #[allow(dead_code)]
+        pub fn get_json_symbols<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R)
+            -> Vec<JsonSymbol>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let mut r = Vec::new();
+
+            for (index, shdr) in shdrs.iter().enumerate() {
+                let sh_type = ElfShdrType::new(shdr.get_type());
+                if sh_type != ElfShdrType::SHT_SYMTAB
+                && sh_type != ElfShdrType::SHT_DYNSYM {
+                    continue;
+                }
+                let table = get_str(&shstrtab, shdr.get_name() as u64);
+                let syms = match read_syms(shdr, e, reader) {
+                    Ok(syms) => syms,
+                    Err(err) => {
+                        warning!("symbol table {}: {}", table, err);
+                        continue;
+                    }
+                };
+                let strtab = match shdrs.get(shdr.get_link() as usize) {
+                    Some(link) => read_section_data(link, reader).unwrap_or(Vec::new()),
+                    None => Vec::new(),
+                };
+                let xindex = read_xindex(&shdrs, index, e, reader);
+                for (i, sym) in syms.iter().enumerate() {
+                    r.push(JsonSymbol {
+                        table: table.to_string(),
+                        index: i,
+                        name: get_display_name(
+                            sym, xindex.get(i).cloned(), &strtab, &shdrs, &shstrtab).to_string(),
+                        value: sym.get_value() as u64,
+                        size: sym.get_size() as u64,
+                        symbol_type: format!("{}", sym.get_type()),
+                        bind: format!("{}", sym.get_bind()),
+                        visibility: format!("{}", sym.get_visibility()),
+                        section: format_shndx(sym.get_shndx(), xindex.get(i).cloned()),
+                    });
+                }
+            }
+            r
+        }
+
+        // One line per distinct symbol name across all symbol tables, with
+        // how often it occurs, whether any occurrence defines it and which
+        // bindings it has. Unnamed symbols are left out.
+        #[allow(dead_code)]
+        pub fn print_compact_symbols<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R,
+            out: &mut W, max_entries: Option<usize>)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+
+            // Name -> (count, defined, bindings)
+            let mut names: BTreeMap<String, (u64, bool, BTreeSet<ElfSymBind>)> =
+                BTreeMap::new();
+            for shdr in &shdrs {
+                let sh_type = ElfShdrType::new(shdr.get_type());
+                if sh_type != ElfShdrType::SHT_SYMTAB
+                && sh_type != ElfShdrType::SHT_DYNSYM {
+                    continue;
+                }
+
+                let syms = match read_syms(shdr, e, reader) {
+                    Ok(syms) => syms,
+                    Err(err) => {
+                        warning!(
+                            "symbol table {}: {}",
+                            get_str(&shstrtab, shdr.get_name() as u64), err);
+                        continue;
+                    }
+                };
+                let strtab = match shdrs.get(shdr.get_link() as usize) {
+                    Some(link) => read_section_data(link, reader).unwrap_or(Vec::new()),
+                    None => Vec::new(),
+                };
+                for sym in &syms {
+                    let name = get_str(&strtab, sym.get_name() as u64);
+                    if name.is_empty() {
+                        continue;
+                    }
+                    let entry = names.entry(name.to_string())
+                        .or_insert((0, false, BTreeSet::new()));
+                    entry.0 += 1;
+                    entry.1 |= sym.get_shndx() != SHN_UNDEF;
+                    entry.2.insert(sym.get_bind());
+                }
+            }
+
+            try!(heading!(out, ""));
+            try!(heading!(out, "Symbols by name ({} distinct names):", names.len()));
+            try!(heading!(out, "  Count Defined Bind          Name"));
+            let mut limit = EntryLimit::new(max_entries);
+            for (name, &(count, defined, ref binds)) in &names {
+                if !limit.take() {
+                    continue;
+                }
+                let binds: Vec<String> = binds.iter().map(|b| b.to_string()).collect();
+                try!(writeln!(
+                    out,
+                    "  {:5} {:<7} {:<13} {}",
+                    count,
+                    if defined { "yes" } else { "no" },
+                    binds.join(","),
+                    name));
+            }
+            limit.print_omitted(out)
+        }
+
+        // The versions of the symbols in .dynsym, from the version sections.
+        // Section headers are expected to be in host byte order.
+        #[allow(dead_code)]
+        pub fn read_symbol_versions<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], e: &Endianness, reader: &mut R)
+            -> SymbolVersions
+        {
+            let mut versym = Vec::new();
+            let mut defined = Vec::new();
+            let mut needed = Vec::new();
+            for shdr in shdrs {
+                let sh_type = ElfShdrType::new(shdr.get_type());
+                if sh_type == ElfShdrType::SHT_GNU_VERSYM {
+                    versym = parse_versym(&read_section_data(shdr, reader).unwrap_or(Vec::new()), e);
+                    continue;
+                }
+                if sh_type != ElfShdrType::SHT_GNU_VERDEF
+                && sh_type != ElfShdrType::SHT_GNU_VERNEED {
+                    continue;
+                }
+                let data = read_section_data(shdr, reader).unwrap_or(Vec::new());
+                let strtab = match shdrs.get(shdr.get_link() as usize) {
+                    Some(link) => read_section_data(link, reader).unwrap_or(Vec::new()),
+                    None => Vec::new(),
+                };
+                if sh_type == ElfShdrType::SHT_GNU_VERDEF {
+                    defined.extend(parse_verdef(&data, &strtab, e));
+                } else {
+                    needed.extend(parse_verneed(&data, &strtab, e));
+                }
+            }
+            SymbolVersions::new(versym, defined, needed)
+        }
+
+        // All the symbol versioning sections in the order of the section
+        // table, like readelf -V.
+        #[allow(dead_code)]
+        pub fn print_version_info<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], shstrndx: u16, e: &Endianness, reader: &mut R,
+            out: &mut W, max_entries: Option<usize>)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let shstrtab = read_shstrtab(&shdrs, shstrndx, reader);
+            let versions = read_symbol_versions(&shdrs, e, reader);
+            let mut found = false;
+
+            for shdr in &shdrs {
+                let sh_type = ElfShdrType::new(shdr.get_type());
+                let kind = match sh_type {
+                    ElfShdrType::SHT_GNU_VERSYM => "symbols",
+                    ElfShdrType::SHT_GNU_VERDEF => "definition",
+                    ElfShdrType::SHT_GNU_VERNEED => "needs",
+                    _ => continue,
+                };
+                found = true;
+                let data = read_section_data(shdr, reader).unwrap_or(Vec::new());
+                let link = shdrs.get(shdr.get_link() as usize);
+                let count = match sh_type {
+                    ElfShdrType::SHT_GNU_VERSYM => data.len() / 2,
+                    _ => shdr.get_info() as usize,
+                };
+
+                try!(heading!(out, ""));
+                try!(heading!(
+                    out, "Version {} section '{}' contains {} {}:",
+                    kind, get_str(&shstrtab, shdr.get_name() as u64), count,
+                    if count == 1 { "entry" } else { "entries" }));
+                try!(heading!(
+                    out, " Addr: {:#018x}  Offset: {:#010x}  Link: {} ({})",
+                    shdr.get_addr(), shdr.get_offset(), shdr.get_link(),
+                    link.map(|l| get_str(&shstrtab, l.get_name() as u64)).unwrap_or("")));
+
+                let strtab = match link {
+                    Some(link) if sh_type != ElfShdrType::SHT_GNU_VERSYM =>
+                        read_section_data(link, reader).unwrap_or(Vec::new()),
+                    _ => Vec::new(),
+                };
+                match sh_type {
+                    ElfShdrType::SHT_GNU_VERSYM =>
+                        try!(print_versym(&parse_versym(&data, e), &versions, out, max_entries)),
+                    ElfShdrType::SHT_GNU_VERDEF =>
+                        try!(print_verdef(&data, &strtab, count, e, out, max_entries)),
+                    _ => try!(print_verneed(&data, &strtab, count, e, out, max_entries)),
+                }
+            }
+            if !found {
+                try!(writeln!(out, ""));
+                try!(writeln!(out, "No version information found in this file."));
+            }
+            Ok(())
+        }
+
+        // The symbols of .dynsym with their indices, its string table and
+        // the symbol versions, or None if the file has no .dynsym.
+        fn read_dynsyms<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], e: &Endianness, reader: &mut R)
+            -> Option<(Vec<Elf_Sym>, Vec<u8>, SymbolVersions)>
+        {
+            let dynsym = match shdrs.iter().find(
+                |s| ElfShdrType::new(s.get_type()) == ElfShdrType::SHT_DYNSYM) {
+                Some(dynsym) => dynsym,
+                None => return None,
+            };
+            let syms = match read_syms(dynsym, e, reader) {
+                Ok(syms) => syms,
+                Err(err) => {
+                    warning!("dynamic symbol table: {}", err);
+                    return None;
+                }
+            };
+            let strtab = match shdrs.get(dynsym.get_link() as usize) {
+                Some(link) => read_section_data(link, reader).unwrap_or(Vec::new()),
+                None => Vec::new(),
+            };
+            Some((syms, strtab, read_symbol_versions(shdrs, e, reader)))
+        }
+
+        // The symbols the file takes from its dependencies, as
+        // `nm -D --undefined-only` lists them.
+        #[allow(dead_code)]
+        pub fn print_imports<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], e: &Endianness, reader: &mut R, out: &mut W,
+            max_entries: Option<usize>)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let width = ::std::mem::size_of::<$addr>() * 2;
+            let (syms, strtab, versions) = match read_dynsyms(&shdrs, e, reader) {
+                Some(dynsyms) => dynsyms,
+                None => {
+                    warning!("no dynamic symbol table");
+                    return Ok(());
+                }
+            };
+
+            let mut imports = Vec::new();
+            for (i, sym) in syms.iter().enumerate() {
+                let bind = sym.get_bind();
+                if i == 0 || sym.get_shndx() != SHN_UNDEF
+                || (bind != ElfSymBind::STB_GLOBAL && bind != ElfSymBind::STB_WEAK) {
+                    continue;
+                }
+                let name = get_str(&strtab, sym.get_name() as u64);
+                let versioned = match versions.get_version(i) {
+                    Some((version, _)) => format!("{}@{}", name, version),
+                    None => name.to_string(),
+                };
+                let letter = match (bind, sym.get_type()) {
+                    (ElfSymBind::STB_WEAK, ElfSymType::STT_OBJECT) => 'v',
+                    (ElfSymBind::STB_WEAK, _) => 'w',
+                    _ => 'U',
+                };
+                imports.push((name, versioned, letter));
+            }
+            // By name, then in table order
+            imports.sort_by(|a, b| a.0.cmp(b.0));
+
+            let mut limit = EntryLimit::new(max_entries);
+            for (_, name, letter) in imports {
+                if !limit.take() {
+                    continue;
+                }
+                try!(writeln!(out, "{:w$} {} {}", "", letter, name, w = width));
+            }
+            limit.print_omitted(out)
+        }
+
+        // The letter nm gives a defined symbol: what kind of section it is
+        // in, upper case unless the symbol is local.
+        fn get_nm_letter(sym: &Elf_Sym, shdrs: &[Elf_Shdr]) -> char {
+            let bind = sym.get_bind();
+            if sym.get_type() == ElfSymType::STT_GNU_IFUNC {
+                return 'i';
+            }
+            if bind == ElfSymBind::STB_WEAK {
+                return if sym.get_type() == ElfSymType::STT_OBJECT { 'V' } else { 'W' };
+            }
+            if bind == ElfSymBind::STB_GNU_UNIQUE {
+                return 'u';
+            }
+            let letter = match sym.get_shndx() {
+                SHN_ABS => 'A',
+                SHN_COMMON => 'C',
+                shndx => match shdrs.get(shndx as usize) {
+                    Some(shdr) => {
+                        let flags = ElfShdrFlags::new(shdr.get_flags() as u64);
+                        let sh_type = ElfShdrType::new(shdr.get_type());
+                        if flags.contains(SHF_EXECINSTR) {
+                            'T'
+                        } else if !flags.contains(SHF_ALLOC) {
+                            'N'
+                        } else if sh_type == ElfShdrType::SHT_NOBITS {
+                            'B'
+                        } else if !flags.contains(SHF_WRITE) {
+                            'R'
+                        } else {
+                            'D'
+                        }
+                    }
+                    None => '?',
+                },
+            };
+            if bind == ElfSymBind::STB_LOCAL {
+                letter.to_ascii_lowercase()
+            } else {
+                letter
+            }
+        }
+
+        // The symbols the file provides to others, as
+        // `nm -D --defined-only` lists them. Hidden and internal symbols
+        // can't be bound to from outside and are left out.
+        #[allow(dead_code)]
+        pub fn print_exports<R: Read + Seek, W: Write>(
+            shdrs: &[Elf_Shdr], e: &Endianness, reader: &mut R, out: &mut W,
+            max_entries: Option<usize>)
+            -> io::Result<()>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let width = ::std::mem::size_of::<$addr>() * 2;
+            let (syms, strtab, versions) = match read_dynsyms(&shdrs, e, reader) {
+                Some(dynsyms) => dynsyms,
+                None => {
+                    warning!("no dynamic symbol table");
+                    return Ok(());
+                }
+            };
+
+            let mut exports = Vec::new();
+            for (i, sym) in syms.iter().enumerate() {
+                let bind = sym.get_bind();
+                let vis = sym.get_visibility();
+                if sym.get_shndx() == SHN_UNDEF
+                || (bind != ElfSymBind::STB_GLOBAL && bind != ElfSymBind::STB_WEAK
+                    && bind != ElfSymBind::STB_GNU_UNIQUE)
+                || vis == ElfSymVis::STV_HIDDEN || vis == ElfSymVis::STV_INTERNAL {
+                    continue;
+                }
+                let name = get_str(&strtab, sym.get_name() as u64);
+                let versioned = match versions.get_version(i) {
+                    // A version definition has a symbol of its own name
+                    Some((version, _)) if version == name => name.to_string(),
+                    Some((version, default)) => format!(
+                        "{}{}{}", name, if default { "@@" } else { "@" }, version),
+                    None => name.to_string(),
+                };
+                exports.push((name, sym.get_value(), versioned, get_nm_letter(sym, &shdrs)));
+            }
+            // By name, then in table order
+            exports.sort_by(|a, b| a.0.cmp(b.0));
+
+            let mut limit = EntryLimit::new(max_entries);
+            for (_, value, name, letter) in exports {
+                if !limit.take() {
+                    continue;
+                }
+                try!(writeln!(out, "{:0w$x} {} {}", value, letter, name, w = width));
+            }
+            limit.print_omitted(out)
+        }
+
+        // Judges _FORTIFY_SOURCE by the functions imported through .dynsym.
+        // None if the file has no .dynsym.
+        #[allow(dead_code)]
+        pub fn get_fortify_info<R: Read + Seek>(
+            shdrs: &[Elf_Shdr], e: &Endianness, reader: &mut R)
+            -> Option<FortifyInfo>
+        {
+            let shdrs: Vec<Elf_Shdr> =
+                shdrs.iter().map(|s| s.to_host_copy(e)).collect();
+            let (syms, strtab, _) = match read_dynsyms(&shdrs, e, reader) {
+                Some(dynsyms) => dynsyms,
+                None => return None,
+            };
+            let imports = syms.iter()
+                .filter(|sym| sym.get_shndx() == SHN_UNDEF)
+                .map(|sym| get_str(&strtab, sym.get_name() as u64));
+            Some(FortifyInfo::new(imports))
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use ::std::io::{self, Write};
+use super::elf_error::ElfError;
+use super::elf_sym_info::ElfSymType;
+use super::helpers::print_hex_lines;
+
+// The bytes of a symbol, for --dump-symbol: the code of a function or the
+// initial value of an object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolBytes {
+    pub name: String,
+    pub symbol_type: ElfSymType,
+    pub section: String,
+    // st_value, which is an offset into the section in relocatable objects
+    pub address: u64,
+    pub data: Vec<u8>,
+}
+
+// What looking a symbol up by name for --dump-symbol found.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolLookup {
+    Found(SymbolBytes),
+    NotFound,
+    // st_size is 0, so there is nothing to say where the symbol ends
+    NoSize,
+    // Undefined, absolute, common, or in a section without file contents
+    // such as .bss
+    NotInFile,
+    // st_value and st_size reach past the end of the section
+    OutsideSection,
+    // The section's offset can't be read from
+    Unreadable(ElfError),
+}
+
+#[allow(dead_code)]
+pub fn print_symbol_bytes<W: Write>(symbol: &SymbolBytes, out: &mut W) -> io::Result<()> {
+    try!(writeln!(out, ""));
+    try!(writeln!(
+        out, "Hex dump of symbol '{}' ({}, {} bytes in {}):",
+        symbol.name, symbol.symbol_type, symbol.data.len(), symbol.section));
+    try!(print_hex_lines(&symbol.data, symbol.address, out));
+    writeln!(out, "")
+}
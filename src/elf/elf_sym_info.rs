@@ -0,0 +1,160 @@
+use ::std::fmt::{Display, Formatter};
+
+// Symbol type, the low nibble of st_info.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct ElfSymType {
+    data: u8,
+}
+
+#[allow(dead_code)]
+impl ElfSymType {
+    pub const STT_NOTYPE: ElfSymType = ElfSymType { data: 0 };
+    pub const STT_OBJECT: ElfSymType = ElfSymType { data: 1 };
+    pub const STT_FUNC: ElfSymType = ElfSymType { data: 2 };
+    pub const STT_SECTION: ElfSymType = ElfSymType { data: 3 };
+    pub const STT_FILE: ElfSymType = ElfSymType { data: 4 };
+    pub const STT_COMMON: ElfSymType = ElfSymType { data: 5 };
+    pub const STT_TLS: ElfSymType = ElfSymType { data: 6 };
+    pub const STT_GNU_IFUNC: ElfSymType = ElfSymType { data: 10 };
+
+    pub fn from_info(st_info: u8) -> ElfSymType {
+        ElfSymType { data: st_info & 0xf }
+    }
+    pub fn get_value(&self) -> u8 {
+        self.data
+    }
+    pub fn describe(&self) -> &'static str {
+        match *self {
+            ElfSymType::STT_NOTYPE => "type is not specified",
+            ElfSymType::STT_OBJECT => "data object, such as a variable or an array",
+            ElfSymType::STT_FUNC => "function or other executable code",
+            ElfSymType::STT_SECTION => "section, used for relocations",
+            ElfSymType::STT_FILE => "name of the source file of the object",
+            ElfSymType::STT_COMMON => "uninitialized common block",
+            ElfSymType::STT_TLS => "thread-local storage entity",
+            ElfSymType::STT_GNU_IFUNC =>
+                "indirect function, resolved by calling it at load time",
+            _ => "OS- or processor-specific type",
+        }
+    }
+}
+
+impl Display for ElfSymType {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            ElfSymType::STT_NOTYPE => "NOTYPE",
+            ElfSymType::STT_OBJECT => "OBJECT",
+            ElfSymType::STT_FUNC => "FUNC",
+            ElfSymType::STT_SECTION => "SECTION",
+            ElfSymType::STT_FILE => "FILE",
+            ElfSymType::STT_COMMON => "COMMON",
+            ElfSymType::STT_TLS => "TLS",
+            ElfSymType::STT_GNU_IFUNC => "IFUNC",
+            ElfSymType { data } => return fmt.pad(&format!("<{}>", data)),
+        };
+        fmt.pad(s)
+    }
+}
+
+// Symbol binding, the high nibble of st_info.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct ElfSymBind {
+    data: u8,
+}
+
+#[allow(dead_code)]
+impl ElfSymBind {
+    pub const STB_LOCAL: ElfSymBind = ElfSymBind { data: 0 };
+    pub const STB_GLOBAL: ElfSymBind = ElfSymBind { data: 1 };
+    pub const STB_WEAK: ElfSymBind = ElfSymBind { data: 2 };
+    pub const STB_GNU_UNIQUE: ElfSymBind = ElfSymBind { data: 10 };
+
+    pub fn from_info(st_info: u8) -> ElfSymBind {
+        ElfSymBind { data: st_info >> 4 }
+    }
+    pub fn get_value(&self) -> u8 {
+        self.data
+    }
+    pub fn describe(&self) -> &'static str {
+        match *self {
+            ElfSymBind::STB_LOCAL => "not visible outside the object file",
+            ElfSymBind::STB_GLOBAL => "visible to all object files being combined",
+            ElfSymBind::STB_WEAK => "global, but may be overridden by another definition",
+            ElfSymBind::STB_GNU_UNIQUE =>
+                "global, and the only definition in the whole process",
+            _ => "OS- or processor-specific binding",
+        }
+    }
+}
+
+impl Display for ElfSymBind {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            ElfSymBind::STB_LOCAL => "LOCAL",
+            ElfSymBind::STB_GLOBAL => "GLOBAL",
+            ElfSymBind::STB_WEAK => "WEAK",
+            ElfSymBind::STB_GNU_UNIQUE => "UNIQUE",
+            ElfSymBind { data } => return fmt.pad(&format!("<{}>", data)),
+        };
+        fmt.pad(s)
+    }
+}
+
+// On 64-bit PowerPC (ELFv2), bits 5..7 of st_other encode how far past the
+// global entry point of a function its local entry point is, which callers
+// sharing its TOC branch to. None for a symbol with the bits clear.
+const EM_PPC64: u16 = 21;
+const STO_PPC64_LOCAL_BIT: u8 = 5;
+const STO_PPC64_LOCAL_MASK: u8 = 0xe0;
+
+#[allow(dead_code)]
+pub fn get_ppc64_local_entry(machine: u16, st_other: u8) -> Option<u32> {
+    if machine != EM_PPC64 || st_other & STO_PPC64_LOCAL_MASK == 0 {
+        return None;
+    }
+    // 1 means there is a single entry point, 2..6 that the local one is
+    // 1 << n bytes past the global one
+    let n = (st_other & STO_PPC64_LOCAL_MASK) >> STO_PPC64_LOCAL_BIT;
+    Some(((1u32 << n) >> 2) << 2)
+}
+
+// Symbol visibility, the low two bits of st_other.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct ElfSymVis {
+    data: u8,
+}
+
+#[allow(dead_code)]
+impl ElfSymVis {
+    pub const STV_DEFAULT: ElfSymVis = ElfSymVis { data: 0 };
+    pub const STV_INTERNAL: ElfSymVis = ElfSymVis { data: 1 };
+    pub const STV_HIDDEN: ElfSymVis = ElfSymVis { data: 2 };
+    pub const STV_PROTECTED: ElfSymVis = ElfSymVis { data: 3 };
+
+    pub fn from_other(st_other: u8) -> ElfSymVis {
+        ElfSymVis { data: st_other & 0x3 }
+    }
+    pub fn get_value(&self) -> u8 {
+        self.data
+    }
+    pub fn describe(&self) -> &'static str {
+        match *self {
+            ElfSymVis::STV_DEFAULT => "visibility is given by the binding",
+            ElfSymVis::STV_INTERNAL => "hidden, with processor-specific extra meaning",
+            ElfSymVis::STV_HIDDEN => "not visible outside the component defining it",
+            _ => "visible outside, but always bound within the component",
+        }
+    }
+}
+
+impl Display for ElfSymVis {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            ElfSymVis::STV_DEFAULT => "DEFAULT",
+            ElfSymVis::STV_INTERNAL => "INTERNAL",
+            ElfSymVis::STV_HIDDEN => "HIDDEN",
+            _ => "PROTECTED",
+        };
+        fmt.pad(s)
+    }
+}
@@ -0,0 +1,64 @@
+use ::std::fmt::{Display, Formatter};
+use super::elf_ehdr_machine::{get_machine_string, machine_short_name};
+use super::elf_ei_class::ElfEiClass;
+use super::elf_ei_data::ElfEiData;
+use super::elf_ei_os_abi::ElfEiOsAbi;
+
+// What a file was built for: the header fields callers usually need together
+// to decide whether and how to handle it. The machine is the raw e_machine,
+// as files name machines newer than the table in elf_ehdr_machine.
+#[derive(Debug, Clone, Copy)]
+pub struct TargetInfo {
+    machine: u16,
+    class: ElfEiClass,
+    data: ElfEiData,
+    os_abi: ElfEiOsAbi,
+}
+
+#[allow(dead_code)]
+impl TargetInfo {
+    pub fn new(
+        machine: u16, class: ElfEiClass, data: ElfEiData,
+        os_abi: ElfEiOsAbi)
+        -> TargetInfo
+    {
+        TargetInfo {
+            machine: machine,
+            class: class,
+            data: data,
+            os_abi: os_abi,
+        }
+    }
+    pub fn get_machine(&self) -> u16 {
+        self.machine
+    }
+    pub fn get_class(&self) -> ElfEiClass {
+        self.class
+    }
+    pub fn get_data(&self) -> ElfEiData {
+        self.data
+    }
+    pub fn get_os_abi(&self) -> ElfEiOsAbi {
+        self.os_abi
+    }
+}
+
+// Renders as e.g. `x86-64 / ELF64 / little-endian / GNU`.
+impl Display for TargetInfo {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        match machine_short_name(self.machine) {
+            Some(name) => try!(write!(fmt, "{}", name)),
+            None => try!(write!(fmt, "{}", get_machine_string(self.machine))),
+        }
+        try!(write!(fmt, " / {} / ", self.class));
+        match self.data {
+            ElfEiData::ELFDATA2LSB => try!(write!(fmt, "little-endian")),
+            ElfEiData::ELFDATA2MSB => try!(write!(fmt, "big-endian")),
+            data => try!(write!(fmt, "{}", data)),
+        }
+        match self.os_abi.get_short_name() {
+            Some(name) => write!(fmt, " / {}", name),
+            None => write!(fmt, " / {}", self.os_abi.get_description(self.machine)),
+        }
+    }
+}
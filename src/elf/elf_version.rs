@@ -0,0 +1,298 @@
+use ::std::collections::BTreeMap;
+use ::std::io::{self, Write};
+use ::to_host::Endianness;
+use super::helpers::{get_u16, get_u32, get_str, EntryLimit};
+
+// Symbol versioning (the GNU extension): .gnu.version holds one index per
+// dynamic symbol, and .gnu.version_d / .gnu.version_r name the indices the
+// file defines and the ones it needs from its dependencies. The structures
+// are the same for both classes.
+
+// Indices 0 and 1 mean "local" and "global": the symbol has no version.
+const VER_NDX_GLOBAL: u16 = 1;
+// Set on a defined symbol that isn't the default version of its name.
+const VERSYM_HIDDEN: u16 = 0x8000;
+
+// vd_flags and vna_flags
+const VER_FLG_BASE: u16 = 0x1;
+const VER_FLG_WEAK: u16 = 0x2;
+const VER_FLG_INFO: u16 = 0x4;
+
+const VERDEF_SIZE: usize = 20;
+const VERDAUX_SIZE: usize = 8;
+const VERNEED_SIZE: usize = 16;
+const VERNAUX_SIZE: usize = 16;
+
+#[derive(Debug, Clone, Default)]
+pub struct SymbolVersions {
+    versym: Vec<u16>,
+    // Index -> (name, whether the file defines it)
+    names: BTreeMap<u16, (String, bool)>,
+}
+
+#[allow(dead_code)]
+impl SymbolVersions {
+    pub fn new(
+        versym: Vec<u16>, defined: Vec<(u16, String)>, needed: Vec<(u16, String)>)
+        -> SymbolVersions
+    {
+        let mut names = BTreeMap::new();
+        for (index, name) in defined {
+            names.insert(index, (name, true));
+        }
+        for (index, name) in needed {
+            names.insert(index, (name, false));
+        }
+        SymbolVersions { versym: versym, names: names }
+    }
+    // The version of the symbol with the given index in .dynsym, and
+    // whether it is the default version of the name, i.e. one the file
+    // defines and doesn't hide.
+    pub fn get_version(&self, sym_index: usize) -> Option<(&str, bool)> {
+        let versym = match self.versym.get(sym_index) {
+            Some(&versym) => versym,
+            None => return None,
+        };
+        let index = versym & !VERSYM_HIDDEN;
+        if index <= VER_NDX_GLOBAL {
+            return None;
+        }
+        self.names.get(&index).map(|&(ref name, defined)| {
+            (name.as_str(), defined && (versym & VERSYM_HIDDEN) == 0)
+        })
+    }
+    // The name of a version index, whether defined or needed.
+    pub fn get_name(&self, index: u16) -> Option<&str> {
+        self.names.get(&(index & !VERSYM_HIDDEN)).map(|&(ref name, _)| name.as_str())
+    }
+}
+
+// Flags of a version definition or requirement the way readelf spells them.
+#[allow(dead_code)]
+pub fn get_version_flags_string(flags: u16) -> String {
+    if flags == 0 {
+        return "none".to_string();
+    }
+    let mut names = Vec::new();
+    for &(flag, name) in &[(VER_FLG_BASE, "BASE"), (VER_FLG_WEAK, "WEAK"), (VER_FLG_INFO, "INFO")] {
+        if flags & flag != 0 {
+            names.push(name);
+        }
+    }
+    if flags & !(VER_FLG_BASE | VER_FLG_WEAK | VER_FLG_INFO) != 0 {
+        names.push("<unknown>");
+    }
+    names.join(" | ")
+}
+
+// Offsets into the version sections are printed like readelf's "%#06x",
+// which has no 0x for zero.
+fn get_offset_string(offset: usize) -> String {
+    if offset == 0 {
+        "000000".to_string()
+    } else {
+        format!("{:#06x}", offset)
+    }
+}
+
+// The .gnu.version array, four entries a line, as readelf -V prints it.
+#[allow(dead_code)]
+pub fn print_versym<W: Write>(
+    versym: &[u16], versions: &SymbolVersions, out: &mut W, max_entries: Option<usize>)
+    -> io::Result<()>
+{
+    let mut limit = EntryLimit::new(max_entries);
+    for (row, chunk) in versym.chunks(4).enumerate() {
+        if !limit.take() {
+            continue;
+        }
+        try!(write!(out, "  {:03x}:", row * 4));
+        for &v in chunk {
+            let entry = match v {
+                0 => "   0 (*local*)    ".to_string(),
+                1 => "   1 (*global*)   ".to_string(),
+                _ => {
+                    let mut entry = format!(
+                        "{:4x}{}", v & !VERSYM_HIDDEN,
+                        if v & VERSYM_HIDDEN != 0 { 'h' } else { ' ' });
+                    if let Some(name) = versions.get_name(v) {
+                        // readelf pads the closing parenthesis to line up
+                        // names of up to 12 characters
+                        let width = (12 - name.len() as isize).abs() as usize;
+                        entry.push_str(&format!("({}{:<w$}", name, ")", w = width));
+                    }
+                    format!("{:<18}", entry)
+                }
+            };
+            try!(write!(out, "{}", entry));
+        }
+        try!(writeln!(out, ""));
+    }
+    limit.print_omitted(out)
+}
+
+// The first `count` definitions in .gnu.version_d, with their parents.
+#[allow(dead_code)]
+pub fn print_verdef<W: Write>(
+    data: &[u8], strtab: &[u8], count: usize, e: &Endianness, out: &mut W,
+    max_entries: Option<usize>)
+    -> io::Result<()>
+{
+    let mut limit = EntryLimit::new(max_entries);
+    let mut offset = 0usize;
+    for _ in 0..count {
+        if offset + VERDEF_SIZE > data.len() {
+            break;
+        }
+        let version = get_u16(data, offset, e).unwrap_or(0);
+        let flags = get_u16(data, offset + 2, e).unwrap_or(0);
+        let ndx = get_u16(data, offset + 4, e).unwrap_or(0);
+        let cnt = get_u16(data, offset + 6, e).unwrap_or(0);
+        let aux = get_u32(data, offset + 12, e).unwrap_or(0) as usize;
+        let next = get_u32(data, offset + 16, e).unwrap_or(0) as usize;
+
+        if limit.take() {
+            try!(write!(
+                out, "  {}: Rev: {}  Flags: {}  Index: {}  Cnt: {}  ",
+                get_offset_string(offset), version, get_version_flags_string(flags), ndx, cnt));
+            let mut aux_offset = offset + aux;
+            if aux_offset + VERDAUX_SIZE > data.len() {
+                try!(writeln!(out, ""));
+                break;
+            }
+            let name = get_u32(data, aux_offset, e).unwrap_or(0);
+            try!(writeln!(out, "Name: {}", get_str(strtab, name as u64)));
+            for j in 1..cnt {
+                let aux_next = get_u32(data, aux_offset + 4, e).unwrap_or(0) as usize;
+                aux_offset += aux_next;
+                if aux_next == 0 || aux_offset + VERDAUX_SIZE > data.len() {
+                    break;
+                }
+                let name = get_u32(data, aux_offset, e).unwrap_or(0);
+                try!(writeln!(
+                    out, "  {}: Parent {}: {}",
+                    get_offset_string(aux_offset), j, get_str(strtab, name as u64)));
+            }
+        }
+        if next == 0 {
+            break;
+        }
+        offset += next;
+    }
+    limit.print_omitted(out)
+}
+
+// The first `count` dependencies in .gnu.version_r with the versions needed
+// from each.
+#[allow(dead_code)]
+pub fn print_verneed<W: Write>(
+    data: &[u8], strtab: &[u8], count: usize, e: &Endianness, out: &mut W,
+    max_entries: Option<usize>)
+    -> io::Result<()>
+{
+    let mut limit = EntryLimit::new(max_entries);
+    let mut offset = 0usize;
+    for _ in 0..count {
+        if offset + VERNEED_SIZE > data.len() {
+            break;
+        }
+        let version = get_u16(data, offset, e).unwrap_or(0);
+        let cnt = get_u16(data, offset + 2, e).unwrap_or(0);
+        let file = get_u32(data, offset + 4, e).unwrap_or(0);
+        let aux = get_u32(data, offset + 8, e).unwrap_or(0) as usize;
+        let next = get_u32(data, offset + 12, e).unwrap_or(0) as usize;
+
+        if limit.take() {
+            try!(writeln!(
+                out, "  {}: Version: {}  File: {}  Cnt: {}",
+                get_offset_string(offset), version, get_str(strtab, file as u64), cnt));
+            let mut aux_offset = offset + aux;
+            for _ in 0..cnt {
+                if aux_offset + VERNAUX_SIZE > data.len() {
+                    break;
+                }
+                let flags = get_u16(data, aux_offset + 4, e).unwrap_or(0);
+                let other = get_u16(data, aux_offset + 6, e).unwrap_or(0);
+                let name = get_u32(data, aux_offset + 8, e).unwrap_or(0);
+                let aux_next = get_u32(data, aux_offset + 12, e).unwrap_or(0) as usize;
+                try!(writeln!(
+                    out, "  {}:   Name: {}  Flags: {}  Version: {}",
+                    get_offset_string(aux_offset), get_str(strtab, name as u64),
+                    get_version_flags_string(flags), other));
+                if aux_next == 0 {
+                    break;
+                }
+                aux_offset += aux_next;
+            }
+        }
+        if next == 0 {
+            break;
+        }
+        offset += next;
+    }
+    limit.print_omitted(out)
+}
+
+#[allow(dead_code)]
+pub fn parse_versym(data: &[u8], e: &Endianness) -> Vec<u16> {
+    (0..data.len() / 2)
+        .filter_map(|i| get_u16(data, i * 2, e))
+        .collect()
+}
+
+// Index -> name of each version definition. The name of a definition is
+// its first auxiliary entry; the rest name its parents.
+#[allow(dead_code)]
+pub fn parse_verdef(data: &[u8], strtab: &[u8], e: &Endianness) -> Vec<(u16, String)> {
+    let mut r = Vec::new();
+    let mut offset = 0usize;
+    while offset + VERDEF_SIZE <= data.len() {
+        let ndx = get_u16(data, offset + 4, e).unwrap_or(0);
+        let cnt = get_u16(data, offset + 6, e).unwrap_or(0);
+        let aux = get_u32(data, offset + 12, e).unwrap_or(0) as usize;
+        let next = get_u32(data, offset + 16, e).unwrap_or(0) as usize;
+
+        let aux_offset = offset + aux;
+        if cnt > 0 && aux_offset + VERDAUX_SIZE <= data.len() {
+            let name = get_u32(data, aux_offset, e).unwrap_or(0);
+            r.push((ndx, get_str(strtab, name as u64).to_string()));
+        }
+        if next == 0 {
+            break;
+        }
+        offset += next;
+    }
+    r
+}
+
+// Index -> name of each version needed from a dependency.
+#[allow(dead_code)]
+pub fn parse_verneed(data: &[u8], strtab: &[u8], e: &Endianness) -> Vec<(u16, String)> {
+    let mut r = Vec::new();
+    let mut offset = 0usize;
+    while offset + VERNEED_SIZE <= data.len() {
+        let cnt = get_u16(data, offset + 2, e).unwrap_or(0);
+        let aux = get_u32(data, offset + 8, e).unwrap_or(0) as usize;
+        let next = get_u32(data, offset + 12, e).unwrap_or(0) as usize;
+
+        let mut aux_offset = offset + aux;
+        for _ in 0..cnt {
+            if aux_offset + VERNAUX_SIZE > data.len() {
+                break;
+            }
+            let other = get_u16(data, aux_offset + 6, e).unwrap_or(0);
+            let name = get_u32(data, aux_offset + 8, e).unwrap_or(0);
+            let aux_next = get_u32(data, aux_offset + 12, e).unwrap_or(0) as usize;
+            r.push((other, get_str(strtab, name as u64).to_string()));
+            if aux_next == 0 {
+                break;
+            }
+            aux_offset += aux_next;
+        }
+        if next == 0 {
+            break;
+        }
+        offset += next;
+    }
+    r
+}
@@ -1,15 +1,28 @@
 #[macro_export]
 macro_rules! read_ehdr {
     ($t:ty) => {
+        use super::super::elf_error::ElfError;
+
         #[allow(dead_code)]
         fn convert_byte_vec_to_ehdr_box(
             mut v: Vec<u8>)
-            -> Result<Box<$t>, ()>
+            -> Result<Box<$t>, ElfError>
         {
             let ehdr_size = ::std::mem::size_of::<$t>();
 
-            assert_eq!(ehdr_size as usize, v.len());
+            if v.len() < ehdr_size {
+                // Not being an ELF file at all is the better explanation,
+                // also for an empty file, which has no magic to be cut short
+                let magic = &[0x7f, b'E', b'L', b'F'];
+                if v.is_empty() || !magic.starts_with(&v[..v.len().min(EI_MAGIC_SIZE)]) {
+                    return Err(ElfError::InvalidMagic);
+                }
+                return Err(ElfError::TruncatedHeader(v.len() as u64));
+            }
             
+            // Only the magic is checked here. The remaining identification
+            // fields are checked by Elf_Ehdr::validate_ident, so that a
+            // header with odd fields can still be displayed.
             try!(validate_elf_magic(&v[..EI_MAGIC_SIZE]));
 
             let bytes_ptr: *mut u8 = v.as_mut_ptr();
@@ -24,13 +37,13 @@ macro_rules! read_ehdr {
             Ok(ehdr_box)
         }
 
-        fn validate_elf_magic(magic: &[u8]) -> Result<(), ()> 
+        fn validate_elf_magic(magic: &[u8]) -> Result<(), ElfError> 
         {
             let proper_magic = &[0x7f, b'E', b'L', b'F'];
             assert_eq!(magic.len(), proper_magic.len());
 
             if proper_magic != magic {
-                return Err(())
+                return Err(ElfError::InvalidMagic)
             }
             Ok(())
         }
@@ -38,7 +51,7 @@ macro_rules! read_ehdr {
         #[allow(dead_code)]
         pub fn read_ehdr<R: Read + Seek>(
             reader: &mut R)
-            -> Box<$t>
+            -> Result<Box<$t>, ElfError>
         {
             use std::io::SeekFrom;
 
@@ -46,10 +59,12 @@ macro_rules! read_ehdr {
             let ehdr_offset = 0;
 
             let mut b = Vec::<u8>::with_capacity(ehdr_size as usize);
-            reader.seek(SeekFrom::Start(ehdr_offset)).unwrap();
-            reader.take(ehdr_size as u64).read_to_end(&mut b).unwrap();
+            try!(reader.seek(SeekFrom::Start(ehdr_offset))
+                 .map_err(|e| ElfError::Unreadable(e.kind())));
+            try!(reader.take(ehdr_size as u64).read_to_end(&mut b)
+                 .map_err(|e| ElfError::Unreadable(e.kind())));
 
-            convert_byte_vec_to_ehdr_box(b).unwrap()
+            convert_byte_vec_to_ehdr_box(b)
         }
 
     }
@@ -58,36 +73,405 @@ macro_rules! read_ehdr {
 #[macro_export]
 macro_rules! read_phdrs {
     ($phdr:ty, $ehdr:ty) => {
+        // Reads the program header table, with as many entries as
+        // get_program_header_count gives. Entries the file is too short for
+        // are left out. The header is expected in host byte order.
         #[allow(dead_code)]
-        fn convert_byte_vec_to_phdrs_vec(
-            v: Vec<u8>, phdr_num: u16, phdr_size: u16) 
-            -> Vec<$phdr> {
-            assert_eq!(phdr_num as usize * phdr_size as usize, v.len());
-            let mut r: Vec<$phdr> = unsafe {
-                ::std::mem::transmute(v)
-            };
-            unsafe {
-                r.set_len(phdr_num as usize);
+        pub fn read_phdrs<R: Read + Seek>(
+            ehdr: &$ehdr, reader: &mut R)
+            -> Vec<$phdr>
+        {
+            let phdr_size = ::std::mem::size_of::<$phdr>();
+            let phdr_num = ehdr.get_program_header_count(reader);
+
+            let b = super::super::helpers::read_bytes(
+                reader, ehdr.get_phoff() as u64, (phdr_size * phdr_num) as u64)
+                .unwrap_or(Vec::new());
+            b.chunks(phdr_size).filter(|chunk| chunk.len() == phdr_size).map(|chunk| unsafe {
+                ::std::ptr::read_unaligned(chunk.as_ptr() as *const $phdr)
+            }).collect()
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! read_dyns {
+    ($dyn:ty, $phdr:ty) => {
+        // Reads the entries of a PT_DYNAMIC segment up to and including
+        // DT_NULL. The program header is expected in host byte order, the
+        // returned entries are in host byte order. Fails if p_offset can't be
+        // read from.
+        #[allow(dead_code)]
+        pub fn read_dyns<R: Read + Seek>(
+            phdr: &$phdr, e: &::to_host::Endianness, reader: &mut R)
+            -> Result<Vec<$dyn>, super::super::elf_error::ElfError>
+        {
+            use ::to_host::ToHostCopyStruct;
+            use super::super::elf_dyn_tag::ElfDynTag;
+
+            let dyn_size = ::std::mem::size_of::<$dyn>();
+            let b = try!(super::super::helpers::read_bytes(
+                reader, phdr.get_offset() as u64, phdr.get_filesz() as u64));
+
+            let mut r = Vec::new();
+            for chunk in b.chunks(dyn_size) {
+                if chunk.len() != dyn_size {
+                    break;
+                }
+                let d: $dyn = unsafe {
+                    ::std::ptr::read_unaligned(chunk.as_ptr() as *const $dyn)
+                };
+                let d = d.to_host_copy(e);
+                let tag = d.get_tag();
+                r.push(d);
+                if tag == ElfDynTag::DT_NULL {
+                    break;
+                }
+            }
+            Ok(r)
+        }
+    }
+}
+
+// Counts the rows of a table against --max-entries, so that only the first
+// ones are printed and the rest are summed up in a footer.
+#[derive(Debug, Clone, Copy)]
+pub struct EntryLimit {
+    max: Option<usize>,
+    shown: usize,
+    omitted: usize,
+}
+
+#[allow(dead_code)]
+impl EntryLimit {
+    pub fn new(max: Option<usize>) -> EntryLimit {
+        EntryLimit { max: max, shown: 0, omitted: 0 }
+    }
+    // Whether the next row is to be printed.
+    pub fn take(&mut self) -> bool {
+        match self.max {
+            Some(max) if self.shown >= max => {
+                self.omitted += 1;
+                false
+            }
+            _ => {
+                self.shown += 1;
+                true
+            }
+        }
+    }
+    pub fn print_omitted<W: ::std::io::Write>(&self, out: &mut W) -> ::std::io::Result<()> {
+        if self.omitted == 0 {
+            return Ok(());
+        }
+        writeln!(out, "  ... ({} more)", self.omitted)
+    }
+}
+
+// Reads up to `size` bytes at `offset`, fewer if the file ends first.
+// Both come from the file, and an offset the reader can't seek to, such as
+// one past 2^63, is an error rather than a panic.
+#[allow(dead_code)]
+pub fn read_bytes<R: ::std::io::Read + ::std::io::Seek>(
+    reader: &mut R, offset: u64, size: u64)
+    -> Result<Vec<u8>, super::elf_error::ElfError>
+{
+    use std::io::{Read, SeekFrom};
+    use super::elf_error::ElfError;
+
+    // `size` comes from the file and may not fit in usize on 32-bit hosts,
+    // so let the buffer grow to what is actually read instead of reserving.
+    let mut b = Vec::<u8>::new();
+    if reader.seek(SeekFrom::Start(offset)).is_err() {
+        return Err(ElfError::BadOffset(offset));
+    }
+    if reader.take(size).read_to_end(&mut b).is_err() {
+        return Err(ElfError::Truncated);
+    }
+    Ok(b)
+}
+
+// Writes the whole image out again, from its first byte to the end of the
+// reader. Nothing is reformatted, so an unmodified file comes out byte for
+// byte as it went in. Returns the number of bytes written.
+#[allow(dead_code)]
+pub fn write_to<R: ::std::io::Read + ::std::io::Seek, W: ::std::io::Write>(
+    reader: &mut R, out: &mut W)
+    -> ::std::io::Result<u64>
+{
+    use std::io::SeekFrom;
+
+    // Offset 0 is the image base, also for an EmbeddedElf
+    try!(reader.seek(SeekFrom::Start(0)));
+    ::std::io::copy(reader, out)
+}
+
+// Returns the NUL-terminated string starting at `offset` in a string table.
+// The offset is checked before narrowing it to usize, so on 32-bit hosts an
+// offset beyond 4 GiB can't wrap around into the table.
+#[allow(dead_code)]
+pub fn get_str(strtab: &[u8], offset: u64) -> &str {
+    if offset >= strtab.len() as u64 {
+        return "<corrupt>";
+    }
+    let s = &strtab[offset as usize..];
+    let end = s.iter().position(|&b| b == 0).unwrap_or(s.len());
+    ::std::str::from_utf8(&s[..end]).unwrap_or("<corrupt>")
+}
+
+// Read a half, word or xword out of a byte buffer in file byte order and
+// convert it to host byte order. Out-of-range reads return None.
+#[allow(dead_code)]
+pub fn get_u16(
+    bytes: &[u8], offset: usize, e: &::to_host::Endianness) -> Option<u16>
+{
+    use ::to_host::to_host_copy::ToHostCopy;
+
+    if offset > bytes.len() || bytes.len() - offset < 2 {
+        return None;
+    }
+    let value: u16 = unsafe {
+        ::std::ptr::read_unaligned(bytes[offset..].as_ptr() as *const u16)
+    };
+    Some(value.to_host_copy(e))
+}
+
+#[allow(dead_code)]
+pub fn get_u32(
+    bytes: &[u8], offset: usize, e: &::to_host::Endianness) -> Option<u32>
+{
+    use ::to_host::to_host_copy::ToHostCopy;
+
+    if offset > bytes.len() || bytes.len() - offset < 4 {
+        return None;
+    }
+    let value: u32 = unsafe {
+        ::std::ptr::read_unaligned(bytes[offset..].as_ptr() as *const u32)
+    };
+    Some(value.to_host_copy(e))
+}
+
+#[allow(dead_code)]
+pub fn get_u64(
+    bytes: &[u8], offset: usize, e: &::to_host::Endianness) -> Option<u64>
+{
+    use ::to_host::to_host_copy::ToHostCopy;
+
+    if offset > bytes.len() || bytes.len() - offset < 8 {
+        return None;
+    }
+    let value: u64 = unsafe {
+        ::std::ptr::read_unaligned(bytes[offset..].as_ptr() as *const u64)
+    };
+    Some(value.to_host_copy(e))
+}
+
+// sh_addralign is a power of two, with 0 and 1 both meaning the section
+// has no alignment constraint. None for any other value.
+#[allow(dead_code)]
+pub fn get_alignment_exponent(align: u64) -> Option<u32> {
+    if align == 0 {
+        Some(0)
+    } else if align.is_power_of_two() {
+        Some(align.trailing_zeros())
+    } else {
+        None
+    }
+}
+
+// The alignment as a number or, with `pow2`, as 2^n. Values which aren't
+// powers of two are always shown as numbers.
+#[allow(dead_code)]
+pub fn get_alignment_string(align: u64, pow2: bool) -> String {
+    match get_alignment_exponent(align) {
+        Some(n) if pow2 && align != 0 => format!("2^{}", n),
+        _ => format!("{}", align),
+    }
+}
+
+// Shannon entropy in bits per byte, from 0.0 for a run of one value up to
+// 8.0 for uniformly random data. Compressed or encrypted data comes close
+// to 8.0, while code and tables are usually well below 7.0.
+#[allow(dead_code)]
+pub fn get_entropy(bytes: &[u8]) -> f64 {
+    if bytes.is_empty() {
+        return 0.0;
+    }
+    let mut counts = [0u64; 256];
+    for &b in bytes {
+        counts[b as usize] += 1;
+    }
+    let len = bytes.len() as f64;
+    counts.iter()
+        .filter(|&&count| count != 0)
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+// 16 bytes a line, in groups of four, followed by the printable ones, the
+// way readelf -x shows section contents. `address` is that of the first byte.
+#[allow(dead_code)]
+pub fn print_hex_lines<W: ::std::io::Write>(
+    bytes: &[u8], address: u64, out: &mut W)
+    -> ::std::io::Result<()>
+{
+    for (i, line) in bytes.chunks(16).enumerate() {
+        try!(write!(out, "  0x{:08x} ", address + i as u64 * 16));
+        for j in 0..16 {
+            match line.get(j) {
+                Some(b) => try!(write!(out, "{:02x}", b)),
+                None => try!(write!(out, "  ")),
+            }
+            if j % 4 == 3 {
+                try!(write!(out, " "));
             }
-            r
         }
+        let text: String = line.iter()
+            .map(|&b| if b >= 0x20 && b < 0x7f { b as char } else { '.' })
+            .collect();
+        try!(writeln!(out, "{}", text));
+    }
+    Ok(())
+}
+
+// Runs of at least `min_len` printable ASCII characters (tabs included), with
+// their offsets, as found by strings(1).
+#[allow(dead_code)]
+pub fn get_strings(bytes: &[u8], min_len: usize) -> Vec<(usize, &str)> {
+    let printable = |b: u8| b == b'\t' || (b >= 0x20 && b < 0x7f);
+    let mut r = Vec::new();
+    let mut start = 0;
+    for i in 0..bytes.len() + 1 {
+        if i < bytes.len() && printable(bytes[i]) {
+            continue;
+        }
+        if i - start >= min_len.max(1) {
+            // Printable ASCII is valid UTF-8
+            r.push((start, ::std::str::from_utf8(&bytes[start..i]).unwrap()));
+        }
+        start = i + 1;
+    }
+    r
+}
+
+// CRC-32 as used by .gnu_debuglink (the zlib one: reflected polynomial
+// 0xedb88320, inverted on input and output).
+#[allow(dead_code)]
+pub fn get_crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in bytes {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb88320 & mask);
+        }
+    }
+    !crc
+}
 
+#[macro_export]
+macro_rules! read_shdrs {
+    ($shdr:ty, $ehdr:ty) => {
+        // Reads the section header table as-is: entries are neither
+        // converted to host byte order nor resolved against the string table.
+        // There are as many as get_section_header_count gives. The ELF header
+        // is expected to be in host byte order.
         #[allow(dead_code)]
-        pub fn read_phdrs<R: Read + Seek>(
+        pub fn read_shdrs<R: Read + Seek>(
             ehdr: &$ehdr, reader: &mut R)
-            -> Vec<$phdr>
+            -> Result<Vec<$shdr>, super::super::elf_error::ElfError>
         {
-            use std::io::SeekFrom;
+            use super::super::elf_error::ElfError;
+
+            let shdr_size = ::std::mem::size_of::<$shdr>();
+            let shdr_num = ehdr.get_section_header_count(reader);
+
+            if shdr_num == 0 {
+                return Ok(Vec::new());
+            }
+            if ehdr.get_shentsize() as usize != shdr_size {
+                return Err(ElfError::UnexpectedEntrySize(ehdr.get_shentsize() as u64));
+            }
+
+            let size = (shdr_size as u64).saturating_mul(shdr_num);
+            let b = try!(super::super::helpers::read_bytes(
+                reader, ehdr.get_shoff() as u64, size));
+            if b.len() as u64 != size {
+                return Err(ElfError::Truncated);
+            }
+
+            let r = b.chunks(shdr_size).map(|chunk| unsafe {
+                ::std::ptr::read_unaligned(chunk.as_ptr() as *const $shdr)
+            }).collect();
+            Ok(r)
+        }
+
+        // The section header table of one file, read on first use and kept,
+        // so that the validation pass and the modes share a single read.
+        pub struct LazyShdrs {
+            table: ::std::cell::RefCell<
+                Option<Result<::std::rc::Rc<[$shdr]>, super::super::elf_error::ElfError>>>,
+        }
 
-            let phdr_size = ehdr.get_phentsize() * ehdr.get_phnum();
-            let phdr_offset = ehdr.get_phoff();
-            let phdr_num = ehdr.get_phnum();
+        #[allow(dead_code)]
+        impl LazyShdrs {
+            pub fn new() -> LazyShdrs {
+                LazyShdrs { table: ::std::cell::RefCell::new(None) }
+            }
+            // As read_shdrs, but every call shares the one table. The header
+            // and reader must be the same on every call.
+            pub fn get<R: Read + Seek>(&self, ehdr: &$ehdr, reader: &mut R)
+                -> Result<::std::rc::Rc<[$shdr]>, super::super::elf_error::ElfError>
+            {
+                let mut table = self.table.borrow_mut();
+                if table.is_none() {
+                    *table = Some(read_shdrs(ehdr, reader).map(::std::rc::Rc::from));
+                }
+                table.as_ref().unwrap().clone()
+            }
+        }
+    }
+}
 
-            let mut b = Vec::<u8>::with_capacity(phdr_size as usize * phdr_num as usize);
-            reader.seek(SeekFrom::Start(phdr_offset as u64)).unwrap();
-            reader.take(phdr_size as u64 * phdr_num as u64).read_to_end(&mut b).unwrap();
+#[macro_export]
+macro_rules! read_syms {
+    ($sym:ty, $shdr:ty) => {
+        // Reads the entries of a symbol table section, stepping by
+        // sh_entsize. The section header is expected in host byte order, the
+        // returned symbols are in host byte order.
+        #[allow(dead_code)]
+        pub fn read_syms<R: Read + Seek>(
+            shdr: &$shdr, e: &::to_host::Endianness, reader: &mut R)
+            -> Result<Vec<$sym>, super::super::elf_error::ElfError>
+        {
+            use ::to_host::ToHostCopyStruct;
+            use super::super::elf_error::ElfError;
 
-            convert_byte_vec_to_phdrs_vec(b, phdr_num, phdr_size)
+            let sym_size = ::std::mem::size_of::<$sym>();
+            let count = try!(shdr.get_entry_count(sym_size));
+            let stride = match shdr.get_entsize() as usize {
+                0 => sym_size,
+                entsize if entsize < sym_size =>
+                    return Err(ElfError::UnexpectedEntrySize(entsize as u64)),
+                entsize => entsize,
+            };
+            let b = try!(super::super::helpers::read_bytes(
+                reader, shdr.get_offset() as u64, shdr.get_size() as u64));
+
+            let r = b.chunks(stride)
+                .take(count as usize)
+                .filter(|chunk| chunk.len() >= sym_size)
+                .map(|chunk| {
+                    let sym: $sym = unsafe {
+                        ::std::ptr::read_unaligned(chunk.as_ptr() as *const $sym)
+                    };
+                    sym.to_host_copy(e)
+                })
+                .collect();
+            Ok(r)
         }
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,4 @@
+pub mod elf_error;
 pub mod elf_ident;
 pub mod elf_ei_class;
 pub mod elf_ei_data;
@@ -9,12 +10,50 @@ pub mod elf_ehdr_type;
 pub mod elf_ehdr_machine;
 pub mod elf_phdr_type;
 pub mod elf_phdr_flags;
+pub mod elf_shdr_type;
+pub mod elf_shdr_flags;
+pub mod elf_sym_info;
+pub mod elf_dyn_tag;
+pub mod elf_dyn_flags;
+pub mod elf_note;
+pub mod elf_gnu_property;
+pub mod elf_hash;
+pub mod elf_target;
+pub mod elf_debuglink;
+pub mod elf_dwarf;
+pub mod elf_ident_override;
+pub mod elf_embedded;
+pub mod elf_scan;
+pub mod elf_rel_type;
+pub mod elf_json;
+pub mod elf_version;
+pub mod elf_section_order;
+pub mod elf_fortify;
+pub mod elf_checksec;
+pub mod elf_eh_frame_hdr;
+pub mod elf_arm_exidx;
+pub mod elf_counts;
+pub mod elf_layout;
+pub mod elf_report;
+pub mod elf_needed;
+pub mod elf_strtab;
+pub mod elf_sym_dump;
 
 #[macro_use]
 mod elf_ehdr;
 #[macro_use]
 mod elf_phdr;
 #[macro_use]
+mod elf_dyn;
+#[macro_use]
+mod elf_shdr;
+#[macro_use]
+mod elf_sym;
+#[macro_use]
+mod elf_heuristics;
+#[macro_use]
+mod elf_rel;
+#[macro_use]
 mod helpers;
 
 mod b32;
@@ -26,6 +65,66 @@ pub use ::elf::b32::elf_phdr::Elf_Phdr as Elf32_Phdr;
 pub use ::elf::b64::elf_ehdr::Elf_Ehdr as Elf64_Ehdr;
 pub use ::elf::b64::elf_phdr::Elf_Phdr as Elf64_Phdr;
 
+pub use ::elf::b32::elf_shdr::Elf_Shdr as Elf32_Shdr;
+pub use ::elf::b64::elf_shdr::Elf_Shdr as Elf64_Shdr;
+
+pub use ::elf::b32::elf_shdr::Section as Elf32_Section;
+pub use ::elf::b64::elf_shdr::Section as Elf64_Section;
+pub use ::elf::b32::elf_shdr::get_sections as get_elf32_sections;
+pub use ::elf::b64::elf_shdr::get_sections as get_elf64_sections;
+pub use ::elf::b32::elf_shdr::get_json_sections as get_elf32_json_sections;
+pub use ::elf::b64::elf_shdr::get_json_sections as get_elf64_json_sections;
+pub use ::elf::b32::elf_shdr::get_section_at_offset as get_elf32_section_at_offset;
+pub use ::elf::b64::elf_shdr::get_section_at_offset as get_elf64_section_at_offset;
+pub use ::elf::b32::elf_shdr::get_string_tables as get_elf32_string_tables;
+pub use ::elf::b64::elf_shdr::get_string_tables as get_elf64_string_tables;
+pub use ::elf::b32::elf_shdr::get_section_problems as get_elf32_section_problems;
+pub use ::elf::b64::elf_shdr::get_section_problems as get_elf64_section_problems;
+pub use ::elf::b32::elf_shdr::get_tls_problems as get_elf32_tls_problems;
+pub use ::elf::b64::elf_shdr::get_tls_problems as get_elf64_tls_problems;
+pub use ::elf::b32::elf_shdr::get_text_segment_problems as get_elf32_text_segment_problems;
+pub use ::elf::b64::elf_shdr::get_text_segment_problems as get_elf64_text_segment_problems;
+pub use ::elf::b32::elf_shdr::get_eh_frame_hdr as get_elf32_eh_frame_hdr;
+pub use ::elf::b64::elf_shdr::get_eh_frame_hdr as get_elf64_eh_frame_hdr;
+pub use ::elf::b32::elf_shdr::get_eh_frame_hdr_problems as get_elf32_eh_frame_hdr_problems;
+pub use ::elf::b64::elf_shdr::get_eh_frame_hdr_problems as get_elf64_eh_frame_hdr_problems;
+pub use ::elf::b32::elf_shdr::get_interpreter as get_elf32_interpreter;
+pub use ::elf::b64::elf_shdr::get_interpreter as get_elf64_interpreter;
+pub use ::elf::b32::elf_shdr::get_interpreter_problems as get_elf32_interpreter_problems;
+pub use ::elf::b64::elf_shdr::get_interpreter_problems as get_elf64_interpreter_problems;
+
+pub use ::elf::b32::elf_sym::Elf_Sym as Elf32_Sym;
+pub use ::elf::b64::elf_sym::Elf_Sym as Elf64_Sym;
+
+pub use ::elf::b32::elf_rel::{Elf_Rel as Elf32_Rel, Elf_Rela as Elf32_Rela};
+pub use ::elf::b64::elf_rel::{Elf_Rel as Elf64_Rel, Elf_Rela as Elf64_Rela};
+pub use ::elf::b32::elf_rel::read_rels as read_elf32_rels;
+pub use ::elf::b64::elf_rel::read_rels as read_elf64_rels;
+pub use ::elf::b32::elf_rel::read_relas as read_elf32_relas;
+pub use ::elf::b64::elf_rel::read_relas as read_elf64_relas;
+pub use ::elf::b32::elf_rel::print_plt_stubs as print_elf32_plt_stubs;
+pub use ::elf::b64::elf_rel::print_plt_stubs as print_elf64_plt_stubs;
+pub use ::elf::b32::elf_rel::read_relrs as read_elf32_relrs;
+pub use ::elf::b64::elf_rel::read_relrs as read_elf64_relrs;
+pub use ::elf::b32::elf_rel::decode_relr as decode_elf32_relr;
+pub use ::elf::b64::elf_rel::decode_relr as decode_elf64_relr;
+pub use ::elf::b32::elf_rel::print_relocations as print_elf32_relocations;
+pub use ::elf::b64::elf_rel::print_relocations as print_elf64_relocations;
+pub use ::elf::b32::elf_rel::is_relocation_section as is_elf32_relocation_section;
+pub use ::elf::b64::elf_rel::is_relocation_section as is_elf64_relocation_section;
+pub use ::elf::b32::elf_rel::ResolvedReloc as Elf32_ResolvedReloc;
+pub use ::elf::b64::elf_rel::ResolvedReloc as Elf64_ResolvedReloc;
+pub use ::elf::b32::elf_rel::get_relocations as get_elf32_relocations;
+pub use ::elf::b64::elf_rel::get_relocations as get_elf64_relocations;
+pub use ::elf::b32::elf_rel::get_relative_count_problems as get_elf32_relative_count_problems;
+pub use ::elf::b64::elf_rel::get_relative_count_problems as get_elf64_relative_count_problems;
+
+pub use ::elf::b32::elf_sym::Symbol as Elf32_Symbol;
+pub use ::elf::b64::elf_sym::Symbol as Elf64_Symbol;
+
+pub use ::elf::b32::elf_dyn::Elf_Dyn as Elf32_Dyn;
+pub use ::elf::b64::elf_dyn::Elf_Dyn as Elf64_Dyn;
+
 pub mod prelude;
 
 pub use ::elf::b32::helpers::read_ehdr as read_elf32_ehdr;
@@ -34,6 +133,108 @@ pub use ::elf::b64::helpers::read_ehdr as read_elf64_ehdr;
 pub use ::elf::b32::helpers::read_phdrs as read_elf32_phdrs;
 pub use ::elf::b64::helpers::read_phdrs as read_elf64_phdrs;
 
+pub use ::elf::b32::elf_phdr::read_vaddr as read_elf32_vaddr;
+pub use ::elf::b64::elf_phdr::read_vaddr as read_elf64_vaddr;
+
+pub use ::elf::b32::elf_phdr::get_rwx_segments as get_elf32_rwx_segments;
+pub use ::elf::b64::elf_phdr::get_rwx_segments as get_elf64_rwx_segments;
+pub use ::elf::b32::elf_phdr::get_json_segments as get_elf32_json_segments;
+pub use ::elf::b64::elf_phdr::get_json_segments as get_elf64_json_segments;
+pub use ::elf::b32::elf_phdr::has_rwx_segment as has_elf32_rwx_segment;
+pub use ::elf::b64::elf_phdr::has_rwx_segment as has_elf64_rwx_segment;
+pub use ::elf::b32::elf_phdr::get_load_order_problems as get_elf32_load_order_problems;
+pub use ::elf::b64::elf_phdr::get_load_order_problems as get_elf64_load_order_problems;
+
+pub use ::elf::b32::helpers::read_shdrs as read_elf32_shdrs;
+pub use ::elf::b64::helpers::read_shdrs as read_elf64_shdrs;
+pub use ::elf::b32::helpers::LazyShdrs as LazyElf32Shdrs;
+pub use ::elf::b64::helpers::LazyShdrs as LazyElf64Shdrs;
+
+pub use ::elf::b32::elf_shdr::print_sections as print_elf32_sections;
+pub use ::elf::b64::elf_shdr::print_sections as print_elf64_sections;
+
+pub use ::elf::b32::elf_shdr::print_section_details as print_elf32_section_details;
+pub use ::elf::b64::elf_shdr::print_section_details as print_elf64_section_details;
+
+pub use ::elf::b32::elf_shdr::print_section_links as print_elf32_section_links;
+pub use ::elf::b64::elf_shdr::print_section_links as print_elf64_section_links;
+
+pub use ::elf::b32::elf_shdr::read_debuglink as read_elf32_debuglink;
+pub use ::elf::b64::elf_shdr::read_debuglink as read_elf64_debuglink;
+
+pub use ::elf::b32::elf_shdr::print_debug_info as print_elf32_debug_info;
+pub use ::elf::b64::elf_shdr::print_debug_info as print_elf64_debug_info;
+
+pub use ::elf::b32::elf_shdr::print_debug_line as print_elf32_debug_line;
+pub use ::elf::b64::elf_shdr::print_debug_line as print_elf64_debug_line;
+
+pub use ::elf::b32::elf_shdr::print_section_notes as print_elf32_notes;
+pub use ::elf::b64::elf_shdr::print_section_notes as print_elf64_notes;
+pub use ::elf::b32::elf_shdr::print_arm_exidx as print_elf32_arm_exidx;
+pub use ::elf::b64::elf_shdr::print_arm_exidx as print_elf64_arm_exidx;
+pub use ::elf::b32::elf_shdr::get_structure_counts as get_elf32_structure_counts;
+pub use ::elf::b64::elf_shdr::get_structure_counts as get_elf64_structure_counts;
+pub use ::elf::b32::elf_shdr::print_relative_offsets as print_elf32_relative_offsets;
+pub use ::elf::b64::elf_shdr::print_relative_offsets as print_elf64_relative_offsets;
+
+pub use ::elf::b32::elf_shdr::print_debuglink as print_elf32_debuglink;
+pub use ::elf::b64::elf_shdr::print_debuglink as print_elf64_debuglink;
+
+pub use ::elf::b32::elf_shdr::get_section_entropy as get_elf32_section_entropy;
+pub use ::elf::b64::elf_shdr::get_section_entropy as get_elf64_section_entropy;
+
+pub use ::elf::b32::elf_shdr::print_section_entropy as print_elf32_section_entropy;
+pub use ::elf::b64::elf_shdr::print_section_entropy as print_elf64_section_entropy;
+
+pub use ::elf::b32::elf_shdr::print_strings as print_elf32_strings;
+pub use ::elf::b64::elf_shdr::print_strings as print_elf64_strings;
+
+pub use ::elf::b32::elf_shdr::print_hex_dumps as print_elf32_hex_dumps;
+pub use ::elf::b64::elf_shdr::print_hex_dumps as print_elf64_hex_dumps;
+
+pub use ::elf::b32::helpers::read_syms as read_elf32_syms;
+pub use ::elf::b64::helpers::read_syms as read_elf64_syms;
+
+pub use ::elf::b32::elf_sym::print_symbols as print_elf32_symbols;
+pub use ::elf::b64::elf_sym::print_symbols as print_elf64_symbols;
+pub use ::elf::b32::elf_sym::get_json_symbols as get_elf32_json_symbols;
+pub use ::elf::b64::elf_sym::get_json_symbols as get_elf64_json_symbols;
+pub use ::elf::b32::elf_sym::get_symbol_bytes as get_elf32_symbol_bytes;
+pub use ::elf::b64::elf_sym::get_symbol_bytes as get_elf64_symbol_bytes;
+
+pub use ::elf::b32::elf_sym::print_compact_symbols as print_elf32_compact_symbols;
+pub use ::elf::b64::elf_sym::print_compact_symbols as print_elf64_compact_symbols;
+pub use ::elf::b32::elf_sym::read_symbol_versions as read_elf32_symbol_versions;
+pub use ::elf::b64::elf_sym::read_symbol_versions as read_elf64_symbol_versions;
+pub use ::elf::b32::elf_sym::print_imports as print_elf32_imports;
+pub use ::elf::b64::elf_sym::print_imports as print_elf64_imports;
+pub use ::elf::b32::elf_sym::print_exports as print_elf32_exports;
+pub use ::elf::b64::elf_sym::print_exports as print_elf64_exports;
+
+pub use ::elf::b32::elf_sym::print_version_info as print_elf32_version_info;
+pub use ::elf::b64::elf_sym::print_version_info as print_elf64_version_info;
+pub use ::elf::b32::elf_sym::get_fortify_info as get_elf32_fortify_info;
+pub use ::elf::b64::elf_sym::get_fortify_info as get_elf64_fortify_info;
+
+pub use ::elf::b32::helpers::read_dyns as read_elf32_dyns;
+pub use ::elf::b64::helpers::read_dyns as read_elf64_dyns;
+
+pub use ::elf::b32::elf_dyn::print_dynamic as print_elf32_dynamic;
+pub use ::elf::b64::elf_dyn::print_dynamic as print_elf64_dynamic;
+pub use ::elf::b32::elf_dyn::get_dependencies as get_elf32_dependencies;
+pub use ::elf::b64::elf_dyn::get_dependencies as get_elf64_dependencies;
+
+pub use ::elf::b32::elf_dyn::print_histogram as print_elf32_histogram;
+pub use ::elf::b64::elf_dyn::print_histogram as print_elf64_histogram;
+
+pub use ::elf::b32::elf_heuristics::print_packing_report as print_elf32_packing_report;
+pub use ::elf::b64::elf_heuristics::print_packing_report as print_elf64_packing_report;
+
+pub use ::elf::b32::elf_dyn::has_text_relocations as elf32_has_text_relocations;
+pub use ::elf::b64::elf_dyn::has_text_relocations as elf64_has_text_relocations;
+pub use ::elf::b32::elf_dyn::has_dynamic_text_relocations as elf32_has_dynamic_text_relocations;
+pub use ::elf::b64::elf_dyn::has_dynamic_text_relocations as elf64_has_dynamic_text_relocations;
+
 fn _static_asserts() {
     let ei_bytes: elf_ident::ElfIdent = unsafe {
         ::std::mem::uninitialized()
@@ -41,18 +242,4 @@ fn _static_asserts() {
     let _ei_named: elf_ident_named::ElfIdentNamed = unsafe {
         ::std::mem::transmute(ei_bytes)
     };
-
-    let ehdr_type_bytes: b64::primitive::Elf64_Half = unsafe {
-        ::std::mem::uninitialized()
-    };
-    let _ehdr_type: elf_ehdr_type::ElfEhdrType = unsafe {
-        ::std::mem::transmute(ehdr_type_bytes)
-    };
-
-    let ehdr_machine_bytes: b64::primitive::Elf64_Half = unsafe {
-        ::std::mem::uninitialized()
-    };
-    let _ehdr_machine: elf_ehdr_machine::ElfEhdrMachine = unsafe {
-        ::std::mem::transmute(ehdr_machine_bytes)
-    };
 }
@@ -1,16 +1,100 @@
+pub use super::elf_error::ElfError;
 pub use super::elf_ident::ElfIdent;
 pub use super::b64::primitive::*;
 pub use super::elf_ei_class::ElfEiClass;
 pub use super::elf_ei_data::ElfEiData;
+pub use super::elf_ident_override::IdentOverride;
+pub use super::elf_embedded::EmbeddedElf;
+pub use super::elf_scan::find_elf_headers;
 pub use super::elf_ei_version::ElfEiVersion;
 pub use super::elf_ei_os_abi::ElfEiOsAbi;
 pub use super::elf_ei_abi_version::ElfEiAbiVersion;
 pub use super::elf_ident_named::ElfIdentNamed;
-pub use super::elf_ehdr_type::ElfEhdrType;
-pub use super::elf_ehdr_machine::ElfEhdrMachine;
+pub use super::elf_ehdr_type::{get_type_short_name, get_type_string};
+pub use super::elf_ehdr_machine::{ElfEhdrMachine, machine_name};
+pub use super::elf_target::TargetInfo;
 pub use super::{Elf32_Ehdr, Elf64_Ehdr};
 pub use super::elf_phdr_type::ElfPhdrType;
 pub use super::elf_phdr_flags::ElfPhdrFlags;
+pub use super::elf_gnu_property::GnuProperty;
 pub use super::{Elf32_Phdr, Elf64_Phdr};
 pub use super::{read_elf32_ehdr, read_elf64_ehdr};
 pub use super::{read_elf32_phdrs, read_elf64_phdrs};
+pub use super::{read_elf32_vaddr, read_elf64_vaddr};
+pub use super::{get_elf32_rwx_segments, get_elf64_rwx_segments};
+pub use super::{get_elf32_json_segments, get_elf64_json_segments};
+pub use super::{has_elf32_rwx_segment, has_elf64_rwx_segment};
+pub use super::{get_elf32_load_order_problems, get_elf64_load_order_problems};
+pub use super::{Elf32_Shdr, Elf64_Shdr};
+pub use super::{Elf32_Section, Elf64_Section, get_elf32_sections, get_elf64_sections};
+pub use super::{get_elf32_json_sections, get_elf64_json_sections};
+pub use super::{get_elf32_section_at_offset, get_elf64_section_at_offset};
+pub use super::{get_elf32_string_tables, get_elf64_string_tables};
+pub use super::elf_strtab::StringTable;
+pub use super::{get_elf32_section_problems, get_elf64_section_problems};
+pub use super::{get_elf32_tls_problems, get_elf64_tls_problems};
+pub use super::{get_elf32_text_segment_problems, get_elf64_text_segment_problems};
+pub use super::{get_elf32_eh_frame_hdr, get_elf64_eh_frame_hdr};
+pub use super::{get_elf32_eh_frame_hdr_problems, get_elf64_eh_frame_hdr_problems};
+pub use super::{get_elf32_interpreter, get_elf64_interpreter};
+pub use super::{get_elf32_interpreter_problems, get_elf64_interpreter_problems};
+pub use super::elf_json::JsonReport;
+pub use super::elf_report::{OutputFormat, TextReporter, JsonReporter, CsvReporter, YamlReporter, StableReporter};
+pub use super::elf_section_order::SectionOrder;
+pub use super::elf_checksec::Checksec;
+pub use super::{read_elf32_shdrs, read_elf64_shdrs};
+pub use super::{LazyElf32Shdrs, LazyElf64Shdrs};
+pub use super::elf_shdr_type::ElfShdrType;
+pub use super::elf_shdr_flags::ElfShdrFlags;
+pub use super::{print_elf32_sections, print_elf64_sections};
+pub use super::{print_elf32_section_details, print_elf64_section_details};
+pub use super::{print_elf32_section_links, print_elf64_section_links};
+pub use super::{get_elf32_section_entropy, get_elf64_section_entropy};
+pub use super::{print_elf32_section_entropy, print_elf64_section_entropy};
+pub use super::{print_elf32_strings, print_elf64_strings};
+pub use super::{print_elf32_hex_dumps, print_elf64_hex_dumps};
+pub use super::{print_elf32_notes, print_elf64_notes};
+pub use super::{print_elf32_arm_exidx, print_elf64_arm_exidx};
+pub use super::{get_elf32_structure_counts, get_elf64_structure_counts};
+pub use super::{print_elf32_relative_offsets, print_elf64_relative_offsets};
+pub use super::{print_elf32_debuglink, print_elf64_debuglink};
+pub use super::{read_elf32_debuglink, read_elf64_debuglink};
+pub use super::helpers::get_crc32;
+pub use super::helpers::EntryLimit;
+pub use super::helpers::write_to;
+pub use super::{print_elf32_debug_info, print_elf64_debug_info};
+pub use super::{print_elf32_debug_line, print_elf64_debug_line};
+pub use super::elf_sym_info::{ElfSymType, ElfSymBind, ElfSymVis};
+pub use super::{Elf32_Sym, Elf64_Sym};
+pub use super::{Elf32_Symbol, Elf64_Symbol};
+pub use super::{Elf32_Rel, Elf64_Rel, Elf32_Rela, Elf64_Rela};
+pub use super::{read_elf32_rels, read_elf64_rels, read_elf32_relas, read_elf64_relas};
+pub use super::{print_elf32_plt_stubs, print_elf64_plt_stubs};
+pub use super::{read_elf32_relrs, read_elf64_relrs, decode_elf32_relr, decode_elf64_relr};
+pub use super::{print_elf32_relocations, print_elf64_relocations};
+pub use super::{is_elf32_relocation_section, is_elf64_relocation_section};
+pub use super::{Elf32_ResolvedReloc, Elf64_ResolvedReloc};
+pub use super::{get_elf32_relocations, get_elf64_relocations};
+pub use super::{get_elf32_relative_count_problems, get_elf64_relative_count_problems};
+pub use super::{read_elf32_syms, read_elf64_syms};
+pub use super::{print_elf32_symbols, print_elf64_symbols};
+pub use super::{get_elf32_json_symbols, get_elf64_json_symbols};
+pub use super::{get_elf32_symbol_bytes, get_elf64_symbol_bytes};
+pub use super::elf_sym_dump::{SymbolLookup, print_symbol_bytes};
+pub use super::{print_elf32_compact_symbols, print_elf64_compact_symbols};
+pub use super::{read_elf32_symbol_versions, read_elf64_symbol_versions};
+pub use super::{print_elf32_imports, print_elf64_imports};
+pub use super::{print_elf32_exports, print_elf64_exports};
+pub use super::{print_elf32_version_info, print_elf64_version_info};
+pub use super::{get_elf32_fortify_info, get_elf64_fortify_info};
+pub use super::elf_dyn_tag::ElfDynTag;
+pub use super::elf_dyn_flags::{ElfDynFlags, ElfDynFlags1};
+pub use super::{Elf32_Dyn, Elf64_Dyn};
+pub use super::{read_elf32_dyns, read_elf64_dyns};
+pub use super::{elf32_has_dynamic_text_relocations, elf64_has_dynamic_text_relocations};
+pub use super::{print_elf32_dynamic, print_elf64_dynamic};
+pub use super::{get_elf32_dependencies, get_elf64_dependencies};
+pub use super::elf_needed::{print_needed, print_needed_dot, get_soname_problems};
+pub use super::elf_needed::split_search_path;
+pub use super::{print_elf32_histogram, print_elf64_histogram};
+pub use super::{print_elf32_packing_report, print_elf64_packing_report};
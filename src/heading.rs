@@ -0,0 +1,26 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// The titles, column headings and keys around tables are written through
+// `heading!`, so that --no-header can leave just the rows for other tools.
+
+static SHOWN: AtomicBool = AtomicBool::new(true);
+
+pub fn set_shown(shown: bool) {
+    SHOWN.store(shown, Ordering::Relaxed);
+}
+
+pub fn is_shown() -> bool {
+    SHOWN.load(Ordering::Relaxed)
+}
+
+// Like writeln!, but writes nothing under --no-header.
+#[macro_export]
+macro_rules! heading {
+    ($out:expr, $($arg:tt)*) => {{
+        if $crate::heading::is_shown() {
+            writeln!($out, $($arg)*)
+        } else {
+            Ok(())
+        }
+    }};
+}
@@ -6,105 +6,1506 @@ extern crate clap;
 #[macro_use]
 mod to_host;
 
+#[macro_use]
+mod warning;
+#[macro_use]
+mod heading;
+
 mod elf;
 use elf::prelude::*;
 
+#[cfg(target_os = "linux")]
+mod proc_maps;
+
 use clap::App;
 
+use std::fmt::{Display, Formatter};
 use std::fs::File;
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+// Processing a file fails either because the file itself is malformed or
+// because it (or the report) couldn't be read or written. Under --strict,
+// warnings about it are failures too.
+#[derive(Debug)]
+enum WorkError {
+    Elf(ElfError),
+    Io(io::Error),
+    Strict(usize),
+    // A section named on the command line which isn't there
+    NoSuchSection(String),
+    NotRelocationSection(String),
+    // A symbol named on the command line which isn't there, or which has no
+    // bytes in the file to show, and why
+    NoSuchSymbol(String),
+    SymbolNotDumpable(String, &'static str),
+    // --offset, and the size of the file
+    OffsetPastEnd(u64, u64),
+}
+
+impl From<ElfError> for WorkError {
+    fn from(e: ElfError) -> WorkError {
+        WorkError::Elf(e)
+    }
+}
+
+impl From<io::Error> for WorkError {
+    fn from(e: io::Error) -> WorkError {
+        WorkError::Io(e)
+    }
+}
+
+impl Display for WorkError {
+    fn fmt(&self, fmt: &mut Formatter) -> ::std::fmt::Result {
+        match *self {
+            WorkError::Elf(ref e) => write!(fmt, "{}", e),
+            WorkError::Io(ref e) => write!(fmt, "{}", e),
+            WorkError::Strict(1) =>
+                write!(fmt, "1 warning, which --strict treats as an error"),
+            WorkError::Strict(n) =>
+                write!(fmt, "{} warnings, which --strict treats as errors", n),
+            WorkError::NoSuchSection(ref name) =>
+                write!(fmt, "there is no section named '{}'", name),
+            WorkError::NotRelocationSection(ref name) =>
+                write!(fmt, "section '{}' does not hold relocations", name),
+            WorkError::NoSuchSymbol(ref name) =>
+                write!(fmt, "there is no symbol named '{}'", name),
+            WorkError::SymbolNotDumpable(ref name, why) =>
+                write!(fmt, "can't dump symbol '{}': {}", name, why),
+            WorkError::OffsetPastEnd(offset, size) =>
+                write!(fmt, "--offset {:#x} is past the end of the file, which is {:#x} bytes",
+                       offset, size),
+        }
+    }
+}
 
-fn work(options: clap::ArgMatches) {
-    let path = options.value_of("FILE").unwrap();
+// All reports go to `out` rather than straight to stdout, so the same code
+// serves --output and can be pointed at an in-memory buffer.
+fn work<W: Write>(options: &clap::ArgMatches, path: &str, out: &mut W)
+    -> Result<(), WorkError>
+{
+    let file = try!(File::open(path));
+    // With --offset, the ELF image starts that far into the file
+    let base = options.value_of("offset").and_then(parse_offset).unwrap_or(0);
+    let size = try!(file.metadata()).len();
+    if base != 0 && base >= size {
+        return Err(WorkError::OffsetPastEnd(base, size));
+    }
+    let mut f = IdentOverride::new(EmbeddedElf::new(file, base));
+
+    if options.is_present("scan") {
+        // The file itself needn't be an ELF
+        return print_scan(&mut f, base, out);
+    }
 
-    let mut f = File::open(path).unwrap();
+    if let Some(data) = options.value_of("force-endian").and_then(parse_force_endian) {
+        let ehdr = try!(read_elf32_ehdr(&mut f));
+        warning!(
+            "decoding as {} (--force-endian), the file says {}",
+            data, ehdr.get_data());
+        f.set_data(data);
+    }
+    let forced_class = options.value_of("force-class").and_then(parse_force_class);
+    let max_entries = options.value_of("max-entries").and_then(parse_max_entries);
+    if let Some(class) = forced_class {
+        let ehdr = try!(read_elf32_ehdr(&mut f));
+        warning!(
+            "decoding as {} (--force-class), the file says {}",
+            class, ehdr.get_class());
+        f.set_class(class);
+    }
 
     // FIXME: This is lazy guessing of bitness.
     // We first read Ehdr as 32-bit variant and then check 
     // if it's actually 32-bit. It works because ELF_EICLASS is always the same.
-    let ehdr = read_elf32_ehdr(&mut f);
-    let elf_ident = ehdr.get_ident();
-    let elf_class = elf_ident.get_class();
+    let ehdr = match read_elf32_ehdr(&mut f) {
+        Ok(ehdr) => ehdr,
+        Err(e) => {
+            try!(print_truncated_ident(options, e, &mut f, out));
+            return Err(WorkError::Elf(e));
+        }
+    };
+    if let Err(e) = ehdr.validate_ident() {
+        // The magic is fine, so what can be decoded is still worth showing.
+        if options.is_present("file-header") {
+            try!(ehdr.print_ident(out));
+            try!(out.flush());
+        }
+        return Err(WorkError::Elf(e));
+    }
+    let elf_class = ehdr.get_class();
+    // Read by whichever of the validation pass and the modes comes first
+    let elf32_shdrs = LazyElf32Shdrs::new();
+    let elf64_shdrs = LazyElf64Shdrs::new();
+    // The 64-bit header is longer, and is read unchecked from here on
+    if elf_class == ElfEiClass::ELFCLASS64 {
+        if let Err(e) = read_elf64_ehdr(&mut f) {
+            try!(print_truncated_ident(options, e, &mut f, out));
+            return Err(WorkError::Elf(e));
+        }
+    }
+
+    // The validation pass. Anything odd about the structure of the file is
+    // a warning, which --strict turns into an error. It yields the extent of
+    // the ELF image and the size of the file. It runs for --validate and
+    // --strict, and for --extract-overlay, which needs the extent; otherwise
+    // a mode reads no more of the file than it prints.
+    let validate = options.is_present("validate") || options.is_present("strict")
+        || options.is_present("extract-overlay");
+    let image = if validate {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+        let file_size = try!(f.seek(SeekFrom::End(0)));
+        let file_name = Path::new(path).file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or(path.to_string());
+        // Program headers are only read if their table is where it should be
+        let (layout_problems, problems, extent) = match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let phnum = ehdr.get_program_header_count(&mut f);
+                let shnum = ehdr.get_section_header_count(&mut f);
+                let layout_problems = ehdr.get_layout_problems(file_size, phnum, shnum);
+                let mut problems = ehdr.get_version_problems();
+                let shdrs = elf32_shdrs.get(&ehdr, &mut f).unwrap_or_default();
+                problems.extend(get_elf32_section_problems(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f));
+                let shdrs: Vec<Elf32_Shdr> =
+                    shdrs.iter().map(|s| s.to_host_copy(&e)).collect();
+                let file_phdrs = if layout_problems.is_empty() {
+                    read_elf32_phdrs(&ehdr, &mut f)
+                } else {
+                    Vec::new()
+                };
+                let phdrs: Vec<Elf32_Phdr> =
+                    file_phdrs.iter().map(|p| p.to_host_copy(&e)).collect();
+                problems.extend(get_elf32_load_order_problems(&phdrs));
+                problems.extend(get_elf32_tls_problems(&phdrs, &shdrs));
+                problems.extend(get_elf32_text_segment_problems(
+                    &phdrs, &shdrs, ehdr.get_shstrndx(), &mut f));
+                problems.extend(get_elf32_eh_frame_hdr_problems(
+                    &phdrs, &shdrs, ehdr.get_shstrndx(), &e, &mut f));
+                problems.extend(get_elf32_interpreter_problems(
+                    &phdrs, &shdrs, ehdr.get_shstrndx(), &mut f));
+                problems.extend(get_elf32_relative_count_problems(
+                    ehdr.get_machine(), &file_phdrs, &e, &mut f));
+                // Only --validate reads .dynamic here
+                if options.is_present("validate") {
+                    match get_elf32_dependencies(&file_phdrs, &e, &mut f) {
+                        Ok(Some(dependencies)) =>
+                            problems.extend(get_soname_problems(&dependencies, &file_name)),
+                        Ok(None) => {}
+                        Err(err) => problems.push(
+                            format!("the dynamic section can't be read: {}", err)),
+                    }
+                }
+                (layout_problems, problems, ehdr.get_used_extent(&phdrs, &shdrs))
+            }
+            _ => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let phnum = ehdr.get_program_header_count(&mut f);
+                let shnum = ehdr.get_section_header_count(&mut f);
+                let layout_problems = ehdr.get_layout_problems(file_size, phnum, shnum);
+                let mut problems = ehdr.get_version_problems();
+                let shdrs = elf64_shdrs.get(&ehdr, &mut f).unwrap_or_default();
+                problems.extend(get_elf64_section_problems(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f));
+                let shdrs: Vec<Elf64_Shdr> =
+                    shdrs.iter().map(|s| s.to_host_copy(&e)).collect();
+                let file_phdrs = if layout_problems.is_empty() {
+                    read_elf64_phdrs(&ehdr, &mut f)
+                } else {
+                    Vec::new()
+                };
+                let phdrs: Vec<Elf64_Phdr> =
+                    file_phdrs.iter().map(|p| p.to_host_copy(&e)).collect();
+                problems.extend(get_elf64_load_order_problems(&phdrs));
+                problems.extend(get_elf64_tls_problems(&phdrs, &shdrs));
+                problems.extend(get_elf64_text_segment_problems(
+                    &phdrs, &shdrs, ehdr.get_shstrndx(), &mut f));
+                problems.extend(get_elf64_eh_frame_hdr_problems(
+                    &phdrs, &shdrs, ehdr.get_shstrndx(), &e, &mut f));
+                problems.extend(get_elf64_interpreter_problems(
+                    &phdrs, &shdrs, ehdr.get_shstrndx(), &mut f));
+                problems.extend(get_elf64_relative_count_problems(
+                    ehdr.get_machine(), &file_phdrs, &e, &mut f));
+                // Only --validate reads .dynamic here
+                if options.is_present("validate") {
+                    match get_elf64_dependencies(&file_phdrs, &e, &mut f) {
+                        Ok(Some(dependencies)) =>
+                            problems.extend(get_soname_problems(&dependencies, &file_name)),
+                        Ok(None) => {}
+                        Err(err) => problems.push(
+                            format!("the dynamic section can't be read: {}", err)),
+                    }
+                }
+                (layout_problems, problems, ehdr.get_used_extent(&phdrs, &shdrs))
+            }
+        };
+        let mut problems: Vec<String> = layout_problems.into_iter()
+            .map(|problem| match forced_class {
+                Some(_) => format!("with the forced class, {}", problem),
+                None => problem,
+            })
+            .chain(problems)
+            .collect();
+        if file_size > extent {
+            // Self-extracting archives and installers append their payload
+            problems.push(format!(
+                "{} bytes of data are appended after the ELF image, at offset {:#x}",
+                file_size - extent, extent));
+        }
+        for problem in &problems {
+            warning!("{}", problem);
+        }
+
+        if options.is_present("validate") {
+            try!(writeln!(out, ""));
+            try!(writeln!(out, "Validation report:"));
+            if problems.is_empty() {
+                try!(writeln!(out, "  No structural problems found."));
+            }
+            for problem in &problems {
+                try!(writeln!(out, "  {}", problem));
+            }
+            try!(writeln!(
+                out, "  trailing bytes after ELF: {}", file_size.saturating_sub(extent)));
+        }
+        Some((extent, file_size))
+    } else {
+        None
+    };
+
+    if let (Some(path), Some((extent, file_size))) = (options.value_of("extract-overlay"), image) {
+        if file_size > extent {
+            let mut overlay = Vec::new();
+            try!(f.seek(SeekFrom::Start(extent)));
+            try!((&mut f).take(file_size - extent).read_to_end(&mut overlay));
+            try!(try!(File::create(path)).write_all(&overlay));
+            try!(writeln!(
+                out, "Wrote {} bytes of appended data, from offset {:#x}, to {}",
+                overlay.len(), extent, path));
+        } else {
+            try!(writeln!(out, "There is no appended data in this file."));
+        }
+    }
+
+    if let Some(path) = options.value_of("extract") {
+        let written = try!(write_to(&mut f, &mut try!(File::create(path))));
+        try!(writeln!(out, "Wrote {} bytes of the ELF image to {}", written, path));
+    }
 
     if options.is_present("file-header") {
         if elf_class != ElfEiClass::ELFCLASS32 {
             // Reread the header as Elf64_Ehdr
-            let ehdr = read_elf64_ehdr(&mut f);
-            print!("{}", ehdr);
+            let ehdr = try!(read_elf64_ehdr(&mut f));
+            try!(write!(out, "{}", ehdr));
         } else {
-            print!("{}", ehdr);
+            try!(write!(out, "{}", ehdr));
         }
     }
 
-    if options.is_present("program-headers")
-    || options.is_present("segments") {
+    if options.is_present("raw-header") {
+        if elf_class != ElfEiClass::ELFCLASS32 {
+            let ehdr = try!(read_elf64_ehdr(&mut f));
+            try!(ehdr.print_raw(out));
+        } else {
+            try!(ehdr.print_raw(out));
+        }
+    }
+
+    if options.is_present("offsets-table") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let phnum = ehdr.get_program_header_count(&mut f);
+                let shnum = elf32_shdrs.get(&ehdr, &mut f).map(|s| s.len()).unwrap_or(0);
+                try!(ehdr.print_offsets(base, phnum, shnum, out));
+            }
+            _ => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let phnum = ehdr.get_program_header_count(&mut f);
+                let shnum = elf64_shdrs.get(&ehdr, &mut f).map(|s| s.len()).unwrap_or(0);
+                try!(ehdr.print_offsets(base, phnum, shnum, out));
+            }
+        }
+    }
+
+    // With --format=text, json, csv, yaml or stable (--json and --stable are
+    // short for those), the headers go into the report instead
+    let format = if options.is_present("json") {
+        OutputFormat::Json
+    } else if options.is_present("stable") {
+        OutputFormat::Stable
+    } else {
+        options.value_of("format").and_then(OutputFormat::parse).unwrap_or(OutputFormat::Readelf)
+    };
+    let structured = format.is_structured();
+
+    if !structured && (options.is_present("program-headers")
+    || options.is_present("segments")) {
         use to_host::ToHostCopyStruct;
         // FIXME: All of this is broken due to ehdr being always 32-bit here.
 
         let ehdr = ehdr.to_host_copy(&ehdr.get_endianness());
         let e = ehdr.get_endianness();
-        let e_type: ElfEhdrType = unsafe {
-            std::mem::transmute(ehdr.get_type())
+        // The fields past e_version are at different offsets in the two
+        // classes
+        let (e_type, entry, phnum, phoff) = match elf_class {
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                (ehdr.get_type(), ehdr.get_entry() as u64,
+                 ehdr.get_program_header_count(&mut f), ehdr.get_phoff() as u64)
+            }
+            _ => (ehdr.get_type(), ehdr.get_entry() as u64,
+                  ehdr.get_program_header_count(&mut f), ehdr.get_phoff() as u64),
         };
 
-        println!("");
-        println!("Elf file type is {}", e_type);
-        println!("Entry point {:#x}", ehdr.get_entry());
-        println!(
-            "There are {} program headers, starting at offset {}",
-            ehdr.get_phnum(), ehdr.get_phoff());
-        println!("");
+        if phnum == 0 {
+            // Relocatable objects have no segments, only sections
+            try!(writeln!(out, ""));
+            try!(writeln!(out, "There are no program headers in this file."));
+            // Without PT_INTERP, .interp may still say what the file would be
+            // run with, for example once the program headers are stripped
+            let interpreter = match elf_class {
+                ElfEiClass::ELFCLASS32 => {
+                    let shdrs: Vec<Elf32_Shdr> = elf32_shdrs.get(&ehdr, &mut f)
+                        .unwrap_or_default().iter().map(|s| s.to_host_copy(&e)).collect();
+                    get_elf32_interpreter(&[], &shdrs, ehdr.get_shstrndx(), &mut f)
+                }
+                ElfEiClass::ELFCLASS64 => {
+                    let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                    let shdrs: Vec<Elf64_Shdr> = elf64_shdrs.get(&ehdr, &mut f)
+                        .unwrap_or_default().iter().map(|s| s.to_host_copy(&e)).collect();
+                    get_elf64_interpreter(&[], &shdrs, ehdr.get_shstrndx(), &mut f)
+                }
+                _ => None,
+            };
+            if let Some(interpreter) = interpreter {
+                try!(writeln!(
+                    out, "The .interp section requests program interpreter: {}", interpreter));
+            }
+        } else {
+            try!(heading!(out, ""));
+            try!(heading!(out, "Elf file type is {}", get_type_string(e_type)));
+            try!(heading!(out, "Entry point {:#x}", entry));
+            try!(heading!(
+                out,
+                "There are {} program headers, starting at offset {}",
+                phnum, phoff));
+            try!(heading!(out, ""));
+        }
+
+        if phnum != 0 {
+            match elf_class {
+                // FIXME: This fugly code is due to ehdr and phdrs being of 
+                // different type in different branches of control flow
+                ElfEiClass::ELFCLASS32 => {
+                    let phdrs = read_elf32_phdrs(&ehdr, &mut f);
+
+                    try!(heading!(out, "Program headers:"));
+                    try!(heading!(
+                        out,
+                        concat!(
+                            "  ",
+                            "Type           ",
+                            "Offset   ",
+                            "VirtAddr           ",
+                            "PhysAddr           ",
+                            "FileSiz  ",
+                            "MemSiz   ",
+                            "Flg ",
+                            "Align")));
+                    let mut limit = EntryLimit::new(max_entries);
+                    for phdr in &phdrs {
+                        if !limit.take() {
+                            continue;
+                        }
+                        try!(write!(out, "  "));
+                        try!(phdr.print_with_endianness(&e, out));
+                        try!(writeln!(out, ""));
+
+                        let phdr = phdr.to_host_copy(&e);
+                        if phdr.get_type() == ElfPhdrType::PT_INTERP.get_value() {
+                            let interpreter = get_elf32_interpreter(&[phdr], &[], 0, &mut f);
+                            if let Some(interpreter) = interpreter {
+                                try!(writeln!(
+                                    out, "      [Requesting program interpreter: {}]", interpreter));
+                            }
+                        }
+                        if phdr.get_type() == ElfPhdrType::PT_GNU_PROPERTY.get_value() {
+                            for property in phdr.read_gnu_properties(&e, &mut f) {
+                                try!(writeln!(out, "      [Property: {}]", property));
+                            }
+                        }
+                    }
+                    try!(limit.print_omitted(out));
+
+                    let phdrs: Vec<Elf32_Phdr> =
+                        phdrs.iter().map(|p| p.to_host_copy(&e)).collect();
+                    // Informational: JIT-heavy programs may want this
+                    for i in get_elf32_rwx_segments(&phdrs) {
+                        try!(writeln!(
+                            out,
+                            " Note: segment {} is readable, writable and executable, which \
+                             breaks W^X (expected only in programs that generate code at run time)",
+                            i));
+                    }
+                    let loads: Vec<(usize, u64, u64)> = phdrs.iter().enumerate()
+                        .filter(|&(_, p)| p.get_type() == ElfPhdrType::PT_LOAD.get_value())
+                        .map(|(i, p)| (i, p.get_offset() as u64, p.get_vaddr() as u64))
+                        .collect();
+                    try!(print_process_segments(options, path, &loads, out));
+                }
+                ElfEiClass::ELFCLASS64 => {
+                    let ehdr = try!(read_elf64_ehdr(&mut f));
+                    let phdrs = read_elf64_phdrs(&ehdr, &mut f);
+
+                    try!(heading!(out, "Program headers:"));
+                    try!(heading!(
+                        out,
+                        concat!(
+                            "  ",
+                            "Type           ",
+                            "Offset   ",
+                            "VirtAddr           ",
+                            "PhysAddr           ",
+                            "FileSiz  ",
+                            "MemSiz   ",
+                            "Flg ",
+                            "Align")));
+                    let mut limit = EntryLimit::new(max_entries);
+                    for phdr in &phdrs {
+                        if !limit.take() {
+                            continue;
+                        }
+                        try!(write!(out, "  "));
+                        try!(phdr.print_with_endianness(&e, out));
+                        try!(writeln!(out, ""));
+
+                        let phdr = phdr.to_host_copy(&e);
+                        if phdr.get_type() == ElfPhdrType::PT_INTERP.get_value() {
+                            let interpreter = get_elf64_interpreter(&[phdr], &[], 0, &mut f);
+                            if let Some(interpreter) = interpreter {
+                                try!(writeln!(
+                                    out, "      [Requesting program interpreter: {}]", interpreter));
+                            }
+                        }
+                        if phdr.get_type() == ElfPhdrType::PT_GNU_PROPERTY.get_value() {
+                            for property in phdr.read_gnu_properties(&e, &mut f) {
+                                try!(writeln!(out, "      [Property: {}]", property));
+                            }
+                        }
+                    }
+                    try!(limit.print_omitted(out));
+
+                    let phdrs: Vec<Elf64_Phdr> =
+                        phdrs.iter().map(|p| p.to_host_copy(&e)).collect();
+                    // Informational: JIT-heavy programs may want this
+                    for i in get_elf64_rwx_segments(&phdrs) {
+                        try!(writeln!(
+                            out,
+                            " Note: segment {} is readable, writable and executable, which \
+                             breaks W^X (expected only in programs that generate code at run time)",
+                            i));
+                    }
+                    let loads: Vec<(usize, u64, u64)> = phdrs.iter().enumerate()
+                        .filter(|&(_, p)| p.get_type() == ElfPhdrType::PT_LOAD.get_value())
+                        .map(|(i, p)| (i, p.get_offset() as u64, p.get_vaddr() as u64))
+                        .collect();
+                    try!(print_process_segments(options, path, &loads, out));
+                }
+                _ => {
+                    try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+                }
+            }
+        }
+    }
+
+    if !structured && (options.is_present("section-headers")
+    || options.is_present("sections")
+    || options.is_present("section-details")
+    || options.is_present("long-sections")) {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+        let alloc_only = options.is_present("alloc-only");
+        let details = options.is_present("section-details");
+        let links = options.is_present("long-sections");
+        let pow2_align = options.is_present("align-pow2");
+        let order = options.value_of("sort").and_then(SectionOrder::parse)
+            .unwrap_or(SectionOrder::Index);
 
         match elf_class {
-            // FIXME: This fugly code is due to ehdr and phdrs being of 
-            // different type in different branches of control flow
             ElfEiClass::ELFCLASS32 => {
-                let phdrs = read_elf32_phdrs(&ehdr, &mut f);
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                if links {
+                    try!(print_elf32_section_links(
+                        ehdr.get_machine(), &shdrs, ehdr.get_shoff(),
+                        ehdr.get_shstrndx(), &e, &mut f, out, alloc_only, order,
+                        max_entries));
+                } else if details {
+                    try!(print_elf32_section_details(
+                        ehdr.get_machine(), &shdrs, ehdr.get_shoff(),
+                        ehdr.get_shstrndx(), &e, &mut f, out, alloc_only, order,
+                        max_entries, pow2_align));
+                } else {
+                    try!(print_elf32_sections(
+                        ehdr.get_machine(), &shdrs, ehdr.get_shoff(),
+                        ehdr.get_shstrndx(), &e, &mut f, out, alloc_only, order,
+                        max_entries, pow2_align));
+                }
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                if links {
+                    try!(print_elf64_section_links(
+                        ehdr.get_machine(), &shdrs, ehdr.get_shoff(),
+                        ehdr.get_shstrndx(), &e, &mut f, out, alloc_only, order,
+                        max_entries));
+                } else if details {
+                    try!(print_elf64_section_details(
+                        ehdr.get_machine(), &shdrs, ehdr.get_shoff(),
+                        ehdr.get_shstrndx(), &e, &mut f, out, alloc_only, order,
+                        max_entries, pow2_align));
+                } else {
+                    try!(print_elf64_sections(
+                        ehdr.get_machine(), &shdrs, ehdr.get_shoff(),
+                        ehdr.get_shstrndx(), &e, &mut f, out, alloc_only, order,
+                        max_entries, pow2_align));
+                }
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if !structured && options.is_present("syms") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+        let legend = options.is_present("legend");
+        let relative = options.is_present("sym-relative");
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_symbols(
+                    ehdr.get_machine(), &shdrs, ehdr.get_shstrndx(), &e, &mut f, out,
+                    legend, relative, max_entries));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_symbols(
+                    ehdr.get_machine(), &shdrs, ehdr.get_shstrndx(), &e, &mut f, out,
+                    legend, relative, max_entries));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("compact-symbols") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_compact_symbols(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out, max_entries));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_compact_symbols(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out, max_entries));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("imports") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_imports(
+                    &shdrs, &e, &mut f, out, max_entries));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_imports(
+                    &shdrs, &e, &mut f, out, max_entries));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("exports") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_exports(
+                    &shdrs, &e, &mut f, out, max_entries));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_exports(
+                    &shdrs, &e, &mut f, out, max_entries));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("version-info") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_version_info(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out, max_entries));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_version_info(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out, max_entries));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("relocs") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+        let only_section = options.value_of("only-section");
+        let check_only_section = |is_relocation_section: Option<bool>| {
+            match (only_section, is_relocation_section) {
+                (Some(name), None) => Err(WorkError::NoSuchSection(name.to_string())),
+                (Some(name), Some(false)) =>
+                    Err(WorkError::NotRelocationSection(name.to_string())),
+                _ => Ok(()),
+            }
+        };
 
-                println!("Program headers:");
-                println!(
-                    concat!(
-                        "  ",
-                        "Type           ",
-                        "Offset   ",
-                        "VirtAddr           ",
-                        "PhysAddr           ",
-                        "FileSiz  ",
-                        "MemSiz   ",
-                        "Flg ",
-                        "Align"));
-                for phdr in phdrs {
-                    print!("  ");
-                    phdr.print_with_endianness(&e);
-                    println!("");
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                if let Some(name) = only_section {
+                    try!(check_only_section(is_elf32_relocation_section(
+                        &shdrs, ehdr.get_shstrndx(), &e, &mut f, name)));
                 }
+                try!(print_elf32_relocations(
+                    ehdr.get_machine(), &shdrs, ehdr.get_shstrndx(), &e,
+                    &mut f, out, max_entries, only_section));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                if let Some(name) = only_section {
+                    try!(check_only_section(is_elf64_relocation_section(
+                        &shdrs, ehdr.get_shstrndx(), &e, &mut f, name)));
+                }
+                try!(print_elf64_relocations(
+                    ehdr.get_machine(), &shdrs, ehdr.get_shstrndx(), &e,
+                    &mut f, out, max_entries, only_section));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("plt-stubs") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_plt_stubs(
+                    ehdr.get_machine(), &shdrs, ehdr.get_shstrndx(), &e,
+                    &mut f, out, max_entries));
             }
             ElfEiClass::ELFCLASS64 => {
-                let ehdr = read_elf64_ehdr(&mut f);
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_plt_stubs(
+                    ehdr.get_machine(), &shdrs, ehdr.get_shstrndx(), &e,
+                    &mut f, out, max_entries));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("dynamic") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let phdrs = read_elf32_phdrs(&ehdr, &mut f);
+                try!(print_elf32_dynamic(&phdrs, &e, &mut f, out, max_entries));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let phdrs = read_elf64_phdrs(&ehdr, &mut f);
+                try!(print_elf64_dynamic(&phdrs, &e, &mut f, out, max_entries));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("needed") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        let dependencies = match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let phdrs = read_elf32_phdrs(&ehdr, &mut f);
+                try!(get_elf32_dependencies(&phdrs, &e, &mut f))
+            }
+            _ => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
                 let phdrs = read_elf64_phdrs(&ehdr, &mut f);
+                try!(get_elf64_dependencies(&phdrs, &e, &mut f))
+            }
+        };
+        // Without .dynamic the graph is the file on its own
+        let dependencies = dependencies.unwrap_or_default();
+        if format == OutputFormat::Dot {
+            let file_name = Path::new(path).file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or(path.to_string());
+            try!(print_needed_dot(&dependencies, &file_name, out));
+        } else {
+            try!(print_needed(&dependencies, out));
+        }
+    }
+
+    if options.is_present("histogram") {
+        use to_host::ToHostCopyStruct;
 
-                println!("Program headers:");
-                println!(
-                    concat!(
-                        "  ",
-                        "Type           ",
-                        "Offset   ",
-                        "VirtAddr           ",
-                        "PhysAddr           ",
-                        "FileSiz  ",
-                        "MemSiz   ",
-                        "Flg ",
-                        "Align"));
-                for phdr in phdrs {
-                    print!("  ");
-                    phdr.print_with_endianness(&e);
-                    println!("");
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let phdrs = read_elf32_phdrs(&ehdr, &mut f);
+                try!(print_elf32_histogram(&phdrs, &e, &mut f, out));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let phdrs = read_elf64_phdrs(&ehdr, &mut f);
+                try!(print_elf64_histogram(&phdrs, &e, &mut f, out));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("notes") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+        let raw = options.is_present("raw-notes");
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_notes(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out, raw));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_notes(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out, raw));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("count") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        let counts = match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs: Vec<Elf32_Shdr> = try!(elf32_shdrs.get(&ehdr, &mut f)).iter()
+                    .map(|s| s.to_host_copy(&e)).collect();
+                let phdrs: Vec<Elf32_Phdr> = read_elf32_phdrs(&ehdr, &mut f).iter()
+                    .map(|p| p.to_host_copy(&e)).collect();
+                get_elf32_structure_counts(&phdrs, &shdrs, &e, &mut f)
+            }
+            _ => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs: Vec<Elf64_Shdr> = try!(elf64_shdrs.get(&ehdr, &mut f)).iter()
+                    .map(|s| s.to_host_copy(&e)).collect();
+                let phdrs: Vec<Elf64_Phdr> = read_elf64_phdrs(&ehdr, &mut f).iter()
+                    .map(|p| p.to_host_copy(&e)).collect();
+                get_elf64_structure_counts(&phdrs, &shdrs, &e, &mut f)
+            }
+        };
+        try!(writeln!(out, "{}", counts));
+    }
+
+    if options.is_present("relative-offsets") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs: Vec<Elf32_Shdr> = try!(elf32_shdrs.get(&ehdr, &mut f)).iter()
+                    .map(|s| s.to_host_copy(&e)).collect();
+                let phdrs: Vec<Elf32_Phdr> = read_elf32_phdrs(&ehdr, &mut f).iter()
+                    .map(|p| p.to_host_copy(&e)).collect();
+                try!(print_elf32_relative_offsets(&ehdr, &phdrs, &shdrs, &mut f, out));
+            }
+            _ => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs: Vec<Elf64_Shdr> = try!(elf64_shdrs.get(&ehdr, &mut f)).iter()
+                    .map(|s| s.to_host_copy(&e)).collect();
+                let phdrs: Vec<Elf64_Phdr> = read_elf64_phdrs(&ehdr, &mut f).iter()
+                    .map(|p| p.to_host_copy(&e)).collect();
+                try!(print_elf64_relative_offsets(&ehdr, &phdrs, &shdrs, &mut f, out));
+            }
+        }
+    }
+
+    if options.is_present("arm-exidx") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_arm_exidx(
+                    ehdr.get_machine(), &shdrs, ehdr.get_shstrndx(), &e, &mut f, out));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_arm_exidx(
+                    ehdr.get_machine(), &shdrs, ehdr.get_shstrndx(), &e, &mut f, out));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("debuglink") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_debuglink(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_debuglink(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("debug-info") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_debug_info(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_debug_info(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if options.is_present("debug-line") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_debug_line(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_debug_line(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if let Some(debugfile) = options.value_of("check-debuglink") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        let link = match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                read_elf32_debuglink(&shdrs, ehdr.get_shstrndx(), &e, &mut f)
+            }
+            _ => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                read_elf64_debuglink(&shdrs, ehdr.get_shstrndx(), &e, &mut f)
+            }
+        };
+
+        let mut data = Vec::new();
+        try!(try!(File::open(debugfile)).read_to_end(&mut data));
+        let crc = get_crc32(&data);
+
+        match link {
+            Some(ref link) if link.get_crc() == crc => try!(writeln!(
+                out,
+                "CRC of {} matches .gnu_debuglink: {:#x}", debugfile, crc)),
+            Some(ref link) => try!(writeln!(
+                out,
+                "CRC of {} is {:#x}, but .gnu_debuglink expects {:#x}",
+                debugfile, crc, link.get_crc())),
+            None => try!(writeln!(
+                out, "There is no .gnu_debuglink section in this file.")),
+        }
+    }
+
+    if options.is_present("entropy") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_section_entropy(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out, max_entries));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_section_entropy(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out, max_entries));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if let Some(sections) = options.value_of("hex-dump") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+        // clap can't take the option more than once, so several sections are
+        // given as a list
+        let sections: Vec<&str> = sections.split(',').collect();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_hex_dumps(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out, &sections));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_hex_dumps(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out, &sections));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if let Some(name) = options.value_of("dump-symbol") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        let lookup = match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                Some(get_elf32_symbol_bytes(&shdrs, ehdr.get_shstrndx(), &e, &mut f, name))
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                Some(get_elf64_symbol_bytes(&shdrs, ehdr.get_shstrndx(), &e, &mut f, name))
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+                None
+            }
+        };
+        let not_dumpable = |why| WorkError::SymbolNotDumpable(name.to_string(), why);
+        match lookup {
+            Some(SymbolLookup::Found(symbol)) => try!(print_symbol_bytes(&symbol, out)),
+            Some(SymbolLookup::NotFound) =>
+                return Err(WorkError::NoSuchSymbol(name.to_string())),
+            Some(SymbolLookup::NoSize) =>
+                return Err(not_dumpable("it has no size")),
+            Some(SymbolLookup::NotInFile) =>
+                return Err(not_dumpable("it has no contents in the file")),
+            Some(SymbolLookup::OutsideSection) =>
+                return Err(not_dumpable("it runs past the end of its section")),
+            Some(SymbolLookup::Unreadable(err)) => return Err(WorkError::Elf(err)),
+            None => {}
+        }
+    }
+
+    if options.is_present("strings") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+        let min_len = options.value_of("min-len").and_then(parse_min_len).unwrap_or(4);
+        let alloc_only = options.is_present("alloc-only");
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_strings(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out,
+                    min_len, alloc_only, max_entries));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_strings(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f, out,
+                    min_len, alloc_only, max_entries));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    if structured {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+        let segments = options.is_present("program-headers")
+            || options.is_present("segments");
+        let section_details = options.is_present("section-headers")
+            || options.is_present("sections");
+        let symbols = options.is_present("syms");
+
+        let report = match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                let sections = get_elf32_json_sections(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f);
+                let mut report = JsonReport::new(
+                    ehdr.get_target(), ehdr.get_type(), ehdr.get_entry() as u64,
+                    sections);
+                report.set_section_details(section_details);
+                if segments {
+                    let phdrs: Vec<Elf32_Phdr> = read_elf32_phdrs(&ehdr, &mut f).iter()
+                        .map(|p| p.to_host_copy(&e)).collect();
+                    report.set_segments(get_elf32_json_segments(&phdrs));
+                }
+                if symbols {
+                    report.set_symbols(get_elf32_json_symbols(
+                        &shdrs, ehdr.get_shstrndx(), &e, &mut f));
                 }
+                Some(report)
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                let sections = get_elf64_json_sections(
+                    &shdrs, ehdr.get_shstrndx(), &e, &mut f);
+                let mut report = JsonReport::new(
+                    ehdr.get_target(), ehdr.get_type(), ehdr.get_entry() as u64,
+                    sections);
+                report.set_section_details(section_details);
+                if segments {
+                    let phdrs: Vec<Elf64_Phdr> = read_elf64_phdrs(&ehdr, &mut f).iter()
+                        .map(|p| p.to_host_copy(&e)).collect();
+                    report.set_segments(get_elf64_json_segments(&phdrs));
+                }
+                if symbols {
+                    report.set_symbols(get_elf64_json_symbols(
+                        &shdrs, ehdr.get_shstrndx(), &e, &mut f));
+                }
+                Some(report)
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+                None
+            }
+        };
+        match (report, format) {
+            (Some(report), OutputFormat::Text) => try!(report.report(&mut TextReporter::new(), out)),
+            (Some(report), OutputFormat::Csv) => try!(report.report(&mut CsvReporter::new(), out)),
+            (Some(report), OutputFormat::Yaml) => try!(report.report(&mut YamlReporter, out)),
+            (Some(report), OutputFormat::Stable) => try!(report.report(&mut StableReporter, out)),
+            (Some(report), _) => try!(report.report(&mut JsonReporter, out)),
+            (None, _) => {}
+        }
+    }
+
+    if options.is_present("fortify") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        let info = match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                get_elf32_fortify_info(&shdrs, &e, &mut f)
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                get_elf64_fortify_info(&shdrs, &e, &mut f)
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+                None
+            }
+        };
+        match info {
+            Some(info) => try!(info.print(out)),
+            None => warning!(
+                "no dynamic symbol table, can't tell whether the file is fortified"),
+        }
+    }
+
+    if options.is_present("checksec") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        let checksec = match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let phdrs: Vec<Elf32_Phdr> = read_elf32_phdrs(&ehdr, &mut f).iter()
+                    .map(|p| p.to_host_copy(&e)).collect();
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                let mut checksec = Checksec::new();
+                checksec.set_textrel(elf32_has_dynamic_text_relocations(&phdrs, &e, &mut f));
+                checksec.set_rwx(has_elf32_rwx_segment(&phdrs));
+                checksec.set_fortify(get_elf32_fortify_info(&shdrs, &e, &mut f));
+                Some(checksec)
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let phdrs: Vec<Elf64_Phdr> = read_elf64_phdrs(&ehdr, &mut f).iter()
+                    .map(|p| p.to_host_copy(&e)).collect();
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                let mut checksec = Checksec::new();
+                checksec.set_textrel(elf64_has_dynamic_text_relocations(&phdrs, &e, &mut f));
+                checksec.set_rwx(has_elf64_rwx_segment(&phdrs));
+                checksec.set_fortify(get_elf64_fortify_info(&shdrs, &e, &mut f));
+                Some(checksec)
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+                None
+            }
+        };
+        if let Some(checksec) = checksec {
+            try!(checksec.print(out));
+        }
+    }
+
+    if options.is_present("packing") {
+        use to_host::ToHostCopyStruct;
+
+        let e = ehdr.get_endianness();
+
+        match elf_class {
+            ElfEiClass::ELFCLASS32 => {
+                let ehdr = ehdr.to_host_copy(&e);
+                let phdrs = read_elf32_phdrs(&ehdr, &mut f);
+                let shdrs = try!(elf32_shdrs.get(&ehdr, &mut f));
+                try!(print_elf32_packing_report(
+                    &ehdr, &phdrs, &shdrs, &e, &mut f, out));
+            }
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut f)).to_host_copy(&e);
+                let phdrs = read_elf64_phdrs(&ehdr, &mut f);
+                let shdrs = try!(elf64_shdrs.get(&ehdr, &mut f));
+                try!(print_elf64_packing_report(
+                    &ehdr, &phdrs, &shdrs, &e, &mut f, out));
+            }
+            _ => {
+                try!(writeln!(out, "This ELF file has ELFCLASSNONE. We can't get its bitness"));
+            }
+        }
+    }
+
+    try!(out.flush());
+    Ok(())
+}
+
+fn parse_force_endian(value: &str) -> Option<ElfEiData> {
+    match value {
+        "little" => Some(ElfEiData::ELFDATA2LSB),
+        "big" => Some(ElfEiData::ELFDATA2MSB),
+        _ => None,
+    }
+}
+
+fn parse_force_class(value: &str) -> Option<ElfEiClass> {
+    match value {
+        "32" => Some(ElfEiClass::ELFCLASS32),
+        "64" => Some(ElfEiClass::ELFCLASS64),
+        _ => None,
+    }
+}
+
+fn parse_max_entries(value: &str) -> Option<usize> {
+    value.parse().ok()
+}
+
+fn parse_min_len(value: &str) -> Option<usize> {
+    value.parse().ok().and_then(|n| if n > 0 { Some(n) } else { None })
+}
+
+fn parse_pid(value: &str) -> Option<u32> {
+    value.parse().ok()
+}
+
+// Decimal, or hex with 0x
+fn parse_offset(value: &str) -> Option<u64> {
+    if value.starts_with("0x") || value.starts_with("0X") {
+        u64::from_str_radix(&value[2..], 16).ok()
+    } else {
+        value.parse().ok()
+    }
+}
+
+// With --pid, where the segments of the process's executable are mapped.
+#[cfg(target_os = "linux")]
+fn print_process_segments<W: Write>(
+    options: &clap::ArgMatches, path: &str, loads: &[(usize, u64, u64)], out: &mut W)
+    -> io::Result<()>
+{
+    let pid = match options.value_of("pid").and_then(parse_pid) {
+        Some(pid) if path == proc_maps::get_exe_path(pid) => pid,
+        _ => return Ok(()),
+    };
+    match proc_maps::read_exe_maps(pid) {
+        Ok(maps) => proc_maps::print_mapped_segments(pid, loads, &maps, out),
+        Err(e) => {
+            warning!("can't read the mappings of process {}: {}", pid, e);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn print_process_segments<W: Write>(
+    _: &clap::ArgMatches, _: &str, _: &[(usize, u64, u64)], _: &mut W)
+    -> io::Result<()>
+{
+    Ok(())
+}
+
+// The files named on the command line, then the executable of the --pid
+// process.
+fn get_input_paths(options: &clap::ArgMatches) -> Vec<String> {
+    let mut paths: Vec<String> = options.values_of("FILE").unwrap_or(Vec::new())
+        .iter().map(|p| p.to_string()).collect();
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(pid) = options.value_of("pid").and_then(parse_pid) {
+            paths.push(proc_maps::get_exe_path(pid));
+        }
+    }
+    paths
+}
+
+// The ELF headers embedded anywhere in the file (after `base`), each with
+// its file offset, to be given to --offset, and what it is for.
+fn print_scan<R: Read + Seek, W: Write>(reader: &mut R, base: u64, out: &mut W)
+    -> Result<(), WorkError>
+{
+    use to_host::ToHostCopyStruct;
+
+    let mut data = Vec::new();
+    try!(reader.seek(SeekFrom::Start(0)));
+    try!(reader.read_to_end(&mut data));
+    let offsets = find_elf_headers(&data);
+    if offsets.is_empty() {
+        try!(writeln!(out, "No ELF headers found."));
+        return Ok(());
+    }
+    try!(heading!(out, "ELF headers found:"));
+    try!(heading!(out, "  {:<18} {:<5} {}", "Offset", "Type", "Target"));
+    for offset in offsets {
+        let mut image = EmbeddedElf::new(io::Cursor::new(&data[..]), offset);
+        let ehdr = try!(read_elf32_ehdr(&mut image));
+        let e = ehdr.get_endianness();
+        // Both raw, as a blob can hold images for any machine, and
+        // is_plausible_header lets OS- and processor-specific types through
+        let (e_type, target) = match ehdr.get_class() {
+            ElfEiClass::ELFCLASS64 => {
+                let ehdr = try!(read_elf64_ehdr(&mut image)).to_host_copy(&e);
+                (ehdr.get_type(), ehdr.get_target())
+            }
+            _ => {
+                let ehdr = ehdr.to_host_copy(&e);
+                (ehdr.get_type(), ehdr.get_target())
             }
-            ElfEiClass::ELFCLASSNONE => {
-                println!("This ELF file has ELFCLASSNONE. We can't get its bitness");
+        };
+        try!(writeln!(
+            out, "  {:#018x} {:<5} {}", base + offset, get_type_short_name(e_type), target));
+    }
+    Ok(())
+}
+
+// A header cut short still has its identification, which -h shows before
+// the error says the rest is missing.
+fn print_truncated_ident<R: Read + Seek, W: Write>(
+    options: &clap::ArgMatches, error: ElfError, reader: &mut R, out: &mut W)
+    -> io::Result<()>
+{
+    if let ElfError::TruncatedHeader(_) = error {
+        if options.is_present("file-header") {
+            if let Some(ident) = try!(ElfIdent::read(reader)) {
+                try!(ident.print(out));
+                try!(out.flush());
             }
         }
     }
+    Ok(())
+}
+
+// Processes every file in turn. A file that fails is reported and skipped,
+// and the return value tells whether any did.
+fn work_all<W: Write>(options: &clap::ArgMatches, out: &mut W) -> bool {
+    let paths = get_input_paths(options);
+    let strict = options.is_present("strict");
+    let mut ok = true;
+
+    for path in &paths {
+        let warnings = warning::get_count();
+        if paths.len() > 1 {
+            // Without this the reports of different files are
+            // indistinguishable.
+            if let Err(e) = heading!(out, "").and_then(|_| heading!(out, "File: {}", path)) {
+                eprintln!("Error: {}", e);
+                return false;
+            }
+        }
+        let result = work(options, &path, out).and_then(|_| {
+            match warning::get_count() - warnings {
+                n if strict && n > 0 => Err(WorkError::Strict(n)),
+                _ => Ok(()),
+            }
+        });
+        if let Err(e) = result {
+            if paths.len() > 1 {
+                eprintln!("Error: {}: {}", path, e);
+            } else {
+                eprintln!("Error: {}", e);
+            }
+            ok = false;
+        }
+    }
+    ok
 }
 
 fn process_args_and_work() {
@@ -117,11 +1518,146 @@ fn process_args_and_work() {
                     " Similar to readelf, but is not fully compatible."))
         .args_from_usage(
             "-h --file-header     'Display ELF file header'
+                --raw-header      'Display the ELF file header as an annotated hex dump'
+                --offsets-table   'Display where e_phoff, e_shoff and the header tables are in the file'
              -l --program-headers 'Display the program headers'
                 --segments        'An alias for --program-headers'
-             <FILE> 'ELF file to parse'")
+             -S --section-headers 'Display the section headers'
+                --sections        'An alias for --section-headers'
+             -t --section-details 'Display the section details'
+                --long-sections   'Display the sections with sh_link and sh_info spelled out'
+                --alloc-only      'Only display sections occupying memory at run time'
+                --align-pow2      'Display section alignments as powers of two'
+             [sort] --sort=[key] 'Sort the section table by index, addr, size or name'
+             -s --syms            'Display the symbol table'
+                --legend          'Explain the symbol codes used by --syms'
+                --sym-relative    'Display symbol values in --syms as offsets into their sections'
+                --compact-symbols 'Display each symbol name once, with its count and bindings'
+                --imports         'Display the dynamic symbols imported from dependencies'
+                --exports         'Display the dynamic symbols defined for other objects'
+             -V --version-info    'Display the symbol versioning sections'
+             -r --relocs          'Display the relocations (if present)'
+             [only-section] --only-section=[name] 'Only display the relocations in this section with -r'
+             -d --dynamic         'Display the dynamic section (if present)'
+                --plt-stubs       'Display the PLT stubs and the functions they resolve to'
+                --needed          'Display the libraries the file needs (DT_NEEDED); a graph with --format=dot'
+             -I --histogram       'Display histograms of hash table bucket list lengths'
+             -n --notes           'Display the contents of note sections'
+                --raw-notes       'Also dump the descriptors of the notes -n decodes'
+                --arm-exidx       'Display the ARM exception index tables'
+                --relative-offsets 'Display the sections and segments in file order with the gaps between them'
+                --count           'Display how many sections, segments, symbols, relocations, notes and dynamic entries there are'
+                --debuglink       'Display the .gnu_debuglink and .gnu_debugaltlink references'
+             [check-debuglink] --check-debuglink=[debugfile] 'Check the CRC of a debug file against .gnu_debuglink'
+                --debug-info      'Display the DWARF compilation unit headers in .debug_info'
+                --debug-line      'Display the DWARF line number program headers in .debug_line'
+                --entropy         'Display the entropy of each section'
+                --packing         'Report signs of packing or obfuscation (heuristic)'
+                --checksec        'Summarise the hardening of the file: text relocations, RWX segments and _FORTIFY_SOURCE'
+                --fortify         'Report whether _FORTIFY_SOURCE checked functions are used'
+             [hex-dump] -x --hex-dump=[sections] 'Dump the contents of sections, given by names or numbers separated by commas'
+             [dump-symbol] --dump-symbol=[name] 'Dump the bytes of a symbol, given by name'
+                --strings         'Display the printable strings and the sections they are in'
+             [min-len] --min-len=[length] 'Minimum length of a string for --strings (default 4)'
+                --json            'Display the header and sections as JSON (with -l and -S, all header fields)'
+                --stable          'Display the header and sections as one key: value per line, for diff'
+             [format] --format=[format] 'Display the header and sections as readelf tables (the default), text, json, csv, yaml or stable, or --needed as dot'
+             [max-entries] --max-entries=[count] 'Display at most this many rows of each table'
+             [force-endian] --force-endian=[endianness] 'Decode the file as little or big endian regardless of its header'
+             [force-class] --force-class=[bits] 'Decode the file as 32- or 64-bit regardless of its header'
+             [offset] --offset=[offset] 'Parse an ELF image which starts this many bytes into the file'
+                --scan            'Find the ELF headers anywhere in the file, such as in a firmware image'
+             [output] -o --output=[file] 'Write the output to a file instead of stdout'
+                --validate        'Report structural problems and data appended to the file'
+             [extract] --extract=[outfile] 'Write the ELF image, from --offset to the end of the file, to a file unchanged'
+             [extract-overlay] --extract-overlay=[outfile] 'Write the data appended after the ELF image to a file'
+                --no-header       'Print only the rows of tables, without titles, column headings or keys'
+             -q --quiet           'Do not print warnings'
+                --strict          'Treat warnings as errors'
+             [pid] --pid=[pid] 'Also parse the executable of a running process (Linux only)'
+             [FILE]... 'ELF files to parse'")
         .get_matches();
-    work(options);
+    warning::set_quiet(options.is_present("quiet"));
+    heading::set_shown(!options.is_present("no-header"));
+    if let Some(value) = options.value_of("force-endian") {
+        if parse_force_endian(value).is_none() {
+            eprintln!("Error: --force-endian must be 'little' or 'big', not '{}'", value);
+            std::process::exit(1);
+        }
+    }
+    if let Some(value) = options.value_of("force-class") {
+        if parse_force_class(value).is_none() {
+            eprintln!("Error: --force-class must be '32' or '64', not '{}'", value);
+            std::process::exit(1);
+        }
+    }
+    if let Some(value) = options.value_of("max-entries") {
+        if parse_max_entries(value).is_none() {
+            eprintln!("Error: --max-entries must be a number, not '{}'", value);
+            std::process::exit(1);
+        }
+    }
+    if let Some(value) = options.value_of("offset") {
+        if parse_offset(value).is_none() {
+            eprintln!("Error: --offset must be a number, decimal or 0x hex, not '{}'", value);
+            std::process::exit(1);
+        }
+    }
+    if let Some(value) = options.value_of("min-len") {
+        if parse_min_len(value).is_none() {
+            eprintln!("Error: --min-len must be a positive number, not '{}'", value);
+            std::process::exit(1);
+        }
+    }
+    if let Some(value) = options.value_of("pid") {
+        if cfg!(not(target_os = "linux")) {
+            eprintln!("Error: --pid is only supported on Linux");
+            std::process::exit(1);
+        }
+        if parse_pid(value).is_none() {
+            eprintln!("Error: --pid must be a process ID, not '{}'", value);
+            std::process::exit(1);
+        }
+    }
+    if !options.is_present("FILE") && !options.is_present("pid") {
+        eprintln!("Error: no input files");
+        std::process::exit(1);
+    }
+    if let Some(value) = options.value_of("format") {
+        if OutputFormat::parse(value).is_none() {
+            eprintln!(
+                "Error: --format must be 'readelf', 'text', 'json', 'csv', 'yaml', 'stable' or 'dot', not '{}'",
+                value);
+            std::process::exit(1);
+        }
+        if value == "dot" && !options.is_present("needed") {
+            eprintln!("Error: --format=dot is only for --needed");
+            std::process::exit(1);
+        }
+    }
+    if let Some(value) = options.value_of("sort") {
+        if SectionOrder::parse(value).is_none() {
+            eprintln!("Error: --sort must be 'index', 'addr', 'size' or 'name', not '{}'", value);
+            std::process::exit(1);
+        }
+    }
+    let ok = match options.value_of("output") {
+        Some(output) => match File::create(output) {
+            Ok(file) => work_all(&options, &mut BufWriter::new(file)),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                false
+            }
+        },
+        None => {
+            let stdout = io::stdout();
+            let mut out = stdout.lock();
+            work_all(&options, &mut out)
+        }
+    };
+    if !ok {
+        std::process::exit(1);
+    }
 }
 
 fn asserts() {
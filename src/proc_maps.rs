@@ -0,0 +1,104 @@
+use std::fs;
+use std::io::{self, Write};
+
+// Live-process inspection through /proc (--pid). Linux only.
+
+pub fn get_exe_path(pid: u32) -> String {
+    format!("/proc/{}/exe", pid)
+}
+
+// One line of /proc/<pid>/maps.
+#[derive(Debug, Clone)]
+pub struct MapEntry {
+    start: u64,
+    end: u64,
+    perms: String,
+    offset: u64,
+    path: String,
+}
+
+#[allow(dead_code)]
+impl MapEntry {
+    pub fn get_start(&self) -> u64 {
+        self.start
+    }
+    pub fn get_end(&self) -> u64 {
+        self.end
+    }
+    pub fn get_perms(&self) -> &str {
+        &self.perms
+    }
+    pub fn get_offset(&self) -> u64 {
+        self.offset
+    }
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+}
+
+// Lines are `start-end perms offset dev inode [path]`, with numbers in hex.
+// Lines which don't parse are skipped.
+pub fn parse_maps(text: &str) -> Vec<MapEntry> {
+    text.lines().filter_map(|line| {
+        let mut fields = line.splitn(6, ' ');
+        let range = fields.next().unwrap_or("");
+        let perms = fields.next().unwrap_or("");
+        let offset = fields.next().unwrap_or("");
+        let path = fields.nth(2).unwrap_or("").trim_start();
+
+        let mut range = range.splitn(2, '-');
+        let start = range.next().and_then(|s| u64::from_str_radix(s, 16).ok());
+        let end = range.next().and_then(|s| u64::from_str_radix(s, 16).ok());
+        let offset = u64::from_str_radix(offset, 16).ok();
+        match (start, end, offset) {
+            (Some(start), Some(end), Some(offset)) => Some(MapEntry {
+                start: start,
+                end: end,
+                perms: perms.to_string(),
+                offset: offset,
+                path: path.to_string(),
+            }),
+            _ => None,
+        }
+    }).collect()
+}
+
+// The mappings of the process's executable.
+pub fn read_exe_maps(pid: u32) -> io::Result<Vec<MapEntry>> {
+    let exe = try!(fs::read_link(get_exe_path(pid)));
+    let exe = exe.to_string_lossy().into_owned();
+    let text = try!(fs::read_to_string(format!("/proc/{}/maps", pid)));
+    Ok(parse_maps(&text).into_iter().filter(|m| m.path == exe).collect())
+}
+
+// Where each loadable segment (index, file offset, virtual address) is
+// mapped in the process. A segment is found by the file offset of its first
+// page; the load bias is the distance between its run-time and link-time
+// addresses, which is what ASLR shifts position-independent executables by.
+pub fn print_mapped_segments<W: Write>(
+    pid: u32, loads: &[(usize, u64, u64)], maps: &[MapEntry], out: &mut W)
+    -> io::Result<()>
+{
+    const PAGE_SIZE: u64 = 4096;
+
+    try!(writeln!(out, ""));
+    try!(writeln!(out, "Segments mapped in process {}:", pid));
+    try!(writeln!(
+        out,
+        "  [Nr] {:<18} {:<18} {:<18} Perms",
+        "VirtAddr", "Mapped at", "Load bias"));
+    for &(i, offset, vaddr) in loads {
+        let page = offset & !(PAGE_SIZE - 1);
+        match maps.iter().find(|m| m.offset == page) {
+            Some(m) => {
+                let address = m.start + (offset - page);
+                try!(writeln!(
+                    out,
+                    "  [{:2}] {:#018x} {:#018x} {:#018x} {}",
+                    i, vaddr, address, address.wrapping_sub(vaddr), m.perms));
+            }
+            None => try!(writeln!(out, "  [{:2}] {:#018x} not mapped", i, vaddr)),
+        }
+    }
+    Ok(())
+}
@@ -28,6 +28,8 @@ macro_rules! swap_copy {
 swap_copy!(u64, 8);
 swap_copy!(u32, 4);
 swap_copy!(u16, 2);
+swap_copy!(i64, 8);
+swap_copy!(i32, 4);
 
 #[macro_export]
 macro_rules! swap_copy_wrapper {
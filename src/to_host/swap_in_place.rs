@@ -26,6 +26,8 @@ macro_rules! swap_in_place {
 swap_in_place!(u64, 8);
 swap_in_place!(u32, 4);
 swap_in_place!(u16, 2);
+swap_in_place!(i64, 8);
+swap_in_place!(i32, 4);
 
 #[macro_export]
 macro_rules! swap_in_place_wrapper {
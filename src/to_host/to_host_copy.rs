@@ -26,6 +26,10 @@ impl ToHostCopy for u32 { }
 
 impl ToHostCopy for u16 { }
 
+impl ToHostCopy for i64 { }
+
+impl ToHostCopy for i32 { }
+
 #[macro_export]
 macro_rules! to_host_copy_wrapper {
     ( $wrapper:ty, $t:ty ) => {
@@ -22,6 +22,10 @@ impl ToHostInPlace for u32 { }
 
 impl ToHostInPlace for u16 { }
 
+impl ToHostInPlace for i64 { }
+
+impl ToHostInPlace for i32 { }
+
 #[macro_export]
 macro_rules! to_host_in_place_wrapper {
     ( $wrapper:ty, $t:ty ) => {
@@ -0,0 +1,36 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+// Non-fatal problems with a file are reported through `warning!`, so that
+// --quiet can silence all of them in one place and --strict can tell whether
+// there were any.
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+// Counts silenced warnings too.
+pub fn add_warning() {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn get_count() -> usize {
+    COUNT.load(Ordering::Relaxed)
+}
+
+// Prints `Warning: <message>` to stderr unless --quiet was given.
+#[macro_export]
+macro_rules! warning {
+    ($($arg:tt)*) => {{
+        $crate::warning::add_warning();
+        if !$crate::warning::is_quiet() {
+            eprintln!("Warning: {}", format!($($arg)*));
+        }
+    }};
+}
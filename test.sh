@@ -5,3 +5,718 @@ if diff -q test_results/header.pass.diff <(diff <(cargo run -- -h test_programs/
 else
     echo "Tests fail";
 fi;
+
+# Fixtures generated by test_programs/gen_fixtures.py
+cargo build
+for fixture in le32 le64 be64; do
+    if diff -q test_results/$fixture.header.pass.diff <(diff <(target/debug/writeork -h test_programs/$fixture/min.o) <(readelf -h test_programs/$fixture/min.o)) 2>&1 > /dev/null; then
+        echo "Tests pass: $fixture";
+    else
+        echo "Tests fail: $fixture";
+    fi;
+done;
+
+# Malformed files must be rejected with an error rather than a panic
+for test in "classnone -h" "datanone -h" "shoff4g --validate -s" "symentsize0 --validate -s" "symentsize7 --validate -s" "align3 --validate -S" "shstrndx9 --validate -S"; do
+    fixture=${test%% *};
+    option=${test#* };
+    if diff -q test_results/$fixture.err <(target/debug/writeork $option test_programs/invalid/$fixture.o 2>&1 > /dev/null) > /dev/null; then
+        echo "Tests pass: $fixture";
+    else
+        echo "Tests fail: $fixture";
+    fi;
+done;
+
+# -h reads only the file header, so a section table that is cut off or out
+# of the file goes unremarked
+head -c 64 test_programs/le64/min.o > /tmp/writeork-header64.o;
+if diff -q <(target/debug/writeork -h /tmp/writeork-header64.o 2>&1) <(target/debug/writeork -h test_programs/le64/min.o 2>&1) > /dev/null \
+&& [ -z "$(target/debug/writeork -h test_programs/invalid/shoff4g.o 2>&1 > /dev/null)" ]; then
+    echo "Tests pass: header only";
+else
+    echo "Tests fail: header only";
+fi;
+
+# A path that can't be read, such as a directory, is reported and the files
+# after it are still processed
+if target/debug/writeork -h test_programs test_programs/le64/min.o 2> /dev/null | grep -q "^  Class: *ELF64" \
+&& target/debug/writeork -h test_programs test_programs/le64/min.o 2>&1 > /dev/null | grep -q "^Error: test_programs: The file can't be read: is a directory$" \
+&& ! target/debug/writeork -h test_programs test_programs/le64/min.o > /dev/null 2>&1; then
+    echo "Tests pass: unreadable path";
+else
+    echo "Tests fail: unreadable path";
+fi;
+
+# A header cut short still shows its identification
+head -c 30 test_programs/le64/min.o > /tmp/writeork-header30.o;
+if target/debug/writeork -h /tmp/writeork-header30.o 2> /dev/null | grep -q "Class: *ELF64" \
+&& target/debug/writeork -h /tmp/writeork-header30.o 2>&1 > /dev/null | grep -q "ELF header ends after 30 bytes" \
+&& : > /tmp/writeork-empty.o \
+&& target/debug/writeork -h /tmp/writeork-empty.o 2>&1 > /dev/null | grep -q "wrong magic bytes"; then
+    echo "Tests pass: truncated header";
+else
+    echo "Tests fail: truncated header";
+fi;
+
+# A compiler-generated relocatable object: no program headers, many sections
+# (test_programs/src/obj.c says how to rebuild it)
+obj=test_programs/obj/obj.o;
+if diff -q <(target/debug/writeork -l $obj) <(readelf -lW $obj) > /dev/null \
+&& diff -q <(target/debug/writeork -S $obj | sed '/^Key to Flags/,$d') <(readelf -SW $obj | sed '/^Key to Flags/,$d') > /dev/null \
+&& diff -q <(target/debug/writeork -s $obj) <(readelf -sW $obj) > /dev/null \
+&& diff -q <(target/debug/writeork -r $obj) <(readelf -rW $obj) > /dev/null; then
+    echo "Tests pass: obj";
+else
+    echo "Tests fail: obj";
+fi;
+if target/debug/writeork -S $obj | grep -q "^  \[[ 0-9]*\] .tbss.per_thread_buffer .*zero-filled, takes no space in the file$" \
+&& target/debug/writeork -S $obj | grep -q "^  \[[ 0-9]*\] .tdata.per_thread .*copied from the file$"; then
+    echo "Tests pass: obj tls";
+else
+    echo "Tests fail: obj tls";
+fi;
+# .bss.zeroed is NOBITS, at the same offset as the real bytes of .data.counter
+if [ "$(target/debug/writeork -x .bss.zeroed $obj)" = "Section '.bss.zeroed' has no data in the file." ] \
+&& diff -q <(target/debug/writeork -x .rodata.greeting,.text.print_greeting $obj) <(readelf -x .rodata.greeting -x .text.print_greeting $obj) > /dev/null; then
+    echo "Tests pass: obj hex dump";
+else
+    echo "Tests fail: obj hex dump";
+fi;
+# --dump-symbol shows the same bytes as the section dump, from the symbol's
+# value; symbols without bytes in the file are errors
+if target/debug/writeork --dump-symbol=greeting $obj | grep -q "^  0x00000010 6f636174 61626c65 206f626a 65637400 ocatable object.$" \
+&& [ "$(target/debug/writeork --dump-symbol=old_api test_programs/so/versioned.so | sed -n 3p)" = "  0x00001119 4883ec08 e80effff ff4883c4 08c3     H........H...." ] \
+&& [ "$(target/debug/writeork --dump-symbol=zeroed $obj 2>&1)" = "Error: can't dump symbol 'zeroed': it has no contents in the file" ] \
+&& [ "$(target/debug/writeork --dump-symbol=.LC0 $obj 2>&1)" = "Error: can't dump symbol '.LC0': it has no size" ] \
+&& [ "$(target/debug/writeork --dump-symbol=missing $obj 2>&1)" = "Error: there is no symbol named 'missing'" ]; then
+    echo "Tests pass: dump symbol";
+else
+    echo "Tests fail: dump symbol";
+fi;
+panicked=;
+for option in -h --raw-header --offsets-table -l -S -t --long-sections -s --compact-symbols -r -d --plt-stubs -I -n \
+              --debuglink --debug-info --debug-line --entropy --packing --fortify --checksec --imports --exports \
+              --strings --json --hex-dump=.bss --dump-symbol=greeting; do
+    target/debug/writeork $option $obj > /dev/null 2>&1;
+    if [ $? -eq 101 ]; then
+        panicked="$panicked $option";
+    fi;
+done;
+if [ -z "$panicked" ]; then
+    echo "Tests pass: obj all modes";
+else
+    echo "Tests fail: obj all modes:$panicked";
+fi;
+
+# Symbol versioning matches readelf -V, with and without version sections
+for file in test_programs/so/versioned.so test_programs/le64/min.o; do
+    if diff -q <(target/debug/writeork -V $file) <(readelf -V $file) > /dev/null; then
+        echo "Tests pass: $file versions";
+    else
+        echo "Tests fail: $file versions";
+    fi;
+done;
+
+# --validate counts the bytes appended after everything the headers describe
+cat test_programs/le64/min.o <(printf 'appended') > /tmp/writeork-appended.o;
+if target/debug/writeork --validate test_programs/le64/min.o | grep -q "trailing bytes after ELF: 0$" \
+&& target/debug/writeork --validate /tmp/writeork-appended.o | grep -q "trailing bytes after ELF: 8$"; then
+    echo "Tests pass: trailing bytes";
+else
+    echo "Tests fail: trailing bytes";
+fi;
+rm -f /tmp/writeork-overlay;
+target/debug/writeork -q --extract-overlay=/tmp/writeork-overlay /tmp/writeork-appended.o > /dev/null;
+if [ "$(cat /tmp/writeork-overlay)" = "appended" ]; then
+    echo "Tests pass: extract overlay";
+else
+    echo "Tests fail: extract overlay";
+fi;
+
+# PT_GNU_EH_FRAME must cover exactly .eh_frame_hdr; its p_filesz, 32 bytes
+# into the program header, is patched here
+cp test_programs/so/versioned.so /tmp/writeork-ehframe.so;
+phoff=$(readelf -hW test_programs/so/versioned.so | awk '/Start of program headers/ {print $5}');
+phndx=$(readelf -lW test_programs/so/versioned.so | awk '/^Program Headers/ {p = 1; next} p && $1 == "GNU_EH_FRAME" {print n; exit} p && $1 ~ /^[A-Z_]+$/ && $1 != "Type" {n++}');
+printf '\x30' | dd of=/tmp/writeork-ehframe.so bs=1 seek=$((phoff + phndx * 56 + 32)) conv=notrunc 2> /dev/null;
+if target/debug/writeork --strict -h test_programs/so/versioned.so > /dev/null 2>&1 \
+&& target/debug/writeork --validate -h /tmp/writeork-ehframe.so 2>&1 > /dev/null | grep -q "PT_GNU_EH_FRAME covers 0x30 bytes"; then
+    echo "Tests pass: eh_frame_hdr";
+else
+    echo "Tests fail: eh_frame_hdr";
+fi;
+
+# --strict fails on warnings, and only on warnings
+if target/debug/writeork --strict -s test_programs/invalid/symentsize7.o > /dev/null 2>&1; then
+    echo "Tests fail: strict";
+elif ! target/debug/writeork --strict -s test_programs/le64/min.o > /dev/null 2>&1; then
+    echo "Tests fail: strict";
+else
+    echo "Tests pass: strict";
+fi;
+
+# Full reports are compared against golden outputs captured through --output
+for fixture in le32 le64 be64; do
+    target/debug/writeork -S -s -o /tmp/writeork-$fixture.out test_programs/$fixture/min.o;
+    if diff -q test_results/$fixture.sections-syms.golden /tmp/writeork-$fixture.out > /dev/null; then
+        echo "Tests pass: $fixture golden";
+    else
+        echo "Tests fail: $fixture golden";
+    fi;
+done;
+
+# Relocation listings match readelf -rW
+for fixture in le32 le64 be64; do
+    if diff -q <(target/debug/writeork -r test_programs/$fixture/min.o) <(readelf -rW test_programs/$fixture/min.o) > /dev/null; then
+        echo "Tests pass: $fixture relocs";
+    else
+        echo "Tests fail: $fixture relocs";
+    fi;
+done;
+
+# --strings finds what strings(1) finds
+for fixture in le32 le64 be64; do
+    if diff -q <(target/debug/writeork --strings test_programs/$fixture/min.o | tail -n +4 | awk '{print $1}') <(strings -a -t x test_programs/$fixture/min.o | awk '{print $1}') > /dev/null; then
+        echo "Tests pass: $fixture strings";
+    else
+        echo "Tests fail: $fixture strings";
+    fi;
+done;
+
+# The JSON report is an interface: its keys must not change unnoticed
+for fixture in le32 le64 be64; do
+    if diff -q test_results/$fixture.json.golden <(target/debug/writeork --json test_programs/$fixture/min.o) > /dev/null; then
+        echo "Tests pass: $fixture json";
+    else
+        echo "Tests fail: $fixture json";
+    fi;
+done;
+
+# --no-header leaves only the rows of the table
+if diff -q <(target/debug/writeork --no-header -S test_programs/le64/min.o) <(target/debug/writeork -S test_programs/le64/min.o | grep '^  \[ *[0-9]') > /dev/null; then
+    echo "Tests pass: no header";
+else
+    echo "Tests fail: no header";
+fi;
+
+# -V, -I, -n and --entropy drop their titles too, -s keeps an empty line
+# between the symbol tables
+so=test_programs/so/versioned.so
+if ! target/debug/writeork --no-header -V -I -n --entropy $so | grep -qE '^$|section|Histogram|Displaying|entropy|Owner|Length|Nr' \
+&& [ "$(target/debug/writeork --no-header -s $so | grep -c '^$')" = "1" ] \
+&& [ "$(target/debug/writeork --no-header -s $so | head -1)" != "" ]; then
+    echo "Tests pass: no header titles";
+else
+    echo "Tests fail: no header titles";
+fi;
+
+# --json -l -S adds every header field
+if diff -q test_results/versioned.headers.json.golden <(target/debug/writeork --json -l -S test_programs/so/versioned.so) > /dev/null; then
+    echo "Tests pass: json headers";
+else
+    echo "Tests fail: json headers";
+fi;
+
+# Notes of unknown types are dumped in hex rather than lost
+printf '\x05\x00\x00\x00\x04\x00\x00\x00\x2a\x00\x00\x00ACME\0\0\0\0\x0b\xad\xf0\x0d' > /tmp/writeork-note.bin;
+objcopy --add-section .note.acme=/tmp/writeork-note.bin test_programs/le64/min.o /tmp/writeork-note.o;
+if target/debug/writeork -n /tmp/writeork-note.o | grep -q "^  0x00000000 0badf00d " \
+&& target/debug/writeork -n --raw-notes test_programs/so/versioned.so | grep -q "Descriptor:"; then
+    echo "Tests pass: raw notes";
+else
+    echo "Tests fail: raw notes";
+fi;
+
+# The gold version note is a padded string, shown up to its NUL
+printf '\x04\x00\x00\x00\x0c\x00\x00\x00\x04\x00\x00\x00GNU\0gold 1.16\0\0\0' > /tmp/writeork-gold.bin;
+objcopy --add-section .note.gnu.gold-version=/tmp/writeork-gold.bin test_programs/le64/min.o /tmp/writeork-gold.o;
+if diff -q <(target/debug/writeork -n /tmp/writeork-gold.o) <(readelf -n /tmp/writeork-gold.o) > /dev/null; then
+    echo "Tests pass: gold version";
+else
+    echo "Tests fail: gold version";
+fi;
+
+# BSD identification notes carry the OS version, each packed its own way
+{ printf '\x08\x00\x00\x00\x04\x00\x00\x00\x01\x00\x00\x00FreeBSD\0\x21\x5d\x15\x00';
+  printf '\x07\x00\x00\x00\x04\x00\x00\x00\x01\x00\x00\x00NetBSD\0\0\xa8\x8b\x8b\x3b';
+  printf '\x08\x00\x00\x00\x04\x00\x00\x00\x01\x00\x00\x00OpenBSD\0\0\0\0\0'; } > /tmp/writeork-bsd.bin;
+objcopy --add-section .note.tag=/tmp/writeork-bsd.bin test_programs/le64/min.o /tmp/writeork-bsd.o;
+if [ "$(target/debug/writeork -n /tmp/writeork-bsd.o | grep "^    OS: ")" = "    OS: FreeBSD, version: 14.0 (1400097)
+    OS: NetBSD, version: 9.99.10 (999001000)
+    OS: OpenBSD, version: unspecified" ]; then
+    echo "Tests pass: bsd notes";
+else
+    echo "Tests fail: bsd notes";
+fi;
+
+# On PPC64 the local entry point offset in st_other is shown with the
+# visibility (e_machine and st_other of foo are patched here)
+cp test_programs/le64/min.o /tmp/writeork-ppc64.o;
+printf '\x15\x00' | dd of=/tmp/writeork-ppc64.o bs=1 seek=18 conv=notrunc 2> /dev/null;
+printf '\x60' | dd of=/tmp/writeork-ppc64.o bs=1 seek=101 conv=notrunc 2> /dev/null;
+if diff -q <(target/debug/writeork -s /tmp/writeork-ppc64.o) <(readelf -sW /tmp/writeork-ppc64.o) > /dev/null \
+&& target/debug/writeork -s /tmp/writeork-ppc64.o | grep -q "DEFAULT \[<localentry>: 8\]     1 foo$"; then
+    echo "Tests pass: ppc64 local entry";
+else
+    echo "Tests fail: ppc64 local entry";
+fi;
+
+# --sym-relative shows symbol values as offsets into their sections
+if diff -q test_results/obj.sym-relative.golden <(target/debug/writeork -s --sym-relative test_programs/obj/obj.o) > /dev/null \
+&& target/debug/writeork -s --sym-relative test_programs/so/versioned.so | grep -q "^    27: .text+0xb9  *14 FUNC    GLOBAL DEFAULT   13 old_api$"; then
+    echo "Tests pass: sym relative";
+else
+    echo "Tests fail: sym relative";
+fi;
+
+# Machines are described from the table machine_name looks up, with
+# EM_AARCH64 at 183 (e_machine is patched here)
+cp test_programs/le64/min.o /tmp/writeork-aarch64.o;
+printf '\xb7\x00' | dd of=/tmp/writeork-aarch64.o bs=1 seek=18 conv=notrunc 2> /dev/null;
+if target/debug/writeork -h /tmp/writeork-aarch64.o | grep -q "^  Machine: *ARM AARCH64$"; then
+    echo "Tests pass: machine name";
+else
+    echo "Tests fail: machine name";
+fi;
+
+# e_machine is kept raw: machines added since the table was written, such as
+# EM_RISCV at 243, and values no table has are shown rather than aborting
+cp test_programs/le64/min.o /tmp/writeork-riscv.o;
+printf '\xf3\x00' | dd of=/tmp/writeork-riscv.o bs=1 seek=18 conv=notrunc 2> /dev/null;
+cp test_programs/le64/min.o /tmp/writeork-em1234.o;
+printf '\x34\x12' | dd of=/tmp/writeork-em1234.o bs=1 seek=18 conv=notrunc 2> /dev/null;
+if target/debug/writeork -h /tmp/writeork-riscv.o | grep -q "^  Machine: *RISC-V$" \
+&& target/debug/writeork --json /tmp/writeork-riscv.o | grep -q '^  "machine": "riscv",$' \
+&& target/debug/writeork -h /tmp/writeork-em1234.o | grep -q "^  Machine: *<unknown>: 0x1234$" \
+&& target/debug/writeork --json /tmp/writeork-em1234.o | grep -q '^  "machine": "<unknown>: 0x1234",$' \
+&& target/debug/writeork --validate -S -s -r -x .text --relative-offsets /tmp/writeork-em1234.o > /dev/null 2>&1; then
+    echo "Tests pass: unknown machine";
+else
+    echo "Tests fail: unknown machine";
+fi;
+
+# OS/ABI values from 64 on are named for the machine: 64 is AMD HSA in an
+# AMDGPU object and ARM EABI in an ARM one (EI_OSABI and e_machine are patched
+# here), and GPU objects display without aborting
+ok=true;
+for t in '\xe0\x00 AMD HSA' '\x28\x00 ARM EABI'; do
+    cp test_programs/le64/min.o /tmp/writeork-osabi.o;
+    printf '\x40' | dd of=/tmp/writeork-osabi.o bs=1 seek=7 conv=notrunc 2> /dev/null;
+    printf "${t%% *}" | dd of=/tmp/writeork-osabi.o bs=1 seek=18 conv=notrunc 2> /dev/null;
+    target/debug/writeork -h /tmp/writeork-osabi.o | grep -q "^  OS/ABI: *${t#* }$" || ok=false;
+    target/debug/writeork --json /tmp/writeork-osabi.o | grep -q "^  \"os_abi\": \"${t#* }\",$" || ok=false;
+done;
+cp test_programs/le64/min.o /tmp/writeork-cuda.o;
+printf '\x33' | dd of=/tmp/writeork-cuda.o bs=1 seek=7 conv=notrunc 2> /dev/null;
+printf '\xbe\x00' | dd of=/tmp/writeork-cuda.o bs=1 seek=18 conv=notrunc 2> /dev/null;
+if $ok && [ "$(target/debug/writeork -h /tmp/writeork-cuda.o | grep '^  OS/ABI:\|^  Machine:')" = "  OS/ABI:                            NVIDIA CUDA
+  Machine:                           NVIDIA CUDA architecture" ]; then
+    echo "Tests pass: gpu os abi";
+else
+    echo "Tests fail: gpu os abi";
+fi;
+
+# e_type is kept raw too: OS-specific, processor-specific and unassigned
+# types are named the way readelf names them (e_type is patched here)
+ok=true;
+for t in '\x01\xfe OS' '\x05\xff PROC' '\x42\x00 UNKNOWN'; do
+    cp test_programs/le64/min.o /tmp/writeork-etype.o;
+    printf "${t%% *}" | dd of=/tmp/writeork-etype.o bs=1 seek=16 conv=notrunc 2> /dev/null;
+    [ "$(target/debug/writeork -h /tmp/writeork-etype.o | grep '^  Type:')" = "$(readelf -h /tmp/writeork-etype.o | grep '^  Type:')" ] || ok=false;
+    target/debug/writeork --json /tmp/writeork-etype.o | grep -q "^  \"type\": \"${t#* }\",$" || ok=false;
+done;
+if $ok; then
+    echo "Tests pass: file type";
+else
+    echo "Tests fail: file type";
+fi;
+
+# With extended numbering e_shnum is 0 and the number of sections is in
+# sh_size of section 0 (both are patched here)
+cp test_programs/le64/min.o /tmp/writeork-shnum0.o;
+printf '\x00\x00' | dd of=/tmp/writeork-shnum0.o bs=1 seek=60 conv=notrunc 2> /dev/null;
+printf '\x05' | dd of=/tmp/writeork-shnum0.o bs=1 seek=192 conv=notrunc 2> /dev/null;
+if diff -q <(target/debug/writeork -S /tmp/writeork-shnum0.o | sed '/^Key to Flags/,$d') <(readelf -SW /tmp/writeork-shnum0.o | sed '/^Key to Flags/,$d') > /dev/null \
+&& target/debug/writeork -s /tmp/writeork-shnum0.o | grep -q " FUNC    GLOBAL DEFAULT    1 foo$" \
+&& target/debug/writeork --strict --validate /tmp/writeork-shnum0.o > /dev/null 2>&1; then
+    echo "Tests pass: extended section count";
+else
+    echo "Tests fail: extended section count";
+fi;
+
+# Processor-specific section types are named for the machine (.text is
+# retyped to SHT_X86_64_UNWIND here)
+cp test_programs/le64/min.o /tmp/writeork-unwind.o;
+printf '\x01\x00\x00\x70' | dd of=/tmp/writeork-unwind.o bs=1 seek=228 conv=notrunc 2> /dev/null;
+if diff -q <(target/debug/writeork -S /tmp/writeork-unwind.o | sed '/^Key to Flags/,$d') <(readelf -SW /tmp/writeork-unwind.o | sed '/^Key to Flags/,$d') > /dev/null; then
+    echo "Tests pass: machine section types";
+else
+    echo "Tests fail: machine section types";
+fi;
+
+# ARM exception index entries: test_programs/arm/exidx.o is an EM_ARM build
+# of le32/min.o with a hand-made .ARM.exidx
+if diff -q test_results/exidx.golden <(target/debug/writeork --arm-exidx test_programs/arm/exidx.o) > /dev/null; then
+    echo "Tests pass: arm exidx";
+else
+    echo "Tests fail: arm exidx";
+fi;
+
+# --count agrees with the tables readelf prints
+so=test_programs/so/versioned.so;
+if [ "$(target/debug/writeork --count $so)" = "sections: 29, segments: 9, symbols: 30 (.symtab) 11 (.dynsym), relocations: $(readelf -rW $so | grep -cE '^[0-9a-f]{16}'), notes: 1, dynamic entries: $(readelf -dW $so | grep -c '^ 0x')" ]; then
+    echo "Tests pass: count";
+else
+    echo "Tests fail: count";
+fi;
+
+# SHT_SYMTAB_SHNDX is listed by name (.text is retyped to it and linked to
+# .symtab here)
+cp test_programs/le64/min.o /tmp/writeork-shndx.o;
+printf '\x12' | dd of=/tmp/writeork-shndx.o bs=1 seek=228 conv=notrunc 2> /dev/null;
+printf '\x02' | dd of=/tmp/writeork-shndx.o bs=1 seek=264 conv=notrunc 2> /dev/null;
+if diff -q <(target/debug/writeork --strict -S /tmp/writeork-shndx.o | sed '/^Key to Flags/,$d') <(readelf -SW /tmp/writeork-shndx.o | sed '/^Key to Flags/,$d') > /dev/null; then
+    echo "Tests pass: symtab shndx";
+else
+    echo "Tests fail: symtab shndx";
+fi;
+
+# A symbol whose st_shndx is SHN_XINDEX takes its section from .symtab_shndx
+if diff -q <(target/debug/writeork --strict -s test_programs/le64/xindex.o) <(readelf -sW test_programs/le64/xindex.o) > /dev/null \
+&& target/debug/writeork -s --sym-relative test_programs/le64/xindex.o | grep -q "^     1: .text+0x0 " \
+&& target/debug/writeork --dump-symbol foo test_programs/le64/xindex.o | grep -q "3 bytes in .text"; then
+    echo "Tests pass: symbol xindex";
+else
+    echo "Tests fail: symbol xindex";
+fi;
+
+# A non-zero ABI version is noted for GNU, and the number is kept first
+cp test_programs/le64/min.o /tmp/writeork-abiversion.o;
+printf '\x03\x01' | dd of=/tmp/writeork-abiversion.o bs=1 seek=7 conv=notrunc 2> /dev/null;
+if target/debug/writeork -h /tmp/writeork-abiversion.o | grep -q "^  ABI Version: *1 (unusual for GNU" \
+&& target/debug/writeork -h test_programs/le64/min.o | grep -q "^  ABI Version: *0$"; then
+    echo "Tests pass: abi version";
+else
+    echo "Tests fail: abi version";
+fi;
+
+# --only-section keeps -r to one relocation section, and refuses sections
+# which aren't there or hold no relocations
+so=test_programs/so/versioned.so;
+if diff -q <(target/debug/writeork -r --only-section=.rela.plt $so) <(target/debug/writeork -r $so | sed -n "/'.rela.plt'/,\$p" | sed '1i\\') > /dev/null \
+&& ! target/debug/writeork -r --only-section=.text $so > /dev/null 2>&1 \
+&& target/debug/writeork -r --only-section=.nope $so 2>&1 > /dev/null | grep -q "no section named '.nope'"; then
+    echo "Tests pass: only section";
+else
+    echo "Tests fail: only section";
+fi;
+
+# SystemTap probes, one of them with a semaphore
+# (test_programs/src/stapsdt.c says how to rebuild the library)
+so=test_programs/so/stapsdt.so;
+if diff -q <(target/debug/writeork -n $so) <(readelf -n $so) > /dev/null \
+&& target/debug/writeork -n $so | grep -q "Semaphore: 0x0000000000002000"; then
+    echo "Tests pass: stapsdt";
+else
+    echo "Tests fail: stapsdt";
+fi;
+
+# --relative-offsets lists sections and segments in file order with the
+# padding between them
+if diff -q test_results/versioned.layout.golden <(target/debug/writeork --relative-offsets test_programs/so/versioned.so) > /dev/null; then
+    echo "Tests pass: relative offsets";
+else
+    echo "Tests fail: relative offsets";
+fi;
+
+# With e_phnum at PN_XNUM the count of program headers is taken from
+# sh_info of section 0, which is 44 bytes into the section header
+cp test_programs/so/versioned.so /tmp/writeork-xnum.so;
+shoff=$(readelf -hW test_programs/so/versioned.so | awk '/Start of section headers/ {print $5}');
+printf '\xff\xff' | dd of=/tmp/writeork-xnum.so bs=1 seek=56 conv=notrunc 2> /dev/null;
+printf '\x09' | dd of=/tmp/writeork-xnum.so bs=1 seek=$((shoff + 44)) conv=notrunc 2> /dev/null;
+if diff -q <(target/debug/writeork --strict -l /tmp/writeork-xnum.so) <(target/debug/writeork -l test_programs/so/versioned.so) > /dev/null; then
+    echo "Tests pass: pn_xnum";
+else
+    echo "Tests fail: pn_xnum";
+fi;
+
+# --format=csv prints the records of the JSON report as tables
+if diff -q test_results/versioned.csv.golden <(target/debug/writeork --format=csv -l -S -s test_programs/so/versioned.so) > /dev/null \
+&& target/debug/writeork --format=json -s test_programs/so/versioned.so | grep -q "^  \"symbols\": \[$"; then
+    echo "Tests pass: csv";
+else
+    echo "Tests fail: csv";
+fi;
+
+# --max-entries prints the first rows of each table and counts the rest
+so=test_programs/so/versioned.so;
+if [ "$(target/debug/writeork --max-entries=2 --entropy $so | tail -3)" = "  [ 1]         36   4.002 .note.gnu.build-id
+  [ 2]         52   3.196 .gnu.hash
+  ... (26 more)" ] \
+&& [ "$(target/debug/writeork --max-entries=1 -s $so | grep -c '^  \.\.\. (')" = "2" ] \
+&& [ "$(target/debug/writeork --max-entries=1 -V $so | grep '^  \.\.\. (')" = "  ... (2 more)
+  ... (2 more)" ]; then
+    echo "Tests pass: max entries";
+else
+    echo "Tests fail: max entries";
+fi;
+
+# --format=yaml prints the same records as one YAML document per file
+if diff -q test_results/versioned.yaml.golden <(target/debug/writeork --format=yaml -l -S -s test_programs/so/versioned.so) > /dev/null; then
+    echo "Tests pass: yaml";
+else
+    echo "Tests fail: yaml";
+fi;
+
+# --format=text prints the same records as readelf-style tables; the section
+# table is the one readelf -SW prints
+if diff -q test_results/versioned.text.golden <(target/debug/writeork --format=text -l -S -s test_programs/so/versioned.so) > /dev/null \
+&& diff -q <(target/debug/writeork --format=text -S test_programs/le32/min.o | sed -n '/^Section Headers/,$p') <(readelf -SW test_programs/le32/min.o | sed -n '/^Section Headers/,/^Key/p' | grep -v "^Key") > /dev/null \
+&& [ "$(target/debug/writeork --format=text --no-header -s test_programs/le32/min.o | sed -n 7,8p)" = "     0: 00000000     0 NOTYPE  LOCAL  DEFAULT  UND 
+     1: 00000000     5 FUNC    GLOBAL DEFAULT    1 foo" ]; then
+    echo "Tests pass: text reporter";
+else
+    echo "Tests fail: text reporter";
+fi;
+
+# --stable prints one key: value per line, the same as --format=stable
+if diff -q test_results/versioned.stable.golden <(target/debug/writeork --stable -l -S -s test_programs/so/versioned.so) > /dev/null \
+&& diff -q test_results/versioned.stable.golden <(target/debug/writeork --format=stable -l -S -s test_programs/so/versioned.so) > /dev/null; then
+    echo "Tests pass: stable";
+else
+    echo "Tests fail: stable";
+fi;
+
+# --offset parses an ELF image embedded in a larger file, here after a page
+# of zeros and followed by a trailer
+{ head -c 4096 /dev/zero; cat test_programs/so/versioned.so; printf 'TRAILER'; } > /tmp/writeork-embedded.bin;
+if diff -q <(target/debug/writeork --offset=0x1000 -h -l -S -s -d -n -V /tmp/writeork-embedded.bin 2> /dev/null) <(target/debug/writeork -h -l -S -s -d -n -V test_programs/so/versioned.so) > /dev/null \
+&& target/debug/writeork --validate --offset=4096 -h /tmp/writeork-embedded.bin 2>&1 > /dev/null | grep -q "7 bytes of data are appended" \
+&& ! target/debug/writeork --offset=100000 -h /tmp/writeork-embedded.bin 2> /dev/null; then
+    echo "Tests pass: offset";
+else
+    echo "Tests fail: offset";
+fi;
+
+# --extract writes the image back byte for byte, without what precedes it
+# with --offset
+rm -f /tmp/writeork-extract.so /tmp/writeork-extract.bin;
+target/debug/writeork --extract=/tmp/writeork-extract.so test_programs/so/versioned.so > /dev/null;
+target/debug/writeork --offset=0x1000 --extract=/tmp/writeork-extract.bin /tmp/writeork-embedded.bin > /dev/null;
+if cmp -s /tmp/writeork-extract.so test_programs/so/versioned.so \
+&& cmp -s /tmp/writeork-extract.bin <(tail -c +4097 /tmp/writeork-embedded.bin); then
+    echo "Tests pass: extract";
+else
+    echo "Tests fail: extract";
+fi;
+
+# --scan finds the headers embedded in a blob, but not a bare magic number
+{ head -c 4096 /dev/zero; cat test_programs/so/versioned.so; printf '\x7fELFjunk'; cat test_programs/le32/min.o; } > /tmp/writeork-scan.bin;
+if [ "$(target/debug/writeork --scan /tmp/writeork-scan.bin | tail -n +3)" = "  0x0000000000001000 DYN   x86-64 / ELF64 / little-endian / SYSV
+  0x0000000000004d08 REL   i386 / ELF32 / little-endian / SYSV" ]; then
+    echo "Tests pass: scan";
+else
+    echo "Tests fail: scan";
+fi;
+
+# --scan goes on past images for machines and file types it has no name for
+# (a RISC-V object, then one with e_type 0xfe01)
+cp test_programs/le64/min.o /tmp/writeork-scan-riscv.o;
+printf '\xf3\x00' | dd of=/tmp/writeork-scan-riscv.o bs=1 seek=18 conv=notrunc 2> /dev/null;
+cp test_programs/le64/min.o /tmp/writeork-scan-os.o;
+printf '\x01\xfe' | dd of=/tmp/writeork-scan-os.o bs=1 seek=16 conv=notrunc 2> /dev/null;
+{ head -c 256 /dev/zero; cat /tmp/writeork-scan-riscv.o; head -c 256 /dev/zero; cat /tmp/writeork-scan-os.o; } > /tmp/writeork-scan-odd.bin;
+if [ "$(target/debug/writeork --scan /tmp/writeork-scan-odd.bin | tail -n +3)" = "  0x0000000000000100 REL   riscv / ELF64 / little-endian / SYSV
+  0x00000000000003e0 OS    x86-64 / ELF64 / little-endian / SYSV" ]; then
+    echo "Tests pass: scan odd headers";
+else
+    echo "Tests fail: scan odd headers";
+fi;
+
+# --validate reports a shared library renamed away from its soname, but
+# not one named for its soname, its full version or its link-time name
+# (test_programs/src/soname.c says how to rebuild the library)
+ok=true;
+for name in libsoname.so.1.2 libsoname.so.1 libsoname.so; do
+    cp test_programs/so/libsoname.so.1.2 /tmp/$name;
+    target/debug/writeork --strict --validate /tmp/$name > /dev/null 2>&1 || ok=false;
+done;
+cp test_programs/so/libsoname.so.1.2 /tmp/librenamed.so.1;
+# a name that only shares a prefix with the soname is not a version of it
+for name in libsoname libsoname.so.1.bak; do
+    cp test_programs/so/libsoname.so.1.2 /tmp/$name;
+    target/debug/writeork --validate /tmp/$name 2> /dev/null | grep -q "^  the soname is 'libsoname.so.1', but the file is named '$name'" || ok=false;
+done;
+if $ok && target/debug/writeork --validate /tmp/librenamed.so.1 2> /dev/null | grep -q "^  the soname is 'libsoname.so.1', but the file is named 'librenamed.so.1'"; then
+    echo "Tests pass: soname";
+else
+    echo "Tests fail: soname";
+fi;
+
+# .dynamic is only read for the soname under --validate, and a PT_DYNAMIC
+# offset that can't be read from is a problem rather than a panic
+cp test_programs/so/versioned.so /tmp/writeork-bigdyn.so;
+printf '\x00\x00\x00\x00\x00\x00\x00\x80' | dd of=/tmp/writeork-bigdyn.so bs=1 seek=296 conv=notrunc 2> /dev/null;
+if target/debug/writeork -h /tmp/writeork-bigdyn.so > /dev/null 2>&1 \
+&& ! target/debug/writeork -h /tmp/writeork-bigdyn.so 2>&1 | grep -q "dynamic section" \
+&& target/debug/writeork --validate /tmp/writeork-bigdyn.so 2> /dev/null | grep -q "^  the dynamic section can't be read: Offset 0x8000000000000000 can't be read from$"; then
+    echo "Tests pass: unreadable dynamic";
+else
+    echo "Tests fail: unreadable dynamic";
+fi;
+
+# --needed with --format=dot is a graph of the direct dependencies
+if [ "$(target/debug/writeork --needed --format=dot test_programs/so/versioned.so)" = 'digraph dependencies {
+    "versioned.so" [shape=box];
+    "versioned.so" -> "libc.so.6";
+}' ] \
+&& [ "$(target/debug/writeork --needed test_programs/so/versioned.so)" = "libc.so.6" ]; then
+    echo "Tests pass: needed";
+else
+    echo "Tests fail: needed";
+fi;
+
+# --validate reports .text in a writable segment; here the flags of the
+# second program header are patched to RWX
+cp test_programs/so/versioned.so /tmp/writeork-wtext.so;
+printf '\x07' | dd of=/tmp/writeork-wtext.so bs=1 seek=124 conv=notrunc 2> /dev/null;
+if target/debug/writeork --validate /tmp/writeork-wtext.so 2> /dev/null | grep -q "^  .text is in LOAD segment \[01\], which is writable$" \
+&& ! target/debug/writeork --validate test_programs/so/versioned.so 2>&1 | grep -q "writable"; then
+    echo "Tests pass: writable text";
+else
+    echo "Tests fail: writable text";
+fi;
+
+# A segment that is readable, writable and executable is noted by -l rather
+# than warned about, as JIT-heavy programs may want one; here the flags of
+# PT_GNU_STACK are patched to RWX
+cp test_programs/so/versioned.so /tmp/writeork-rwx.so;
+phoff=$(readelf -hW test_programs/so/versioned.so | awk '/Start of program headers/ {print $5}');
+phndx=$(readelf -lW test_programs/so/versioned.so | awk '/^Program Headers/ {p = 1; next} p && $1 == "GNU_STACK" {print n; exit} p && $1 ~ /^[A-Z_]+$/ && $1 != "Type" {n++}');
+printf '\x07' | dd of=/tmp/writeork-rwx.so bs=1 seek=$((phoff + phndx * 56 + 4)) conv=notrunc 2> /dev/null;
+if target/debug/writeork --strict -l /tmp/writeork-rwx.so | grep -q "^ Note: segment $phndx is readable, writable and executable" \
+&& target/debug/writeork --checksec /tmp/writeork-rwx.so | grep -q "^RWX:      yes (see -l)$" \
+&& target/debug/writeork --checksec test_programs/so/versioned.so | grep -q "^RWX:      no$" \
+&& target/debug/writeork --packing /tmp/writeork-wtext.so | grep -q "^  suspicious: LOAD segment 1 is readable, writable and executable$"; then
+    echo "Tests pass: rwx segments";
+else
+    echo "Tests fail: rwx segments";
+fi;
+
+# --checksec sums up text relocations, RWX segments and _FORTIFY_SOURCE; here
+# the tag of DT_RELACOUNT is patched to DT_TEXTREL
+cp test_programs/so/versioned.so /tmp/writeork-textrel.so;
+dynoff=$(readelf -SW test_programs/so/versioned.so | awk '$2 == ".dynamic" {print $5}');
+dynndx=$(readelf -dW test_programs/so/versioned.so | awk '/^ 0x/ {n++} /\(RELACOUNT\)/ {print n - 1; exit}');
+printf '\x16\x00\x00\x00\x00\x00\x00\x00' | dd of=/tmp/writeork-textrel.so bs=1 seek=$((0x$dynoff + dynndx * 16)) conv=notrunc 2> /dev/null;
+if [ "$(target/debug/writeork --checksec /tmp/writeork-textrel.so)" = "TEXTREL:  yes (see -d)
+RWX:      no
+FORTIFY:  no" ] \
+&& [ "$(target/debug/writeork --checksec test_programs/obj/obj.o)" = "TEXTREL:  no
+RWX:      no
+FORTIFY:  unknown (no dynamic symbols)" ]; then
+    echo "Tests pass: checksec";
+else
+    echo "Tests fail: checksec";
+fi;
+
+# DT_RELACOUNT is decoded by -d and checked against the relative relocations
+# in DT_RELA; here the count of the last but one entry is patched to 5
+cp test_programs/so/versioned.so /tmp/writeork-relacount.so;
+printf '\x05' | dd of=/tmp/writeork-relacount.so bs=1 seek=12144 conv=notrunc 2> /dev/null;
+if diff -q <(target/debug/writeork -d test_programs/so/versioned.so) <(readelf -d test_programs/so/versioned.so) > /dev/null \
+&& target/debug/writeork --validate /tmp/writeork-relacount.so 2> /dev/null | grep -q "^  DT_RELACOUNT is 5, but DT_RELA has 3 relative relocations$" \
+&& ! target/debug/writeork --validate test_programs/so/versioned.so 2>&1 | grep -q "RELACOUNT"; then
+    echo "Tests pass: relacount";
+else
+    echo "Tests fail: relacount";
+fi;
+
+# The interpreter comes from PT_INTERP, or from .interp once the program
+# headers are gone (e_phnum patched to 0); --validate reports the two
+# disagreeing when PT_INTERP is moved one byte into the path
+exe=test_programs/exe/interp;
+cp $exe /tmp/writeork-nophdrs;
+printf '\x00\x00' | dd of=/tmp/writeork-nophdrs bs=1 seek=56 conv=notrunc 2> /dev/null;
+cp $exe /tmp/writeork-interp;
+printf '\x39' | dd of=/tmp/writeork-interp bs=1 seek=128 conv=notrunc 2> /dev/null;
+if target/debug/writeork -l $exe | grep -q "^      \[Requesting program interpreter: /lib64/ld-linux-x86-64.so.2\]$" \
+&& target/debug/writeork -l /tmp/writeork-nophdrs | grep -q "^The .interp section requests program interpreter: /lib64/ld-linux-x86-64.so.2$" \
+&& target/debug/writeork --validate /tmp/writeork-interp 2> /dev/null | grep -q "^  PT_INTERP requests 'lib64/ld-linux-x86-64.so.2', but .interp holds '/lib64/ld-linux-x86-64.so.2'$" \
+&& ! target/debug/writeork --validate $exe 2>&1 | grep -q "PT_INTERP"; then
+    echo "Tests pass: interpreter";
+else
+    echo "Tests fail: interpreter";
+fi;
+
+# --offsets-table gives file offsets, which with --offset count from the
+# start of the containing file
+{ head -c 4096 /dev/zero; cat test_programs/so/versioned.so; } > /tmp/writeork-offsets.bin;
+if [ "$(target/debug/writeork --offsets-table test_programs/le32/min.o)" = "
+File offsets:
+  0x0000001c  e_phoff, which holds 0x0
+  0x00000020  e_shoff, which holds 0x90
+              no program header table
+  0x00000090  the section header table, 5 entries of 40 bytes, to 0x158" ] \
+&& [ "$(target/debug/writeork --offset=0x1000 --offsets-table /tmp/writeork-offsets.bin 2> /dev/null)" = "
+File offsets:
+  0x00001020  e_phoff, which holds 0x40
+  0x00001028  e_shoff, which holds 0x35c0
+  0x00001040  the program header table, 9 entries of 56 bytes, to 0x1238
+  0x000045c0  the section header table, 29 entries of 64 bytes, to 0x4d00" ]; then
+    echo "Tests pass: offsets table";
+else
+    echo "Tests fail: offsets table";
+fi;
+
+# -d lists the directories of DT_RUNPATH and DT_RPATH one per line, with
+# $ORIGIN as written; the RPATH copy has the tag of the first entry patched
+runpath=test_programs/so/librunpath.so;
+cp $runpath /tmp/writeork-rpath.so;
+printf '\x0f' | dd of=/tmp/writeork-rpath.so bs=1 seek=3904 conv=notrunc 2> /dev/null;
+if [ "$(target/debug/writeork -d $runpath | sed -n 4,6p)" = ' 0x000000000000001d (RUNPATH)            Library runpath: [$ORIGIN/../lib:/opt/acme/lib]
+      [0] $ORIGIN/../lib
+      [1] /opt/acme/lib' ] \
+&& [ "$(target/debug/writeork -d /tmp/writeork-rpath.so | sed -n 4,6p)" = ' 0x000000000000000f (RPATH)              Library rpath: [$ORIGIN/../lib:/opt/acme/lib]
+      [0] $ORIGIN/../lib
+      [1] /opt/acme/lib' ]; then
+    echo "Tests pass: search paths";
+else
+    echo "Tests fail: search paths";
+fi;
+
+# --validate reports PT_LOAD entries out of address order; here the third
+# LOAD is moved from 0x2000 to 0x5000, past the fourth
+cp test_programs/so/versioned.so /tmp/writeork-loadorder.so;
+printf '\x50' | dd of=/tmp/writeork-loadorder.so bs=1 seek=193 conv=notrunc 2> /dev/null;
+if target/debug/writeork --validate /tmp/writeork-loadorder.so 2> /dev/null | grep -q "^  LOAD segment \[03\] at 0x3dd8 comes after LOAD segment \[02\] at 0x5000, but loadable segments must be in ascending order of address$" \
+&& ! target/debug/writeork --validate test_programs/so/versioned.so 2>&1 | grep -q "ascending order"; then
+    echo "Tests pass: load order";
+else
+    echo "Tests fail: load order";
+fi;
+
+# A section offset of 2^63 can't be seeked to; the section is skipped with a
+# warning instead of panicking
+cp test_programs/obj/obj.o /tmp/writeork-bigoff.o;
+printf '\x00\x00\x00\x00\x00\x00\x00\x80' | dd of=/tmp/writeork-bigoff.o bs=1 seek=2112 conv=notrunc 2> /dev/null;
+if target/debug/writeork -x 5 /tmp/writeork-bigoff.o 2>&1 | grep -q "^Warning: section '.text.print_greeting' was not dumped: Offset 0x8000000000000000 can't be read from$" \
+&& target/debug/writeork --entropy /tmp/writeork-bigoff.o > /dev/null 2>&1 \
+&& target/debug/writeork --dump-symbol print_greeting /tmp/writeork-bigoff.o 2>&1 | grep -q "^Error: Offset 0x8000000000000000 can't be read from$"; then
+    echo "Tests pass: unreadable offset";
+else
+    echo "Tests fail: unreadable offset";
+fi;
+
+# The Go toolchain writes its note owner as "Go\0\0"; it has to be recognised
+# in a binary Go actually built, not just a hand-made note
+if [ ! -e /bin/cbt ]; then
+    echo "Tests skipped: go build id (no Go binary at /bin/cbt)";
+elif target/debug/writeork -n /bin/cbt | grep -q "^  Go                   0x[0-9a-f]*	GO BUILDID$" \
+&& target/debug/writeork -n /bin/cbt | grep -q "^    Build ID: [A-Za-z0-9_/-]*$" \
+&& ! target/debug/writeork -n /bin/cbt | grep -q "Unknown note type"; then
+    echo "Tests pass: go build id";
+else
+    echo "Tests fail: go build id";
+fi;
@@ -0,0 +1,214 @@
+#!/usr/bin/env python3
+"""Generates minimal relocatable ELF files used as test fixtures.
+
+Each fixture contains the ELF header and five sections (NULL, .text,
+.symtab, .strtab, .shstrtab) with a single global function symbol `foo`
+covering the whole of .text. The output is fully deterministic, so the
+generated files are committed and only need to be regenerated when this
+script changes. Two fixtures add a section after .shstrtab: le64/xindex.o a
+.symtab_shndx that gives `foo` its section index, and arm/exidx.o, an ARM
+build of le32, an .ARM.exidx with three entries:
+
+    python3 test_programs/gen_fixtures.py
+"""
+
+import os
+import struct
+
+ET_REL = 1
+EM_386 = 3
+EM_ARM = 40
+EM_PPC64 = 21
+EM_X86_64 = 62
+
+SHT_PROGBITS = 1
+SHT_SYMTAB = 2
+SHT_STRTAB = 3
+SHT_SYMTAB_SHNDX = 18
+SHT_ARM_EXIDX = 0x70000001
+
+SHF_ALLOC = 0x2
+SHF_EXECINSTR = 0x4
+
+STB_GLOBAL = 1
+STT_FUNC = 2
+
+SHN_XINDEX = 0xffff
+
+FIXTURES = [
+    # (directory, bits, byte order, machine, .text contents)
+    ("le32", 32, "<", EM_386, b"\x8b\x44\x24\x04\xc3"),
+    ("le64", 64, "<", EM_X86_64, b"\x89\xf8\xc3"),
+    ("be64", 64, ">", EM_PPC64, b"\x4e\x80\x00\x20"),
+]
+
+EI_CLASS = 4
+EI_DATA = 5
+
+E_SHOFF = 40
+E_SHSTRNDX = 62
+
+# sh_entsize of .symtab in the le64 fixture: section headers start at 0xa0,
+# .symtab is the third one and sh_entsize is the last field of 64 bytes.
+SYMTAB_ENTSIZE = 0xa0 + 2 * 64 + 56
+# sh_addralign of .text, the second section header.
+TEXT_ADDRALIGN = 0xa0 + 64 + 48
+
+INVALID_FIXTURES = [
+    # (file name, offset to clobber, new bytes), based on the le64 fixture
+    ("classnone.o", EI_CLASS, b"\0"),
+    ("datanone.o", EI_DATA, b"\0"),
+    # Section headers beyond 4 GiB, which doesn't fit in a 32-bit usize.
+    ("shoff4g.o", E_SHOFF, struct.pack("<Q", 0x100000040)),
+    # No entry size, which has to fall back to the size of Elf64_Sym.
+    ("symentsize0.o", SYMTAB_ENTSIZE, struct.pack("<Q", 0)),
+    # An entry size smaller than Elf64_Sym, which can't be right.
+    ("symentsize7.o", SYMTAB_ENTSIZE, struct.pack("<Q", 7)),
+    # An alignment which is not a power of two.
+    ("align3.o", TEXT_ADDRALIGN, struct.pack("<Q", 3)),
+    # A section name string table past the end of the section table.
+    ("shstrndx9.o", E_SHSTRNDX, struct.pack("<H", 9)),
+]
+
+
+def strtab(names):
+    table = b"\0"
+    offsets = {}
+    for name in names:
+        offsets[name] = len(table)
+        table += name.encode() + b"\0"
+    return table, offsets
+
+
+# Entries for functions at 0x8000, 0x8010 and 0x8020 (prel31 offsets from
+# the entry at 0x9000): one that can't be unwound, one with inline unwinding
+# instructions and one pointing into .ARM.extab.
+EXIDX_ADDR = 0x9000
+EXIDX = struct.pack(
+    "<IIIIII", 0x7ffff000, 1, 0x7ffff008, 0x80b0b0b0, 0x7ffff010, 0x8c)
+
+
+def build(bits, order, machine, text, extra=(), foo_shndx=1):
+    """`extra` holds sections to add after .shstrtab, as (name, type, addr,
+    data, link, entsize)."""
+    is64 = bits == 64
+    ehsize = 64 if is64 else 52
+    shentsize = 64 if is64 else 40
+    symentsize = 24 if is64 else 16
+    word_align = 8 if is64 else 4
+
+    names = [".text", ".symtab", ".strtab", ".shstrtab"]
+    names += [name for name, _, _, _, _, _ in extra]
+    shstrtab, shnames = strtab(names)
+    symstrtab, symnames = strtab(["foo"])
+
+    if is64:
+        sym_fmt = order + "IBBHQQ"
+        null_sym = struct.pack(sym_fmt, 0, 0, 0, 0, 0, 0)
+        foo_sym = struct.pack(
+            sym_fmt, symnames["foo"], (STB_GLOBAL << 4) | STT_FUNC, 0,
+            foo_shndx, 0, len(text))
+    else:
+        sym_fmt = order + "IIIBBH"
+        null_sym = struct.pack(sym_fmt, 0, 0, 0, 0, 0, 0)
+        foo_sym = struct.pack(
+            sym_fmt, symnames["foo"], 0, len(text),
+            (STB_GLOBAL << 4) | STT_FUNC, 0, foo_shndx)
+    symtab = null_sym + foo_sym
+
+    sections = [
+        (".text", text, 16),
+        (".symtab", symtab, word_align),
+        (".strtab", symstrtab, 1),
+        (".shstrtab", shstrtab, 1)]
+    sections += [(name, data, 4) for name, _, _, data, _, _ in extra]
+
+    # Lay out section contents right after the ELF header.
+    body = b""
+    offsets = {}
+    for name, data, alignment in sections:
+        pad = -(ehsize + len(body)) % alignment
+        body += b"\0" * pad
+        offsets[name] = ehsize + len(body)
+        body += data
+    pad = -(ehsize + len(body)) % word_align
+    body += b"\0" * pad
+    shoff = ehsize + len(body)
+
+    if is64:
+        sh_fmt = order + "IIQQQQIIQQ"
+    else:
+        sh_fmt = order + "IIIIIIIIII"
+
+    def shdr(name, sh_type, flags, offset, size, link, info, addralign,
+             entsize, addr=0):
+        return struct.pack(
+            sh_fmt, shnames[name] if name else 0, sh_type, flags, addr,
+            offset, size, link, info, addralign, entsize)
+
+    shdrs = [
+        shdr(None, 0, 0, 0, 0, 0, 0, 0, 0),
+        shdr(".text", SHT_PROGBITS, SHF_ALLOC | SHF_EXECINSTR,
+             offsets[".text"], len(text), 0, 0, 16, 0),
+        shdr(".symtab", SHT_SYMTAB, 0, offsets[".symtab"], len(symtab),
+             3, 1, word_align, symentsize),
+        shdr(".strtab", SHT_STRTAB, 0, offsets[".strtab"], len(symstrtab),
+             0, 0, 1, 0),
+        shdr(".shstrtab", SHT_STRTAB, 0, offsets[".shstrtab"],
+             len(shstrtab), 0, 0, 1, 0),
+    ]
+    for name, sh_type, addr, data, link, entsize in extra:
+        shdrs.append(shdr(name, sh_type, 0, offsets[name], len(data), link, 0,
+                          4, entsize, addr))
+
+    ident = b"\x7fELF" + bytes([
+        2 if is64 else 1,
+        1 if order == "<" else 2,
+        1,
+    ]) + b"\0" * 9
+    if is64:
+        ehdr_fmt = order + "16sHHIQQQIHHHHHH"
+    else:
+        ehdr_fmt = order + "16sHHIIIIIHHHHHH"
+    ehdr = struct.pack(
+        ehdr_fmt, ident, ET_REL, machine, 1, 0, 0, shoff, 0, ehsize, 0, 0,
+        shentsize, len(shdrs), 4)
+
+    return ehdr + body + b"".join(shdrs)
+
+
+def main():
+    root = os.path.dirname(os.path.abspath(__file__))
+    for directory, bits, order, machine, text in FIXTURES:
+        path = os.path.join(root, directory)
+        if not os.path.isdir(path):
+            os.makedirs(path)
+        with open(os.path.join(path, "min.o"), "wb") as f:
+            f.write(build(bits, order, machine, text))
+    # One word per symbol, and the real index of `foo`
+    symtab_shndx = struct.pack("<II", 0, 1)
+    with open(os.path.join(root, "le64", "xindex.o"), "wb") as f:
+        f.write(build(
+            64, "<", EM_X86_64, b"\x89\xf8\xc3",
+            [(".symtab_shndx", SHT_SYMTAB_SHNDX, 0, symtab_shndx, 2, 4)],
+            SHN_XINDEX))
+    path = os.path.join(root, "arm")
+    if not os.path.isdir(path):
+        os.makedirs(path)
+    with open(os.path.join(path, "exidx.o"), "wb") as f:
+        f.write(build(
+            32, "<", EM_ARM, b"\x8b\x44\x24\x04\xc3",
+            [(".ARM.exidx", SHT_ARM_EXIDX, EXIDX_ADDR, EXIDX, 0, 0)]))
+
+    path = os.path.join(root, "invalid")
+    if not os.path.isdir(path):
+        os.makedirs(path)
+    for name, offset, value in INVALID_FIXTURES:
+        data = bytearray(build(64, "<", EM_X86_64, b"\x89\xf8\xc3"))
+        data[offset:offset + len(value)] = value
+        with open(os.path.join(path, name), "wb") as f:
+            f.write(data)
+
+
+if __name__ == "__main__":
+    main()
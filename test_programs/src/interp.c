@@ -0,0 +1,10 @@
+/* A position-independent executable with a program interpreter, for the
+ * PT_INTERP and .interp checks. Rebuild with:
+ *
+ *     gcc -O1 -nostdlib -pie -fPIE -Wl,-z,noseparate-code \
+ *         -o test_programs/exe/interp test_programs/src/interp.c
+ */
+void _start(void) {
+    for (;;) {
+    }
+}
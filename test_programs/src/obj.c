@@ -0,0 +1,30 @@
+/* A relocatable object with many sections and relocations of several kinds,
+ * used to check that every mode copes with files that have no program
+ * headers. Rebuild with:
+ *
+ *     gcc -c -O1 -fPIC -ffunction-sections -fdata-sections \
+ *         -o test_programs/obj/obj.o test_programs/src/obj.c
+ */
+#include <stdio.h>
+#include <string.h>
+
+extern int external_counter;
+
+static const char greeting[] = "hello from a relocatable object";
+int counter = 1;
+int zeroed[16];
+__thread int per_thread = 2;
+__thread char per_thread_buffer[64];
+const char *names[] = { "first", "second", "third" };
+
+static int add(int a, int b) {
+    return a + b + counter;
+}
+
+int print_greeting(void) {
+    external_counter++;
+    per_thread_buffer[per_thread]++;
+    return printf("%s %d\n", greeting, add(zeroed[0], (int)strlen(names[1])));
+}
+
+int (*callback)(void) = print_greeting;
@@ -0,0 +1,10 @@
+/* A shared library with a DT_RUNPATH of two directories, one relative to
+ * $ORIGIN, for the search paths of -d. Rebuild with:
+ *
+ *     gcc -O1 -nostdlib -shared -Wl,-z,noseparate-code \
+ *         -Wl,-rpath,'$ORIGIN/../lib:/opt/acme/lib' \
+ *         -o test_programs/so/librunpath.so test_programs/src/runpath.c
+ */
+int runpath_version(void) {
+    return 1;
+}
@@ -0,0 +1,9 @@
+/* A shared library with a soname, libsoname.so.1, installed under its full
+ * version for the soname check of --validate. Rebuild with:
+ *
+ *     gcc -O1 -nostdlib -shared -Wl,-z,noseparate-code,-soname,libsoname.so.1 \
+ *         -o test_programs/so/libsoname.so.1.2 test_programs/src/soname.c
+ */
+int soname_version(void) {
+    return 1;
+}
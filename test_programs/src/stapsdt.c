@@ -0,0 +1,41 @@
+/* A shared library with two SystemTap (USDT) probes laid out the way
+ * <sys/sdt.h> lays them out, one of them guarded by a semaphore, for -n.
+ * The macro stands in for the header so nothing has to be installed.
+ * Rebuild with:
+ *
+ *     gcc -O1 -nostdlib -shared -Wl,-z,noseparate-code \
+ *         -o test_programs/so/stapsdt.so test_programs/src/stapsdt.c
+ */
+__attribute__((section(".probes"))) unsigned short writeork_request__semaphore;
+
+#define PROBE_NOTE(provider, name, args, semaphore)                       \
+    __asm__ __volatile__(                                                 \
+        "990: nop\n"                                                      \
+        ".pushsection .note.stapsdt,\"?\",\"note\"\n"                     \
+        ".balign 4\n"                                                     \
+        ".4byte 992f-991f, 994f-993f, 3\n"                                \
+        "991: .asciz \"stapsdt\"\n"                                       \
+        "992: .balign 4\n"                                                \
+        "993: .8byte 990b\n"                                              \
+        ".8byte _.stapsdt.base\n"                                         \
+        ".8byte " semaphore "\n"                                          \
+        ".asciz \"" provider "\"\n"                                       \
+        ".asciz \"" name "\"\n"                                           \
+        ".asciz \"" args "\"\n"                                           \
+        "994: .balign 4\n"                                                \
+        ".popsection\n"                                                   \
+        ".ifndef _.stapsdt.base\n"                                        \
+        ".pushsection .stapsdt.base,\"aG\",\"progbits\",.stapsdt.base,comdat\n" \
+        ".weak _.stapsdt.base\n"                                          \
+        ".hidden _.stapsdt.base\n"                                        \
+        "_.stapsdt.base: .space 1\n"                                      \
+        ".size _.stapsdt.base, 1\n"                                       \
+        ".popsection\n"                                                   \
+        ".endif\n")
+
+int handle(int fd) {
+    PROBE_NOTE("writeork", "start", "", "0");
+    __asm__ __volatile__("" : : "r"(fd));
+    PROBE_NOTE("writeork", "request", "-4@%edi", "writeork_request__semaphore");
+    return fd + 1;
+}
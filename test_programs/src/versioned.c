@@ -0,0 +1,16 @@
+/* A shared library with versioned symbols of its own and versioned
+ * dependencies on libc, for the symbol versioning modes. Rebuild with:
+ *
+ *     gcc -shared -fPIC -O1 -Wl,--version-script=test_programs/src/versioned.map \
+ *         -o test_programs/so/versioned.so test_programs/src/versioned.c
+ */
+#include <stdio.h>
+#include <string.h>
+
+int old_api(const char *s) {
+    return (int)strlen(s);
+}
+
+int new_api(const char *s) {
+    return printf("%s\n", s);
+}